@@ -0,0 +1,59 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use eheh_lang::{
+    allocator::{self, Strategy},
+    evaluator::Evaluator,
+    lexer::Lexer,
+    objects::environment::Environment,
+    parser::Parser,
+};
+
+const SOURCE: &str = r#"
+let fib = fn(n) {
+    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+};
+fib(22)
+"#;
+
+const ITERATIONS: u32 = 5;
+
+fn run() -> String {
+    let mut lexer = Lexer::new(SOURCE);
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+
+    Evaluator::eval(&program, environment).unwrap().to_string()
+}
+
+fn main() {
+    for strategy in Strategy::ALL {
+        allocator::set_allocator(strategy.allocator());
+
+        // warm up
+        let result = run();
+
+        let mut best = Duration::MAX;
+        for _ in 0..ITERATIONS {
+            let start = Instant::now();
+            assert_eq!(run(), result);
+            best = best.min(start.elapsed());
+        }
+
+        let stats = allocator::stats();
+        println!(
+            "{:<6} fib(22) = {}, best of {}: {:>9.2?}, {} allocations, {} reused, {} bytes",
+            strategy.name(),
+            result,
+            ITERATIONS,
+            best,
+            stats.allocations,
+            stats.reused,
+            stats.bytes
+        );
+    }
+}
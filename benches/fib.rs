@@ -0,0 +1,41 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use eheh_lang::{
+    evaluator::Evaluator, lexer::Lexer, objects::environment::Environment, parser::Parser,
+};
+
+const SOURCE: &str = r#"
+let fib = fn(n) {
+    if (n < 2) { n } else { fib(n - 1) + fib(n - 2) }
+};
+fib(25)
+"#;
+
+const ITERATIONS: u32 = 5;
+
+fn run() -> String {
+    let mut lexer = Lexer::new(SOURCE);
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+
+    Evaluator::eval(&program, environment).unwrap().to_string()
+}
+
+fn main() {
+    // warm up
+    let result = run();
+
+    let mut best = Duration::MAX;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        assert_eq!(run(), result);
+        best = best.min(start.elapsed());
+    }
+
+    println!("fib(25) = {}, best of {}: {:.2?}", result, ITERATIONS, best);
+}
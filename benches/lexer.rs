@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+use eheh_lang::lexer::Lexer;
+
+const SNIPPET: &str = r#"
+// computes the nth fibonacci number
+const fib = fn(n: i32) -> i32 {
+    if (n <= 1) { return n; }
+    return fib(n - 1) + fib(n - 2);
+};
+
+/* a block comment
+   spanning multiple lines */
+let name = "eheh, the language";
+let total = 1_000_000 * 42 / (7 % 3) - 12;
+let is_valid = !(total >= 10 && total != 12) || false;
+defer name;
+"#;
+
+const TARGET_SIZE: usize = 4 * 1024 * 1024;
+const ITERATIONS: u32 = 10;
+
+fn generate_source() -> String {
+    let mut source = String::with_capacity(TARGET_SIZE + SNIPPET.len());
+
+    while source.len() < TARGET_SIZE {
+        source.push_str(SNIPPET);
+    }
+
+    source
+}
+
+fn lex(source: &str) -> usize {
+    Lexer::new(source).count()
+}
+
+fn main() {
+    let source = generate_source();
+    let megabytes = source.len() as f64 / (1024.0 * 1024.0);
+
+    // warm up
+    let tokens = lex(&source);
+
+    let mut best = Duration::MAX;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        assert_eq!(lex(&source), tokens);
+        best = best.min(start.elapsed());
+    }
+
+    println!(
+        "lexer: {} tokens in {:.2} MB, best of {}: {:.2?} ({:.1} MB/s)",
+        tokens,
+        megabytes,
+        ITERATIONS,
+        best,
+        megabytes / best.as_secs_f64()
+    );
+}
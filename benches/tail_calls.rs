@@ -0,0 +1,27 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use eheh_lang::{
+    evaluator::Evaluator, lexer::Lexer, objects::environment::Environment, parser::Parser,
+};
+
+/// Runs on the stack of the main thread: without tail calls reusing the frame of the function
+/// they end, the loop would overflow it long before the end.
+const SOURCE: &str = r#"
+let count = fn(n, total) {
+    if (n == 0) { total } else { count(n - 1, total + 1) }
+};
+count(10000000, 0)
+"#;
+
+fn main() {
+    let start = Instant::now();
+
+    let mut lexer = Lexer::new(SOURCE);
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let result = Evaluator::eval(&program, environment).unwrap().to_string();
+
+    assert_eq!(result, "10000000");
+    println!("count(10000000) = {}: {:.2?}", result, start.elapsed());
+}
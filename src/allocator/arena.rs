@@ -0,0 +1,60 @@
+use crate::{
+    objects::{boolean::Boolean, integer::Integer},
+    traits::object::Object,
+};
+
+use super::{Allocator, Stats, Strategy};
+
+/// Frees nothing until the evaluation is over, then everything at once: no time is spent
+/// freeing while a short script runs, but the memory of a long one grows with every temporary.
+#[derive(Debug)]
+pub struct Arena {
+    retained: Vec<Box<dyn Object>>,
+    stats: Stats,
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Self {
+            retained: Vec::new(),
+            stats: Stats::new(Strategy::Arena),
+        }
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Allocator for Arena {
+    fn integer(&mut self, value: i64) -> Box<dyn Object> {
+        self.stats.record(Box::new(Integer::new(value)))
+    }
+
+    fn boolean(&mut self, value: bool) -> Box<dyn Object> {
+        self.stats.record(Box::new(Boolean::new(value)))
+    }
+
+    fn allocate(&mut self, object: Box<dyn Object>) -> Box<dyn Object> {
+        self.stats.record(object)
+    }
+
+    fn clone_object(&mut self, object: &dyn Object) -> Box<dyn Object> {
+        self.stats.record(object.clone_boxed())
+    }
+
+    fn free(&mut self, object: Box<dyn Object>) {
+        self.retained.push(object);
+    }
+
+    fn reset(&mut self) {
+        self.stats.freed += self.retained.len();
+        self.retained.clear();
+    }
+
+    fn stats(&self) -> Stats {
+        self.stats
+    }
+}
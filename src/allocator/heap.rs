@@ -0,0 +1,52 @@
+use crate::{
+    objects::{boolean::Boolean, integer::Integer},
+    traits::object::Object,
+};
+
+use super::{Allocator, Stats, Strategy};
+
+/// The general purpose allocator, every object is allocated and freed on its own.
+#[derive(Debug, Clone)]
+pub struct Heap {
+    stats: Stats,
+}
+
+impl Heap {
+    pub fn new() -> Self {
+        Self {
+            stats: Stats::new(Strategy::Heap),
+        }
+    }
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Allocator for Heap {
+    fn integer(&mut self, value: i64) -> Box<dyn Object> {
+        self.stats.record(Box::new(Integer::new(value)))
+    }
+
+    fn boolean(&mut self, value: bool) -> Box<dyn Object> {
+        self.stats.record(Box::new(Boolean::new(value)))
+    }
+
+    fn allocate(&mut self, object: Box<dyn Object>) -> Box<dyn Object> {
+        self.stats.record(object)
+    }
+
+    fn clone_object(&mut self, object: &dyn Object) -> Box<dyn Object> {
+        self.stats.record(object.clone_boxed())
+    }
+
+    fn free(&mut self, object: Box<dyn Object>) {
+        self.stats.freed += 1;
+    }
+
+    fn stats(&self) -> Stats {
+        self.stats
+    }
+}
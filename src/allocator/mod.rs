@@ -0,0 +1,178 @@
+use std::{cell::RefCell, fmt::Display};
+
+use crate::traits::object::{Object, ObjectType};
+
+use self::{arena::Arena, heap::Heap, pool::Pool};
+
+pub mod arena;
+pub mod heap;
+pub mod pool;
+
+/// Hands out the objects of the evaluator and takes back the ones it is done with, see
+/// `set_allocator`.
+pub trait Allocator {
+    fn integer(&mut self, value: i64) -> Box<dyn Object>;
+
+    fn boolean(&mut self, value: bool) -> Box<dyn Object>;
+
+    /// Takes an object the evaluator built itself, e.g: a function.
+    fn allocate(&mut self, object: Box<dyn Object>) -> Box<dyn Object>;
+
+    /// A copy of `object`, e.g: the value of a variable being read.
+    fn clone_object(&mut self, object: &dyn Object) -> Box<dyn Object>;
+
+    /// Takes back an object nothing refers to anymore.
+    fn free(&mut self, object: Box<dyn Object>);
+
+    /// Called once an evaluation is over.
+    fn reset(&mut self) {}
+
+    fn stats(&self) -> Stats;
+}
+
+/// The allocators the interpreter ships with.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Strategy {
+    /// Every object is allocated and freed on its own.
+    #[default]
+    Heap,
+    /// The objects freed during an evaluation are kept until it is over, then freed at once.
+    Arena,
+    /// The integers and booleans freed are reused, in slots of their size.
+    Pool,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Heap, Strategy::Arena, Strategy::Pool];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Strategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::Heap => "heap",
+            Strategy::Arena => "arena",
+            Strategy::Pool => "pool",
+        }
+    }
+
+    pub fn allocator(self) -> Box<dyn Allocator> {
+        match self {
+            Strategy::Heap => Box::new(Heap::new()),
+            Strategy::Arena => Box::new(Arena::new()),
+            Strategy::Pool => Box::new(Pool::new()),
+        }
+    }
+}
+
+/// What an allocator did since it was set, see `alloc_stats()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub strategy: &'static str,
+    /// The objects handed out, the reused ones included.
+    pub allocations: usize,
+    pub reused: usize,
+    /// The objects given back to the system.
+    pub freed: usize,
+    /// The size of the objects allocated, not of what they point to.
+    pub bytes: usize,
+}
+
+impl Stats {
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy: strategy.name(),
+            allocations: 0,
+            reused: 0,
+            freed: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Counts an object that was just allocated.
+    pub fn record(&mut self, object: Box<dyn Object>) -> Box<dyn Object> {
+        self.allocations += 1;
+        self.bytes += std::mem::size_of_val(&*object);
+        object
+    }
+}
+
+impl Object for Stats {
+    fn t(&self) -> ObjectType {
+        ObjectType::AllocStats
+    }
+
+    fn inspect(&self) -> String {
+        self.to_string()
+    }
+
+    fn as_any_ref(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(self) -> Box<dyn std::any::Any> {
+        Box::new(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Object> {
+        Box::new(*self)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "alloc_stats {{ strategy: {}, allocations: {}, reused: {}, freed: {}, bytes: {} }}",
+            self.strategy, self.allocations, self.reused, self.freed, self.bytes
+        )
+    }
+}
+
+thread_local! {
+    static ALLOCATOR: RefCell<Box<dyn Allocator>> = RefCell::new(Strategy::default().allocator());
+}
+
+/// Sets the allocator of the evaluations of this thread, returns the previous one.
+pub fn set_allocator(allocator: Box<dyn Allocator>) -> Box<dyn Allocator> {
+    ALLOCATOR.replace(allocator)
+}
+
+pub fn integer(value: i64) -> Box<dyn Object> {
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.integer(value))
+}
+
+pub fn boolean(value: bool) -> Box<dyn Object> {
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.boolean(value))
+}
+
+pub fn allocate(object: Box<dyn Object>) -> Box<dyn Object> {
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.allocate(object))
+}
+
+pub fn clone_object(object: &dyn Object) -> Box<dyn Object> {
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.clone_object(object))
+}
+
+pub fn free(object: Box<dyn Object>) {
+    // dropping the object may drop others, none of them uses the allocator
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.free(object))
+}
+
+pub fn reset() {
+    ALLOCATOR.with_borrow_mut(|allocator| allocator.reset())
+}
+
+pub fn stats() -> Stats {
+    ALLOCATOR.with_borrow(|allocator| allocator.stats())
+}
+
+#[cfg(test)]
+mod test;
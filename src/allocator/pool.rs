@@ -0,0 +1,96 @@
+use crate::{
+    objects::{boolean::Boolean, integer::Integer},
+    traits::object::Object,
+};
+
+use super::{Allocator, Stats, Strategy};
+
+/// The slots kept for each type, the objects freed past it are given back to the system.
+pub const CAPACITY: usize = 1024;
+
+/// Reuses the integers and booleans freed, the objects of every other type are allocated and
+/// freed on their own.
+#[derive(Debug)]
+pub struct Pool {
+    integers: Vec<Box<dyn Object>>,
+    booleans: Vec<Box<dyn Object>>,
+    stats: Stats,
+}
+
+impl Pool {
+    pub fn new() -> Self {
+        Self {
+            integers: Vec::new(),
+            booleans: Vec::new(),
+            stats: Stats::new(Strategy::Pool),
+        }
+    }
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Allocator for Pool {
+    fn integer(&mut self, value: i64) -> Box<dyn Object> {
+        let Some(mut slot) = self.integers.pop() else {
+            return self.stats.record(Box::new(Integer::new(value)));
+        };
+
+        if let Some(integer) = slot.as_any_mut().downcast_mut::<Integer>() {
+            integer.value = value;
+        }
+        self.stats.allocations += 1;
+        self.stats.reused += 1;
+        slot
+    }
+
+    fn boolean(&mut self, value: bool) -> Box<dyn Object> {
+        let Some(mut slot) = self.booleans.pop() else {
+            return self.stats.record(Box::new(Boolean::new(value)));
+        };
+
+        if let Some(boolean) = slot.as_any_mut().downcast_mut::<Boolean>() {
+            boolean.value = value;
+        }
+        self.stats.allocations += 1;
+        self.stats.reused += 1;
+        slot
+    }
+
+    fn allocate(&mut self, object: Box<dyn Object>) -> Box<dyn Object> {
+        self.stats.record(object)
+    }
+
+    fn clone_object(&mut self, object: &dyn Object) -> Box<dyn Object> {
+        if let Some(integer) = object.as_any_ref().downcast_ref::<Integer>() {
+            return self.integer(integer.value);
+        }
+        if let Some(boolean) = object.as_any_ref().downcast_ref::<Boolean>() {
+            return self.boolean(boolean.value);
+        }
+
+        self.stats.record(object.clone_boxed())
+    }
+
+    fn free(&mut self, object: Box<dyn Object>) {
+        let slots = if object.as_any_ref().is::<Integer>() {
+            Some(&mut self.integers)
+        } else if object.as_any_ref().is::<Boolean>() {
+            Some(&mut self.booleans)
+        } else {
+            None
+        };
+
+        match slots {
+            Some(slots) if slots.len() < CAPACITY => slots.push(object),
+            _ => self.stats.freed += 1,
+        }
+    }
+
+    fn stats(&self) -> Stats {
+        self.stats
+    }
+}
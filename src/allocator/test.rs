@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        allocator::{self, pool, Allocator, Stats, Strategy},
+        evaluator::Evaluator,
+        lexer::Lexer,
+        objects::environment::Environment,
+        parser::Parser,
+    };
+
+    const CODE: &str = "
+        let fib = fn(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } };
+        let count = fn(n, even) { if n == 0 { even } else { count(n - 1, !even) } };
+        fib(15) + count(100, true) * 1000";
+
+    fn eval(input: &str) -> String {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+
+        Evaluator::eval(&program, environment).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_strategies() {
+        for strategy in Strategy::ALL {
+            allocator::set_allocator(strategy.allocator());
+
+            assert_eq!(eval(CODE), "1610", "{:?}", strategy);
+
+            let stats = allocator::stats();
+            assert_eq!(stats.strategy, strategy.name());
+            assert!(stats.allocations > 0, "{:?}", stats);
+            assert!(stats.freed <= stats.allocations, "{:?}", stats);
+
+            match strategy {
+                Strategy::Heap | Strategy::Arena => assert_eq!(stats.reused, 0),
+                // fib(15) alone frees thousands of integers
+                Strategy::Pool => assert!(stats.reused > stats.allocations / 2, "{:?}", stats),
+            }
+        }
+    }
+
+    #[test]
+    fn test_arena_frees_on_reset() {
+        let mut arena = Strategy::Arena.allocator();
+        for value in 0..10 {
+            let integer = arena.integer(value);
+            arena.free(integer);
+        }
+        assert_eq!(arena.stats().freed, 0);
+
+        arena.reset();
+        assert_eq!(
+            arena.stats(),
+            Stats {
+                strategy: "arena",
+                allocations: 10,
+                reused: 0,
+                freed: 10,
+                bytes: 80,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pool_reuses_slots() {
+        let mut pool = Strategy::Pool.allocator();
+
+        let integers: Vec<_> = (0..pool::CAPACITY as i64 + 10)
+            .map(|value| pool.integer(value))
+            .collect();
+        for integer in integers {
+            pool.free(integer);
+        }
+        // the slots past the capacity are given back
+        assert_eq!(pool.stats().freed, 10);
+
+        let integer = pool.integer(42);
+        assert_eq!(integer.to_string(), "42");
+        let boolean = pool.boolean(true);
+        pool.free(boolean);
+        assert_eq!(pool.boolean(false).to_string(), "false");
+
+        let stats = pool.stats();
+        assert_eq!(stats.reused, 2);
+        assert_eq!(stats.allocations, pool::CAPACITY + 13);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
@@ -15,7 +17,7 @@ impl BooleanExpression {
     }
 
     pub fn from_token(token: &Token) -> Self {
-        let value = token.literal.to_owned();
+        let value = token.literal().to_owned();
 
         Self {
             token: token.clone(),
@@ -25,18 +27,30 @@ impl BooleanExpression {
 }
 
 impl Expression for BooleanExpression {
-    fn eval(&self) -> String {
-        self.get_token_literal()
-    }
-
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Boolean(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Boolean(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Boolean(*self)
+    }
 }
 
 impl Node for BooleanExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -52,6 +66,7 @@ impl Node for BooleanExpression {
 
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for BooleanExpression {
     fn to_string(&self) -> String {
         self.value.to_string()
@@ -1,38 +1,69 @@
+use std::cell::Cell;
+
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
     types::ASTExpression,
 };
 
+use super::named_argument::NamedArgument;
+
 #[derive(Debug, Clone)]
 pub struct CallExpression {
     pub token: Token,
     pub function: ASTExpression,
     pub args: Vec<ASTExpression>,
+    /// The arguments passed by name, after the positional ones.
+    pub named_args: Vec<NamedArgument>,
+    /// Set by `resolver::resolve` when the value of the call is the value of the function it is
+    /// in, the call then reuses the frame of that function.
+    pub tail: Cell<bool>,
 }
 
 impl CallExpression {
-    pub fn new(token: Token, function: ASTExpression, args: Vec<ASTExpression>) -> Self {
+    pub fn new(
+        token: Token,
+        function: ASTExpression,
+        args: Vec<ASTExpression>,
+        named_args: Vec<NamedArgument>,
+    ) -> Self {
         Self {
             token,
             function,
             args,
+            named_args,
+            tail: Cell::new(false),
         }
     }
 }
 
 impl Expression for CallExpression {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Call(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Call(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Call(*self)
+    }
 }
 
 impl Node for CallExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.function.span().to(&self.token.span)
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -49,6 +80,7 @@ impl Node for CallExpression {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for CallExpression {
     fn to_string(&self) -> String {
         let mut call_str = String::new();
@@ -60,6 +92,7 @@ impl ToString for CallExpression {
                 self.args
                     .iter()
                     .map(|arg| { arg.to_string() })
+                    .chain(self.named_args.iter().map(|arg| arg.to_string()))
                     .collect::<Vec<String>>()
                     .join(", ")
             )
@@ -1,5 +1,9 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
     ast::statements::block_statement::BlockStatement,
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
@@ -14,6 +18,8 @@ pub struct FunctionLiteral {
     pub parameters: Vec<TypedIdentifier>,
     pub return_type: Option<Identifier>,
     pub body: BlockStatement,
+    /// The name of every slot of the frame of a call, set by `resolver::resolve`.
+    pub locals: RefCell<Rc<[String]>>,
 }
 
 impl FunctionLiteral {
@@ -32,22 +38,36 @@ impl FunctionLiteral {
             parameters,
             return_type,
             body,
+            locals: RefCell::default(),
         }
     }
 }
 
 impl Expression for FunctionLiteral {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Function(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Function(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Function(*self)
+    }
 }
 
 impl Node for FunctionLiteral {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.to(&self.body.span())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -62,12 +82,17 @@ impl Node for FunctionLiteral {
     }
 }
 
+#[allow(
+    clippy::single_char_add_str,
+    clippy::to_string_trait_impl,
+    clippy::useless_format
+)]
 impl ToString for FunctionLiteral {
     fn to_string(&self) -> String {
         let mut str = String::new();
 
         if let Some(visibility) = &self.visibility {
-            str.push_str(format!("{}", visibility.literal).as_str());
+            str.push_str(visibility.literal());
         }
 
         str.push_str("fn ");
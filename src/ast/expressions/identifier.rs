@@ -1,41 +1,73 @@
+use std::cell::Cell;
+
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
 
+/// Where a local of a function lives at runtime: in the frame `depth` calls out of the current
+/// one, at `index`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slot {
+    pub depth: usize,
+    pub index: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct Identifier {
     pub token: Token,
     pub value: String,
+    /// Set by `resolver::resolve`, `None` for globals which are looked up by name.
+    pub slot: Cell<Option<Slot>>,
 }
 
 impl Identifier {
     pub fn new(token: Token, value: String) -> Self {
-        Self { token, value }
+        Self {
+            token,
+            value,
+            slot: Cell::new(None),
+        }
     }
 
     pub fn from_token(token: &Token) -> Self {
-        let value = token.literal.to_owned();
+        let value = token.literal().to_owned();
 
         Self {
             token: token.clone(),
             value,
+            slot: Cell::new(None),
         }
     }
 }
 
 impl Expression for Identifier {
-    fn eval(&self) -> String {
-        self.get_token_literal()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Identifier(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Identifier(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Identifier(*self)
+    }
 }
 
 impl Node for Identifier {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -51,6 +83,7 @@ impl Node for Identifier {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Identifier {
     fn to_string(&self) -> String {
         self.value.to_string()
@@ -1,5 +1,7 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
     ast::statements::block_statement::BlockStatement,
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
     types::ASTExpression,
@@ -30,17 +32,34 @@ impl IfExpression {
 }
 
 impl Expression for IfExpression {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::If(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::If(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::If(*self)
+    }
 }
 
 impl Node for IfExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        if let Some(alternative) = &self.alternative {
+            self.token.span.to(&alternative.span())
+        } else {
+            self.token.span.to(&self.consequence.span())
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -55,6 +74,7 @@ impl Node for IfExpression {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for IfExpression {
     fn to_string(&self) -> String {
         if let Some(alternative) = &self.alternative {
@@ -1,4 +1,6 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
     types::ASTExpression,
@@ -24,17 +26,30 @@ impl InfixExpression {
 }
 
 impl Expression for InfixExpression {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Infix(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Infix(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Infix(*self)
+    }
 }
 
 impl Node for InfixExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.lhs.span().to(&self.rhs.span())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -50,6 +65,7 @@ impl Node for InfixExpression {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for InfixExpression {
     fn to_string(&self) -> String {
         format!(
@@ -1,4 +1,6 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
@@ -15,7 +17,7 @@ impl IntegerLiteral {
     }
 
     pub fn from_token(token: &Token) -> Self {
-        let value = token.literal.parse::<i64>().unwrap();
+        let value = token.literal().replace('_', "").parse::<i64>().unwrap();
 
         Self {
             token: token.clone(),
@@ -25,17 +27,30 @@ impl IntegerLiteral {
 }
 
 impl Expression for IntegerLiteral {
-    fn eval(&self) -> String {
-        self.value.to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Integer(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Integer(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Integer(*self)
+    }
 }
 
 impl Node for IntegerLiteral {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -51,6 +66,7 @@ impl Node for IntegerLiteral {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for IntegerLiteral {
     fn to_string(&self) -> String {
         self.value.to_string()
@@ -5,6 +5,7 @@ pub mod identifier;
 pub mod if_expression;
 pub mod infix_expression;
 pub mod integer_literal;
+pub mod named_argument;
 pub mod null_expression;
 pub mod prefix_expression;
 pub mod string_literal;
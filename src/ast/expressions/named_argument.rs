@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
+    traits::{expression::Expression, node::Node},
+    types::ASTExpression,
+};
+
+use super::identifier::Identifier;
+
+/// `name = value` in the arguments of a call, binding the parameter called `name`.
+#[derive(Debug, Clone)]
+pub struct NamedArgument {
+    pub name: Identifier,
+    pub value: ASTExpression,
+}
+
+impl NamedArgument {
+    pub fn new(name: Identifier, value: ASTExpression) -> Self {
+        Self { name, value }
+    }
+}
+
+impl Expression for NamedArgument {
+    fn clone_boxed(&self) -> Box<dyn Expression> {
+        Box::new(self.clone())
+    }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::NamedArgument(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::NamedArgument(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::NamedArgument(*self)
+    }
+}
+
+impl Node for NamedArgument {
+    fn get_token_literal(&self) -> String {
+        self.name.get_token_literal()
+    }
+
+    fn span(&self) -> Span {
+        self.name.span().to(&self.value.span())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_node(&self) -> &dyn Node {
+        self
+    }
+}
+
+impl Display for NamedArgument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.name.value, self.value.to_string())
+    }
+}
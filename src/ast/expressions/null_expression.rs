@@ -1,4 +1,6 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
@@ -15,17 +17,30 @@ impl NullExpression {
 }
 
 impl Expression for NullExpression {
-    fn eval(&self) -> String {
-        self.get_token_literal()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Null(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Null(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Null(*self)
+    }
 }
 
 impl Node for NullExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -40,6 +55,7 @@ impl Node for NullExpression {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for NullExpression {
     fn to_string(&self) -> String {
         "null".to_string()
@@ -1,4 +1,6 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
     types::ASTExpression,
@@ -22,17 +24,30 @@ impl PrefixExpression {
 }
 
 impl Expression for PrefixExpression {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::Prefix(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::Prefix(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::Prefix(*self)
+    }
 }
 
 impl Node for PrefixExpression {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.to(&self.rhs.span())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -48,6 +63,7 @@ impl Node for PrefixExpression {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for PrefixExpression {
     fn to_string(&self) -> String {
         format!("({}{})", self.operator, self.rhs.to_string())
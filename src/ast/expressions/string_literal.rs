@@ -1,5 +1,7 @@
 use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
     ast::statements::block_statement::BlockStatement,
+    source::Span,
     token::Token,
     traits::{expression::Expression, node::Node},
 };
@@ -22,17 +24,30 @@ impl StringLiteral {
 }
 
 impl Expression for StringLiteral {
-    fn eval(&self) -> String {
-        "".to_string()
-    }
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::String(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::String(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::String(*self)
+    }
 }
 
 impl Node for StringLiteral {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -47,6 +62,7 @@ impl Node for StringLiteral {
     }
 }
 
+#[allow(clippy::to_string_trait_impl, clippy::useless_format)]
 impl ToString for StringLiteral {
     fn to_string(&self) -> String {
         format!("{}", self.content)
@@ -1,4 +1,10 @@
-use crate::traits::{expression::Expression, node::Node};
+use crate::{
+    ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression},
+    source::Span,
+    token::Token,
+    traits::{expression::Expression, node::Node},
+    types::ASTExpression,
+};
 
 use super::identifier::Identifier;
 
@@ -6,22 +12,39 @@ use super::identifier::Identifier;
 pub struct TypedIdentifier {
     pub identifier: Identifier,
     pub t: Option<Identifier>,
+    /// The value of a parameter left out of a call, e.g: `y = 10`.
+    pub default: Option<ASTExpression>,
+    /// The `..` of a rest parameter, collecting the remaining arguments into an array.
+    pub rest: Option<Token>,
 }
 
 impl TypedIdentifier {
     pub fn new(identifier: Identifier, t: Option<Identifier>) -> Self {
-        Self { identifier, t }
+        Self {
+            identifier,
+            t,
+            default: None,
+            rest: None,
+        }
     }
 }
 
 impl Expression for TypedIdentifier {
-    fn eval(&self) -> String {
-        self.get_token_literal()
-    }
-
     fn clone_boxed(&self) -> Box<dyn Expression> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> ExpressionKind<'_> {
+        ExpressionKind::TypedIdentifier(self)
+    }
+
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_> {
+        ExpressionKindMut::TypedIdentifier(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedExpression {
+        OwnedExpression::TypedIdentifier(*self)
+    }
 }
 
 impl Node for TypedIdentifier {
@@ -29,6 +52,21 @@ impl Node for TypedIdentifier {
         self.identifier.get_token_literal()
     }
 
+    fn span(&self) -> Span {
+        let start = match &self.rest {
+            Some(rest) => rest.span.clone(),
+            None => self.identifier.span(),
+        };
+
+        if let Some(default) = &self.default {
+            start.to(&default.span())
+        } else if let Some(t) = &self.t {
+            start.to(&t.span())
+        } else {
+            start.to(&self.identifier.span())
+        }
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -41,12 +79,22 @@ impl Node for TypedIdentifier {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for TypedIdentifier {
     fn to_string(&self) -> String {
+        let mut str = String::new();
+        if self.rest.is_some() {
+            str.push_str("..");
+        }
+        str.push_str(&self.identifier.value);
+
         if let Some(t) = &self.t {
-            format!("{}: {}", self.identifier.value.to_string(), t.to_string())
-        } else {
-            self.identifier.value.to_string()
+            str.push_str(&format!(": {}", t.to_string()));
         }
+        if let Some(default) = &self.default {
+            str.push_str(&format!(" = {}", default.to_string()));
+        }
+
+        str
     }
 }
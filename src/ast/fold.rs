@@ -0,0 +1,319 @@
+use crate::{
+    program::Program,
+    types::{ASTExpression, ASTStatement},
+};
+
+use super::{
+    expressions::{
+        boolean_expression::BooleanExpression, call_expression::CallExpression,
+        function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+        infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+        named_argument::NamedArgument, null_expression::NullExpression,
+        prefix_expression::PrefixExpression, string_literal::StringLiteral,
+        typed_identifier::TypedIdentifier,
+    },
+    kind::{OwnedExpression, OwnedStatement},
+    statements::{
+        attributed_statement::AttributedStatement, block_statement::BlockStatement,
+        declare_statement::DeclareStatement, defer_statement::DeferStatement,
+        expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+    },
+};
+
+/// `Visitor` consuming the tree and returning a new one. An expression or statement can be
+/// folded into one of another type, the `walk_*` functions rebuild a node from its folded
+/// children.
+pub trait Fold {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+
+    /// The statements of the program, of a block or of a function body.
+    fn fold_statements(&mut self, statements: Vec<ASTStatement>) -> Vec<ASTStatement> {
+        walk_statements(self, statements)
+    }
+
+    fn fold_statement(&mut self, statement: ASTStatement) -> ASTStatement {
+        walk_statement(self, statement)
+    }
+
+    fn fold_expression(&mut self, expression: ASTExpression) -> ASTExpression {
+        walk_expression(self, expression)
+    }
+
+    fn fold_attributed_statement(&mut self, attributed: AttributedStatement) -> ASTStatement {
+        Box::new(walk_attributed_statement(self, attributed))
+    }
+
+    fn fold_block_statement(&mut self, block: BlockStatement) -> BlockStatement {
+        walk_block_statement(self, block)
+    }
+
+    fn fold_declare_statement(&mut self, declare: DeclareStatement) -> ASTStatement {
+        Box::new(walk_declare_statement(self, declare))
+    }
+
+    fn fold_defer_statement(&mut self, defer: DeferStatement) -> ASTStatement {
+        Box::new(walk_defer_statement(self, defer))
+    }
+
+    fn fold_expression_statement(&mut self, statement: ExpressionStatement) -> ASTStatement {
+        Box::new(walk_expression_statement(self, statement))
+    }
+
+    fn fold_return_statement(&mut self, return_statement: ReturnStatement) -> ASTStatement {
+        Box::new(walk_return_statement(self, return_statement))
+    }
+
+    fn fold_boolean_expression(&mut self, boolean: BooleanExpression) -> ASTExpression {
+        Box::new(boolean)
+    }
+
+    fn fold_call_expression(&mut self, call: CallExpression) -> ASTExpression {
+        Box::new(walk_call_expression(self, call))
+    }
+
+    fn fold_function_literal(&mut self, function: FunctionLiteral) -> ASTExpression {
+        Box::new(walk_function_literal(self, function))
+    }
+
+    fn fold_identifier(&mut self, identifier: Identifier) -> ASTExpression {
+        Box::new(identifier)
+    }
+
+    fn fold_if_expression(&mut self, if_expression: IfExpression) -> ASTExpression {
+        Box::new(walk_if_expression(self, if_expression))
+    }
+
+    fn fold_infix_expression(&mut self, infix: InfixExpression) -> ASTExpression {
+        Box::new(walk_infix_expression(self, infix))
+    }
+
+    fn fold_integer_literal(&mut self, integer: IntegerLiteral) -> ASTExpression {
+        Box::new(integer)
+    }
+
+    fn fold_named_argument(&mut self, argument: NamedArgument) -> NamedArgument {
+        walk_named_argument(self, argument)
+    }
+
+    fn fold_null_expression(&mut self, null: NullExpression) -> ASTExpression {
+        Box::new(null)
+    }
+
+    fn fold_prefix_expression(&mut self, prefix: PrefixExpression) -> ASTExpression {
+        Box::new(walk_prefix_expression(self, prefix))
+    }
+
+    fn fold_string_literal(&mut self, string: StringLiteral) -> ASTExpression {
+        Box::new(string)
+    }
+
+    fn fold_typed_identifier(&mut self, parameter: TypedIdentifier) -> TypedIdentifier {
+        walk_typed_identifier(self, parameter)
+    }
+}
+
+pub fn walk_program<F: Fold + ?Sized>(folder: &mut F, program: Program) -> Program {
+    Program {
+        statements: folder.fold_statements(program.statements),
+        ..program
+    }
+}
+
+pub fn walk_statements<F: Fold + ?Sized>(
+    folder: &mut F,
+    statements: Vec<ASTStatement>,
+) -> Vec<ASTStatement> {
+    statements
+        .into_iter()
+        .map(|statement| folder.fold_statement(statement))
+        .collect()
+}
+
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, statement: ASTStatement) -> ASTStatement {
+    match statement.into_kind() {
+        OwnedStatement::Attributed(attributed) => folder.fold_attributed_statement(attributed),
+        OwnedStatement::Block(block) => Box::new(folder.fold_block_statement(block)),
+        OwnedStatement::Declare(declare) => folder.fold_declare_statement(declare),
+        OwnedStatement::Defer(defer) => folder.fold_defer_statement(defer),
+        OwnedStatement::Expression(statement) => folder.fold_expression_statement(statement),
+        OwnedStatement::Return(return_statement) => folder.fold_return_statement(return_statement),
+    }
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    expression: ASTExpression,
+) -> ASTExpression {
+    match expression.into_kind() {
+        OwnedExpression::Boolean(boolean) => folder.fold_boolean_expression(boolean),
+        OwnedExpression::Call(call) => folder.fold_call_expression(call),
+        OwnedExpression::Function(function) => folder.fold_function_literal(function),
+        OwnedExpression::Identifier(identifier) => folder.fold_identifier(identifier),
+        OwnedExpression::If(if_expression) => folder.fold_if_expression(if_expression),
+        OwnedExpression::Infix(infix) => folder.fold_infix_expression(infix),
+        OwnedExpression::Integer(integer) => folder.fold_integer_literal(integer),
+        OwnedExpression::NamedArgument(argument) => Box::new(folder.fold_named_argument(argument)),
+        OwnedExpression::Null(null) => folder.fold_null_expression(null),
+        OwnedExpression::Prefix(prefix) => folder.fold_prefix_expression(prefix),
+        OwnedExpression::String(string) => folder.fold_string_literal(string),
+        OwnedExpression::TypedIdentifier(parameter) => {
+            Box::new(folder.fold_typed_identifier(parameter))
+        }
+    }
+}
+
+pub fn walk_attributed_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    attributed: AttributedStatement,
+) -> AttributedStatement {
+    AttributedStatement {
+        statement: folder.fold_statement(attributed.statement),
+        ..attributed
+    }
+}
+
+pub fn walk_block_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    block: BlockStatement,
+) -> BlockStatement {
+    BlockStatement {
+        statements: folder.fold_statements(block.statements),
+        ..block
+    }
+}
+
+pub fn walk_declare_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    declare: DeclareStatement,
+) -> DeclareStatement {
+    DeclareStatement {
+        value: declare.value.map(|value| folder.fold_expression(value)),
+        ..declare
+    }
+}
+
+pub fn walk_defer_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    defer: DeferStatement,
+) -> DeferStatement {
+    DeferStatement {
+        value: folder.fold_expression(defer.value),
+        ..defer
+    }
+}
+
+pub fn walk_expression_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: ExpressionStatement,
+) -> ExpressionStatement {
+    ExpressionStatement {
+        expression: folder.fold_expression(statement.expression),
+        ..statement
+    }
+}
+
+pub fn walk_return_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    return_statement: ReturnStatement,
+) -> ReturnStatement {
+    ReturnStatement {
+        value: return_statement
+            .value
+            .map(|value| folder.fold_expression(value)),
+        ..return_statement
+    }
+}
+
+pub fn walk_call_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    call: CallExpression,
+) -> CallExpression {
+    CallExpression {
+        function: folder.fold_expression(call.function),
+        args: call
+            .args
+            .into_iter()
+            .map(|arg| folder.fold_expression(arg))
+            .collect(),
+        named_args: call
+            .named_args
+            .into_iter()
+            .map(|arg| folder.fold_named_argument(arg))
+            .collect(),
+        ..call
+    }
+}
+
+pub fn walk_function_literal<F: Fold + ?Sized>(
+    folder: &mut F,
+    function: FunctionLiteral,
+) -> FunctionLiteral {
+    FunctionLiteral {
+        parameters: function
+            .parameters
+            .into_iter()
+            .map(|parameter| folder.fold_typed_identifier(parameter))
+            .collect(),
+        body: folder.fold_block_statement(function.body),
+        ..function
+    }
+}
+
+pub fn walk_if_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    if_expression: IfExpression,
+) -> IfExpression {
+    IfExpression {
+        condition: folder.fold_expression(if_expression.condition),
+        consequence: folder.fold_block_statement(if_expression.consequence),
+        alternative: if_expression
+            .alternative
+            .map(|alternative| folder.fold_block_statement(alternative)),
+        ..if_expression
+    }
+}
+
+pub fn walk_infix_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    infix: InfixExpression,
+) -> InfixExpression {
+    InfixExpression {
+        lhs: folder.fold_expression(infix.lhs),
+        rhs: folder.fold_expression(infix.rhs),
+        ..infix
+    }
+}
+
+pub fn walk_named_argument<F: Fold + ?Sized>(
+    folder: &mut F,
+    argument: NamedArgument,
+) -> NamedArgument {
+    NamedArgument {
+        value: folder.fold_expression(argument.value),
+        ..argument
+    }
+}
+
+pub fn walk_prefix_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    prefix: PrefixExpression,
+) -> PrefixExpression {
+    PrefixExpression {
+        rhs: folder.fold_expression(prefix.rhs),
+        ..prefix
+    }
+}
+
+pub fn walk_typed_identifier<F: Fold + ?Sized>(
+    folder: &mut F,
+    parameter: TypedIdentifier,
+) -> TypedIdentifier {
+    TypedIdentifier {
+        default: parameter
+            .default
+            .map(|default| folder.fold_expression(default)),
+        ..parameter
+    }
+}
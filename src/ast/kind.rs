@@ -0,0 +1,98 @@
+use super::{
+    expressions::{
+        boolean_expression::BooleanExpression, call_expression::CallExpression,
+        function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+        infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+        named_argument::NamedArgument, null_expression::NullExpression,
+        prefix_expression::PrefixExpression, string_literal::StringLiteral,
+        typed_identifier::TypedIdentifier,
+    },
+    statements::{
+        attributed_statement::AttributedStatement, block_statement::BlockStatement,
+        declare_statement::DeclareStatement, defer_statement::DeferStatement,
+        expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+    },
+};
+
+/// An expression as its type, returned by `Expression::kind` to match on instead of
+/// downcasting: adding a node type makes the matches missing it fail to compile.
+#[derive(Debug, Clone, Copy)]
+pub enum ExpressionKind<'a> {
+    Boolean(&'a BooleanExpression),
+    Call(&'a CallExpression),
+    Function(&'a FunctionLiteral),
+    Identifier(&'a Identifier),
+    If(&'a IfExpression),
+    Infix(&'a InfixExpression),
+    Integer(&'a IntegerLiteral),
+    NamedArgument(&'a NamedArgument),
+    Null(&'a NullExpression),
+    Prefix(&'a PrefixExpression),
+    String(&'a StringLiteral),
+    TypedIdentifier(&'a TypedIdentifier),
+}
+
+#[derive(Debug)]
+pub enum ExpressionKindMut<'a> {
+    Boolean(&'a mut BooleanExpression),
+    Call(&'a mut CallExpression),
+    Function(&'a mut FunctionLiteral),
+    Identifier(&'a mut Identifier),
+    If(&'a mut IfExpression),
+    Infix(&'a mut InfixExpression),
+    Integer(&'a mut IntegerLiteral),
+    NamedArgument(&'a mut NamedArgument),
+    Null(&'a mut NullExpression),
+    Prefix(&'a mut PrefixExpression),
+    String(&'a mut StringLiteral),
+    TypedIdentifier(&'a mut TypedIdentifier),
+}
+
+/// An expression moved out of its box, see `Fold`.
+#[derive(Debug)]
+pub enum OwnedExpression {
+    Boolean(BooleanExpression),
+    Call(CallExpression),
+    Function(FunctionLiteral),
+    Identifier(Identifier),
+    If(IfExpression),
+    Infix(InfixExpression),
+    Integer(IntegerLiteral),
+    NamedArgument(NamedArgument),
+    Null(NullExpression),
+    Prefix(PrefixExpression),
+    String(StringLiteral),
+    TypedIdentifier(TypedIdentifier),
+}
+
+/// A statement as its type, see `ExpressionKind`.
+#[derive(Debug, Clone, Copy)]
+pub enum StatementKind<'a> {
+    Attributed(&'a AttributedStatement),
+    Block(&'a BlockStatement),
+    Declare(&'a DeclareStatement),
+    Defer(&'a DeferStatement),
+    Expression(&'a ExpressionStatement),
+    Return(&'a ReturnStatement),
+}
+
+#[derive(Debug)]
+pub enum StatementKindMut<'a> {
+    Attributed(&'a mut AttributedStatement),
+    Block(&'a mut BlockStatement),
+    Declare(&'a mut DeclareStatement),
+    Defer(&'a mut DeferStatement),
+    Expression(&'a mut ExpressionStatement),
+    Return(&'a mut ReturnStatement),
+}
+
+/// A statement moved out of its box, see `Fold`.
+#[derive(Debug)]
+pub enum OwnedStatement {
+    Attributed(AttributedStatement),
+    Block(BlockStatement),
+    Declare(DeclareStatement),
+    Defer(DeferStatement),
+    Expression(ExpressionStatement),
+    Return(ReturnStatement),
+}
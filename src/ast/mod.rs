@@ -1,3 +1,10 @@
 pub mod expressions;
+pub mod fold;
+pub mod kind;
 pub mod precedence;
 pub mod statements;
+pub mod visit;
+pub mod visit_mut;
+
+#[cfg(test)]
+mod test;
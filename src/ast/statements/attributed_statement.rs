@@ -0,0 +1,133 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    ast::expressions::identifier::Identifier,
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
+    token::Token,
+    traits::{node::Node, statement::Statement},
+    types::ASTStatement,
+};
+
+/// `@name(argument, ...)`, e.g: `@allow(unnecessary_parens)`.
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub token: Token,
+    pub name: Identifier,
+    pub arguments: Vec<Identifier>,
+    /// From the `@` to the closing parenthesis.
+    pub span: Span,
+}
+
+impl Attribute {
+    pub fn new(token: Token, name: Identifier, arguments: Vec<Identifier>, span: Span) -> Self {
+        Self {
+            token,
+            name,
+            arguments,
+            span,
+        }
+    }
+}
+
+impl Node for Attribute {
+    fn get_token_literal(&self) -> String {
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_node(&self) -> &dyn Node {
+        self
+    }
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arguments: Vec<String> = self
+            .arguments
+            .iter()
+            .map(|argument| argument.value.clone())
+            .collect();
+
+        write!(f, "@{}({})", self.name.value, arguments.join(", "))
+    }
+}
+
+/// A statement preceded by attributes, they apply to everything inside of the statement.
+#[derive(Debug, Clone)]
+pub struct AttributedStatement {
+    pub token: Token,
+    pub attributes: Vec<Attribute>,
+    pub statement: ASTStatement,
+}
+
+impl AttributedStatement {
+    pub fn new(token: Token, attributes: Vec<Attribute>, statement: ASTStatement) -> Self {
+        Self {
+            token,
+            attributes,
+            statement,
+        }
+    }
+}
+
+impl Node for AttributedStatement {
+    fn get_token_literal(&self) -> String {
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.to(&self.statement.span())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_node(&self) -> &dyn Node {
+        self
+    }
+}
+
+impl Statement for AttributedStatement {
+    fn clone_boxed(&self) -> Box<dyn Statement> {
+        Box::new(self.clone())
+    }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Attributed(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Attributed(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Attributed(*self)
+    }
+}
+
+impl Display for AttributedStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attributes: Vec<String> = self
+            .attributes
+            .iter()
+            .map(|attribute| attribute.to_string())
+            .collect();
+
+        write!(f, "{} {}", attributes.join(" "), self.statement.to_string())
+    }
+}
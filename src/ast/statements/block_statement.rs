@@ -1,6 +1,8 @@
 use std::any::Any;
 
 use crate::{
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
     token::Token,
     traits::{node::Node, statement::Statement},
     types::ASTStatement,
@@ -10,17 +12,27 @@ use crate::{
 pub struct BlockStatement {
     pub token: Token,
     pub statements: Vec<ASTStatement>,
+    /// From the opening `{` to the closing `}`.
+    pub span: Span,
 }
 
 impl BlockStatement {
-    pub fn new(token: Token, statements: Vec<ASTStatement>) -> Self {
-        Self { token, statements }
+    pub fn new(token: Token, statements: Vec<ASTStatement>, span: Span) -> Self {
+        Self {
+            token,
+            statements,
+            span,
+        }
     }
 }
 
 impl Node for BlockStatement {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -36,12 +48,24 @@ impl Node for BlockStatement {
 }
 
 impl Statement for BlockStatement {
-    fn process(&self) {}
     fn clone_boxed(&self) -> Box<dyn Statement> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Block(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Block(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Block(*self)
+    }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for BlockStatement {
     fn to_string(&self) -> String {
         let mut statements_as_str = String::new();
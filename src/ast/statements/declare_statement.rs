@@ -2,6 +2,8 @@ use std::any::Any;
 
 use crate::{
     ast::expressions::identifier::Identifier,
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
     token::Token,
     traits::{node::Node, statement::Statement},
     types::ASTExpression,
@@ -33,7 +35,15 @@ impl DeclareStatement {
 
 impl Node for DeclareStatement {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        if let Some(value) = &self.value {
+            self.token.span.to(&value.span())
+        } else {
+            self.token.span.to(&self.name.span())
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -50,19 +60,31 @@ impl Node for DeclareStatement {
 }
 
 impl Statement for DeclareStatement {
-    fn process(&self) {}
     fn clone_boxed(&self) -> Box<dyn Statement> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Declare(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Declare(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Declare(*self)
+    }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for DeclareStatement {
     fn to_string(&self) -> String {
         if let Some(type_specifier) = &self.type_specifier {
             if let Some(value) = &self.value {
                 format!(
                     "{} {}: {} = {};",
-                    self.token.literal,
+                    self.token.literal(),
                     self.name.get_token_literal(),
                     type_specifier,
                     value.to_string()
@@ -70,7 +92,7 @@ impl ToString for DeclareStatement {
             } else {
                 format!(
                     "{} {}: {};",
-                    self.token.literal,
+                    self.token.literal(),
                     self.name.get_token_literal(),
                     type_specifier
                 )
@@ -79,12 +101,12 @@ impl ToString for DeclareStatement {
             if let Some(value) = &self.value {
                 format!(
                     "{} {} = {};",
-                    self.token.literal,
+                    self.token.literal(),
                     self.name.get_token_literal(),
                     value.to_string()
                 )
             } else {
-                format!("{} {};", self.token.literal, self.name.get_token_literal())
+                format!("{} {};", self.token.literal(), self.name.get_token_literal())
             }
         }
     }
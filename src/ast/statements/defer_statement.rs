@@ -1,6 +1,8 @@
 use std::any::Any;
 
 use crate::{
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
     token::Token,
     traits::{node::Node, statement::Statement},
     types::ASTExpression,
@@ -20,7 +22,11 @@ impl DeferStatement {
 
 impl Node for DeferStatement {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.to(&self.value.span())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -36,12 +42,24 @@ impl Node for DeferStatement {
 }
 
 impl Statement for DeferStatement {
-    fn process(&self) {}
     fn clone_boxed(&self) -> Box<dyn Statement> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Defer(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Defer(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Defer(*self)
+    }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for DeferStatement {
     fn to_string(&self) -> String {
         format!("Defer {};", self.value.to_string())
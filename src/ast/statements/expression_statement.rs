@@ -1,6 +1,8 @@
 use std::any::Any;
 
 use crate::{
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
     token::Token,
     traits::{node::Node, statement::Statement},
     types::ASTExpression,
@@ -20,7 +22,11 @@ impl ExpressionStatement {
 
 impl Node for ExpressionStatement {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.expression.span()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -36,12 +42,24 @@ impl Node for ExpressionStatement {
 }
 
 impl Statement for ExpressionStatement {
-    fn process(&self) {}
     fn clone_boxed(&self) -> Box<dyn Statement> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Expression(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Expression(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Expression(*self)
+    }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for ExpressionStatement {
     fn to_string(&self) -> String {
         self.expression.to_string()
@@ -1,3 +1,4 @@
+pub mod attributed_statement;
 pub mod block_statement;
 pub mod declare_statement;
 pub mod expression_statement;
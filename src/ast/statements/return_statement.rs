@@ -1,6 +1,8 @@
 use std::any::Any;
 
 use crate::{
+    ast::kind::{OwnedStatement, StatementKind, StatementKindMut},
+    source::Span,
     token::Token,
     traits::{node::Node, statement::Statement},
     types::ASTExpression,
@@ -20,7 +22,15 @@ impl ReturnStatement {
 
 impl Node for ReturnStatement {
     fn get_token_literal(&self) -> String {
-        self.token.literal.to_string()
+        self.token.literal().to_string()
+    }
+
+    fn span(&self) -> Span {
+        if let Some(value) = &self.value {
+            self.token.span.to(&value.span())
+        } else {
+            self.token.span.clone()
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -37,12 +47,24 @@ impl Node for ReturnStatement {
 }
 
 impl Statement for ReturnStatement {
-    fn process(&self) {}
     fn clone_boxed(&self) -> Box<dyn Statement> {
         Box::new(self.clone())
     }
+
+    fn kind(&self) -> StatementKind<'_> {
+        StatementKind::Return(self)
+    }
+
+    fn kind_mut(&mut self) -> StatementKindMut<'_> {
+        StatementKindMut::Return(self)
+    }
+
+    fn into_kind(self: Box<Self>) -> OwnedStatement {
+        OwnedStatement::Return(*self)
+    }
 }
 
+#[allow(clippy::to_string_trait_impl, clippy::useless_format)]
 impl ToString for ReturnStatement {
     fn to_string(&self) -> String {
         if let Some(val) = &self.value {
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{
+            expressions::{
+                boolean_expression::BooleanExpression, identifier::Identifier,
+                infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+                named_argument::NamedArgument, null_expression::NullExpression,
+                string_literal::StringLiteral, typed_identifier::TypedIdentifier,
+            },
+            fold::{self, Fold},
+            kind::ExpressionKind,
+            visit::{self, Visitor},
+            visit_mut::{self, VisitorMut},
+        },
+        lexer::Lexer,
+        parser::Parser,
+        program::Program,
+        token::{token_type::TokenType, Token},
+        traits::node::Node,
+        types::ASTExpression,
+    };
+
+    fn parse(input: &str) -> Program {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+
+        program
+    }
+
+    /// The leaves of the tree in the order they are visited.
+    struct Leaves(Vec<String>);
+
+    impl<'ast> Visitor<'ast> for Leaves {
+        fn visit_boolean_expression(&mut self, boolean: &'ast BooleanExpression) {
+            self.0.push(boolean.value.to_string());
+        }
+
+        fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+            self.0.push(identifier.value.clone());
+        }
+
+        fn visit_integer_literal(&mut self, integer: &'ast IntegerLiteral) {
+            self.0.push(integer.value.to_string());
+        }
+
+        fn visit_named_argument(&mut self, argument: &'ast NamedArgument) {
+            self.0.push(format!("{}=", argument.name.value));
+            visit::walk_named_argument(self, argument);
+        }
+
+        fn visit_null_expression(&mut self, _null: &'ast NullExpression) {
+            self.0.push("null".to_string());
+        }
+
+        fn visit_string_literal(&mut self, string: &'ast StringLiteral) {
+            self.0.push(format!("{:?}", string.content));
+        }
+
+        fn visit_typed_identifier(&mut self, parameter: &'ast TypedIdentifier) {
+            self.0.push(format!("{}:", parameter.identifier.value));
+            visit::walk_typed_identifier(self, parameter);
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        const CODE: &str = r#"
+            @allow(unused_variables) let f = fn(a: i32, b = 1, ..rest) {
+                return if (a > b) { -a } else { "s" };
+            };
+            { f(x, true, b = null); }
+        "#;
+
+        let program = parse(CODE);
+        let mut leaves = Leaves(Vec::new());
+        leaves.visit_program(&program);
+
+        assert_eq!(
+            leaves.0,
+            vec!["a:", "b:", "1", "rest:", "a", "b", "a", "\"s\"", "f", "x", "true", "b=", "null"]
+        );
+    }
+
+    /// Replaces the identifiers named `x` by `0`.
+    struct Zero;
+
+    impl VisitorMut for Zero {
+        fn visit_expression(&mut self, expression: &mut ASTExpression) {
+            if let ExpressionKind::Identifier(identifier) = expression.kind() {
+                if identifier.value == "x" {
+                    let token = Token::new(TokenType::INT, identifier.span());
+                    *expression = Box::new(IntegerLiteral::new(token, 0));
+                }
+            }
+
+            visit_mut::walk_expression(self, expression);
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        let mut program = parse("let y = fn(x) { x + f(x, a = -x) };");
+        Zero.visit_program(&mut program);
+
+        assert_eq!(
+            program.to_string(),
+            "let y = fn (x){\n    (0 + f(0, a = (-0)))\n};"
+        );
+    }
+
+    /// Swaps the operands of infix expressions.
+    struct Swap;
+
+    impl Fold for Swap {
+        fn fold_infix_expression(&mut self, infix: InfixExpression) -> ASTExpression {
+            let infix = fold::walk_infix_expression(self, infix);
+
+            Box::new(InfixExpression {
+                lhs: infix.rhs,
+                rhs: infix.lhs,
+                ..infix
+            })
+        }
+    }
+
+    #[test]
+    fn test_fold() {
+        let program = Swap.fold_program(parse("let y = 1 - 2 * x; if (a < b) { c / d };"));
+
+        assert_eq!(
+            program.to_string(),
+            "let y = ((x * 2) - 1);if (b < a) {\n    (d / c)\n}"
+        );
+    }
+}
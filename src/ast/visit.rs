@@ -0,0 +1,269 @@
+use crate::{
+    program::Program,
+    traits::{expression::Expression, statement::Statement},
+    types::ASTStatement,
+};
+
+use super::{
+    expressions::{
+        boolean_expression::BooleanExpression, call_expression::CallExpression,
+        function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+        infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+        named_argument::NamedArgument, null_expression::NullExpression,
+        prefix_expression::PrefixExpression, string_literal::StringLiteral,
+        typed_identifier::TypedIdentifier,
+    },
+    kind::{ExpressionKind, StatementKind},
+    statements::{
+        attributed_statement::AttributedStatement, block_statement::BlockStatement,
+        declare_statement::DeclareStatement, defer_statement::DeferStatement,
+        expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+    },
+};
+
+/// A pass over the AST without downcasting: it overrides the methods of the nodes it looks at,
+/// the other nodes are walked through by the `walk_*` functions. An overriding method calls the
+/// `walk_*` function of its node to visit the children, see `lint::semantic`.
+///
+/// `visit_identifier` is called for the identifiers read or assigned by expressions, the names
+/// declared by statements and parameters are part of their node.
+///
+/// `VisitorMut` rewrites the tree in place and `Fold` rebuilds it.
+pub trait Visitor<'ast> {
+    fn visit_program(&mut self, program: &'ast Program) {
+        walk_program(self, program);
+    }
+
+    /// The statements of the program, of a block or of a function body.
+    fn visit_statements(&mut self, statements: &'ast [ASTStatement]) {
+        walk_statements(self, statements);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast dyn Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast dyn Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_attributed_statement(&mut self, attributed: &'ast AttributedStatement) {
+        walk_attributed_statement(self, attributed);
+    }
+
+    fn visit_block_statement(&mut self, block: &'ast BlockStatement) {
+        walk_block_statement(self, block);
+    }
+
+    fn visit_declare_statement(&mut self, declare: &'ast DeclareStatement) {
+        walk_declare_statement(self, declare);
+    }
+
+    fn visit_defer_statement(&mut self, defer: &'ast DeferStatement) {
+        walk_defer_statement(self, defer);
+    }
+
+    fn visit_expression_statement(&mut self, statement: &'ast ExpressionStatement) {
+        walk_expression_statement(self, statement);
+    }
+
+    fn visit_return_statement(&mut self, return_statement: &'ast ReturnStatement) {
+        walk_return_statement(self, return_statement);
+    }
+
+    fn visit_boolean_expression(&mut self, _boolean: &'ast BooleanExpression) {}
+
+    fn visit_call_expression(&mut self, call: &'ast CallExpression) {
+        walk_call_expression(self, call);
+    }
+
+    fn visit_function_literal(&mut self, function: &'ast FunctionLiteral) {
+        walk_function_literal(self, function);
+    }
+
+    fn visit_identifier(&mut self, _identifier: &'ast Identifier) {}
+
+    fn visit_if_expression(&mut self, if_expression: &'ast IfExpression) {
+        walk_if_expression(self, if_expression);
+    }
+
+    fn visit_infix_expression(&mut self, infix: &'ast InfixExpression) {
+        walk_infix_expression(self, infix);
+    }
+
+    fn visit_integer_literal(&mut self, _integer: &'ast IntegerLiteral) {}
+
+    fn visit_named_argument(&mut self, argument: &'ast NamedArgument) {
+        walk_named_argument(self, argument);
+    }
+
+    fn visit_null_expression(&mut self, _null: &'ast NullExpression) {}
+
+    fn visit_prefix_expression(&mut self, prefix: &'ast PrefixExpression) {
+        walk_prefix_expression(self, prefix);
+    }
+
+    fn visit_string_literal(&mut self, _string: &'ast StringLiteral) {}
+
+    fn visit_typed_identifier(&mut self, parameter: &'ast TypedIdentifier) {
+        walk_typed_identifier(self, parameter);
+    }
+}
+
+pub fn walk_program<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, program: &'ast Program) {
+    visitor.visit_statements(&program.statements);
+}
+
+pub fn walk_statements<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    statements: &'ast [ASTStatement],
+) {
+    for statement in statements {
+        visitor.visit_statement(statement.as_ref());
+    }
+}
+
+pub fn walk_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    statement: &'ast dyn Statement,
+) {
+    match statement.kind() {
+        StatementKind::Attributed(attributed) => visitor.visit_attributed_statement(attributed),
+        StatementKind::Block(block) => visitor.visit_block_statement(block),
+        StatementKind::Declare(declare) => visitor.visit_declare_statement(declare),
+        StatementKind::Defer(defer) => visitor.visit_defer_statement(defer),
+        StatementKind::Expression(statement) => visitor.visit_expression_statement(statement),
+        StatementKind::Return(return_statement) => visitor.visit_return_statement(return_statement),
+    }
+}
+
+pub fn walk_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    expression: &'ast dyn Expression,
+) {
+    match expression.kind() {
+        ExpressionKind::Boolean(boolean) => visitor.visit_boolean_expression(boolean),
+        ExpressionKind::Call(call) => visitor.visit_call_expression(call),
+        ExpressionKind::Function(function) => visitor.visit_function_literal(function),
+        ExpressionKind::Identifier(identifier) => visitor.visit_identifier(identifier),
+        ExpressionKind::If(if_expression) => visitor.visit_if_expression(if_expression),
+        ExpressionKind::Infix(infix) => visitor.visit_infix_expression(infix),
+        ExpressionKind::Integer(integer) => visitor.visit_integer_literal(integer),
+        ExpressionKind::NamedArgument(argument) => visitor.visit_named_argument(argument),
+        ExpressionKind::Null(null) => visitor.visit_null_expression(null),
+        ExpressionKind::Prefix(prefix) => visitor.visit_prefix_expression(prefix),
+        ExpressionKind::String(string) => visitor.visit_string_literal(string),
+        ExpressionKind::TypedIdentifier(parameter) => visitor.visit_typed_identifier(parameter),
+    }
+}
+
+pub fn walk_attributed_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    attributed: &'ast AttributedStatement,
+) {
+    visitor.visit_statement(attributed.statement.as_ref());
+}
+
+pub fn walk_block_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    block: &'ast BlockStatement,
+) {
+    visitor.visit_statements(&block.statements);
+}
+
+pub fn walk_declare_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    declare: &'ast DeclareStatement,
+) {
+    if let Some(value) = &declare.value {
+        visitor.visit_expression(value.as_ref());
+    }
+}
+
+pub fn walk_defer_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    defer: &'ast DeferStatement,
+) {
+    visitor.visit_expression(defer.value.as_ref());
+}
+
+pub fn walk_expression_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    statement: &'ast ExpressionStatement,
+) {
+    visitor.visit_expression(statement.expression.as_ref());
+}
+
+pub fn walk_return_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    return_statement: &'ast ReturnStatement,
+) {
+    if let Some(value) = &return_statement.value {
+        visitor.visit_expression(value.as_ref());
+    }
+}
+
+pub fn walk_call_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    call: &'ast CallExpression,
+) {
+    visitor.visit_expression(call.function.as_ref());
+    for arg in &call.args {
+        visitor.visit_expression(arg.as_ref());
+    }
+    for arg in &call.named_args {
+        visitor.visit_named_argument(arg);
+    }
+}
+
+pub fn walk_function_literal<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    function: &'ast FunctionLiteral,
+) {
+    for parameter in &function.parameters {
+        visitor.visit_typed_identifier(parameter);
+    }
+    visitor.visit_block_statement(&function.body);
+}
+
+pub fn walk_if_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    if_expression: &'ast IfExpression,
+) {
+    visitor.visit_expression(if_expression.condition.as_ref());
+    visitor.visit_block_statement(&if_expression.consequence);
+    if let Some(alternative) = &if_expression.alternative {
+        visitor.visit_block_statement(alternative);
+    }
+}
+
+pub fn walk_infix_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    infix: &'ast InfixExpression,
+) {
+    visitor.visit_expression(infix.lhs.as_ref());
+    visitor.visit_expression(infix.rhs.as_ref());
+}
+
+pub fn walk_named_argument<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    argument: &'ast NamedArgument,
+) {
+    visitor.visit_expression(argument.value.as_ref());
+}
+
+pub fn walk_prefix_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    prefix: &'ast PrefixExpression,
+) {
+    visitor.visit_expression(prefix.rhs.as_ref());
+}
+
+pub fn walk_typed_identifier<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    parameter: &'ast TypedIdentifier,
+) {
+    if let Some(default) = &parameter.default {
+        visitor.visit_expression(default.as_ref());
+    }
+}
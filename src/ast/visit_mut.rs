@@ -0,0 +1,244 @@
+use crate::{
+    program::Program,
+    types::{ASTExpression, ASTStatement},
+};
+
+use super::{
+    expressions::{
+        boolean_expression::BooleanExpression, call_expression::CallExpression,
+        function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+        infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+        named_argument::NamedArgument, null_expression::NullExpression,
+        prefix_expression::PrefixExpression, string_literal::StringLiteral,
+        typed_identifier::TypedIdentifier,
+    },
+    kind::{ExpressionKindMut, StatementKindMut},
+    statements::{
+        attributed_statement::AttributedStatement, block_statement::BlockStatement,
+        declare_statement::DeclareStatement, defer_statement::DeferStatement,
+        expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+    },
+};
+
+/// `Visitor` on a mutable tree, see `optimizer`. The expressions and statements are visited
+/// through their box, `visit_expression` and `visit_statement` can replace them, and
+/// `visit_statements` can add or remove statements.
+pub trait VisitorMut {
+    fn visit_program(&mut self, program: &mut Program) {
+        walk_program(self, program);
+    }
+
+    /// The statements of the program, of a block or of a function body.
+    fn visit_statements(&mut self, statements: &mut Vec<ASTStatement>) {
+        walk_statements(self, statements);
+    }
+
+    fn visit_statement(&mut self, statement: &mut ASTStatement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &mut ASTExpression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_attributed_statement(&mut self, attributed: &mut AttributedStatement) {
+        walk_attributed_statement(self, attributed);
+    }
+
+    fn visit_block_statement(&mut self, block: &mut BlockStatement) {
+        walk_block_statement(self, block);
+    }
+
+    fn visit_declare_statement(&mut self, declare: &mut DeclareStatement) {
+        walk_declare_statement(self, declare);
+    }
+
+    fn visit_defer_statement(&mut self, defer: &mut DeferStatement) {
+        walk_defer_statement(self, defer);
+    }
+
+    fn visit_expression_statement(&mut self, statement: &mut ExpressionStatement) {
+        walk_expression_statement(self, statement);
+    }
+
+    fn visit_return_statement(&mut self, return_statement: &mut ReturnStatement) {
+        walk_return_statement(self, return_statement);
+    }
+
+    fn visit_boolean_expression(&mut self, _boolean: &mut BooleanExpression) {}
+
+    fn visit_call_expression(&mut self, call: &mut CallExpression) {
+        walk_call_expression(self, call);
+    }
+
+    fn visit_function_literal(&mut self, function: &mut FunctionLiteral) {
+        walk_function_literal(self, function);
+    }
+
+    fn visit_identifier(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_if_expression(&mut self, if_expression: &mut IfExpression) {
+        walk_if_expression(self, if_expression);
+    }
+
+    fn visit_infix_expression(&mut self, infix: &mut InfixExpression) {
+        walk_infix_expression(self, infix);
+    }
+
+    fn visit_integer_literal(&mut self, _integer: &mut IntegerLiteral) {}
+
+    fn visit_named_argument(&mut self, argument: &mut NamedArgument) {
+        walk_named_argument(self, argument);
+    }
+
+    fn visit_null_expression(&mut self, _null: &mut NullExpression) {}
+
+    fn visit_prefix_expression(&mut self, prefix: &mut PrefixExpression) {
+        walk_prefix_expression(self, prefix);
+    }
+
+    fn visit_string_literal(&mut self, _string: &mut StringLiteral) {}
+
+    fn visit_typed_identifier(&mut self, parameter: &mut TypedIdentifier) {
+        walk_typed_identifier(self, parameter);
+    }
+}
+
+pub fn walk_program<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    visitor.visit_statements(&mut program.statements);
+}
+
+pub fn walk_statements<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statements: &mut Vec<ASTStatement>,
+) {
+    for statement in statements.iter_mut() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut ASTStatement) {
+    match statement.kind_mut() {
+        StatementKindMut::Attributed(attributed) => visitor.visit_attributed_statement(attributed),
+        StatementKindMut::Block(block) => visitor.visit_block_statement(block),
+        StatementKindMut::Declare(declare) => visitor.visit_declare_statement(declare),
+        StatementKindMut::Defer(defer) => visitor.visit_defer_statement(defer),
+        StatementKindMut::Expression(statement) => visitor.visit_expression_statement(statement),
+        StatementKindMut::Return(return_statement) => {
+            visitor.visit_return_statement(return_statement)
+        }
+    }
+}
+
+pub fn walk_expression<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut ASTExpression) {
+    match expression.kind_mut() {
+        ExpressionKindMut::Boolean(boolean) => visitor.visit_boolean_expression(boolean),
+        ExpressionKindMut::Call(call) => visitor.visit_call_expression(call),
+        ExpressionKindMut::Function(function) => visitor.visit_function_literal(function),
+        ExpressionKindMut::Identifier(identifier) => visitor.visit_identifier(identifier),
+        ExpressionKindMut::If(if_expression) => visitor.visit_if_expression(if_expression),
+        ExpressionKindMut::Infix(infix) => visitor.visit_infix_expression(infix),
+        ExpressionKindMut::Integer(integer) => visitor.visit_integer_literal(integer),
+        ExpressionKindMut::NamedArgument(argument) => visitor.visit_named_argument(argument),
+        ExpressionKindMut::Null(null) => visitor.visit_null_expression(null),
+        ExpressionKindMut::Prefix(prefix) => visitor.visit_prefix_expression(prefix),
+        ExpressionKindMut::String(string) => visitor.visit_string_literal(string),
+        ExpressionKindMut::TypedIdentifier(parameter) => visitor.visit_typed_identifier(parameter),
+    }
+}
+
+pub fn walk_attributed_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    attributed: &mut AttributedStatement,
+) {
+    visitor.visit_statement(&mut attributed.statement);
+}
+
+pub fn walk_block_statement<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut BlockStatement) {
+    visitor.visit_statements(&mut block.statements);
+}
+
+pub fn walk_declare_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declare: &mut DeclareStatement,
+) {
+    if let Some(value) = &mut declare.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_defer_statement<V: VisitorMut + ?Sized>(visitor: &mut V, defer: &mut DeferStatement) {
+    visitor.visit_expression(&mut defer.value);
+}
+
+pub fn walk_expression_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut ExpressionStatement,
+) {
+    visitor.visit_expression(&mut statement.expression);
+}
+
+pub fn walk_return_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    return_statement: &mut ReturnStatement,
+) {
+    if let Some(value) = &mut return_statement.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_call_expression<V: VisitorMut + ?Sized>(visitor: &mut V, call: &mut CallExpression) {
+    visitor.visit_expression(&mut call.function);
+    for arg in &mut call.args {
+        visitor.visit_expression(arg);
+    }
+    for arg in &mut call.named_args {
+        visitor.visit_named_argument(arg);
+    }
+}
+
+pub fn walk_function_literal<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function: &mut FunctionLiteral,
+) {
+    for parameter in &mut function.parameters {
+        visitor.visit_typed_identifier(parameter);
+    }
+    visitor.visit_block_statement(&mut function.body);
+}
+
+pub fn walk_if_expression<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_expression: &mut IfExpression,
+) {
+    visitor.visit_expression(&mut if_expression.condition);
+    visitor.visit_block_statement(&mut if_expression.consequence);
+    if let Some(alternative) = &mut if_expression.alternative {
+        visitor.visit_block_statement(alternative);
+    }
+}
+
+pub fn walk_infix_expression<V: VisitorMut + ?Sized>(visitor: &mut V, infix: &mut InfixExpression) {
+    visitor.visit_expression(&mut infix.lhs);
+    visitor.visit_expression(&mut infix.rhs);
+}
+
+pub fn walk_named_argument<V: VisitorMut + ?Sized>(visitor: &mut V, argument: &mut NamedArgument) {
+    visitor.visit_expression(&mut argument.value);
+}
+
+pub fn walk_prefix_expression<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    prefix: &mut PrefixExpression,
+) {
+    visitor.visit_expression(&mut prefix.rhs);
+}
+
+pub fn walk_typed_identifier<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut TypedIdentifier,
+) {
+    if let Some(default) = &mut parameter.default {
+        visitor.visit_expression(default);
+    }
+}
@@ -0,0 +1,370 @@
+use std::io::IsTerminal;
+
+use crate::{
+    allocator::Strategy,
+    lint::{self, Level},
+    log::emitter::MessageFormat,
+    optimizer::OptLevel,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Runs the files one after the other in the same environment, starts the REPL when there
+    /// are none.
+    Run { paths: Vec<String> },
+    /// Prints the long-form explanation of an error code.
+    Explain { code: String },
+    /// Applies the machine applicable suggestions to the files in place, or prints them as a
+    /// diff with `--dry-run`.
+    Fix { paths: Vec<String>, dry_run: bool },
+    /// Reports the diagnostics of the files without running them.
+    Check { paths: Vec<String> },
+    /// Formats the files in place, or prints the changes as a diff and fails with `--check`.
+    Fmt { paths: Vec<String>, check: bool },
+    /// Serves the Language Server Protocol over stdio.
+    Lsp,
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run { paths: Vec::new() }
+    }
+}
+
+/// The command line options of the `eheh` binary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    pub message_format: MessageFormat,
+    /// The levels set with `-A`, `-W` and `-D`, in order.
+    pub lints: Vec<(String, Level)>,
+    /// Set with `--max-call-depth`, the evaluator default otherwise.
+    pub max_call_depth: Option<usize>,
+    /// The allocator of the runtime objects, set with `--allocator`.
+    pub allocator: Strategy,
+    /// Set with `-O0` or `-O1`.
+    pub opt_level: OptLevel,
+}
+
+impl Options {
+    /// Whether the colored output is written to stderr: the human diagnostics of the files are,
+    /// the REPL, the explanations and the JSON and SARIF formats write to stdout.
+    pub fn writes_to_stderr(&self) -> bool {
+        if self.message_format != MessageFormat::Human {
+            return false;
+        }
+
+        match &self.command {
+            Command::Run { paths } => !paths.is_empty(),
+            Command::Fix { .. } | Command::Check { .. } | Command::Fmt { .. } => true,
+            Command::Explain { .. } | Command::Lsp => false,
+        }
+    }
+
+    /// Parses the arguments, without the name of the program.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter().peekable();
+
+        if args.peek().is_some_and(|arg| arg == "explain") {
+            args.next();
+
+            let code = args
+                .next()
+                .ok_or("missing the error code to explain, e.g: `eheh explain E01000`")?;
+            if let Some(arg) = args.next() {
+                return Err(format!("unexpected argument `{}`", arg));
+            }
+
+            options.command = Command::Explain { code };
+            return Ok(options);
+        }
+
+        let subcommand =
+            args.next_if(|arg| arg == "fix" || arg == "check" || arg == "fmt" || arg == "lsp");
+        let mut dry_run = false;
+        let mut check = false;
+
+        let mut paths = Vec::new();
+        while let Some(arg) = args.next() {
+            if subcommand.as_deref() == Some("fix") && arg == "--dry-run" {
+                dry_run = true;
+            } else if subcommand.as_deref() == Some("fmt") && arg == "--check" {
+                check = true;
+            } else if subcommand.as_deref() == Some("lsp") && arg == "--stdio" {
+                // the only transport, editors pass it anyway
+            } else if subcommand.as_deref() == Some("lsp") && !arg.starts_with('-') {
+                return Err(format!("unexpected argument `{}`", arg));
+            } else if let Some((flag, level)) = lint_flag(&arg) {
+                let name = match arg[flag.len()..].to_string() {
+                    name if name.is_empty() => args
+                        .next()
+                        .ok_or(format!("missing the lint name after `{}`", flag))?,
+                    name => name,
+                };
+                if !lint::is_known(&name) {
+                    return Err(format!("unknown lint `{}`", name));
+                }
+                options.lints.push((name, level));
+            } else if let Some(format) = arg.strip_prefix("--message-format=") {
+                options.message_format = parse_message_format(format)?;
+            } else if arg == "--message-format" {
+                let format = args
+                    .next()
+                    .ok_or("missing value for `--message-format`".to_string())?;
+                options.message_format = parse_message_format(&format)?;
+            } else if let Some(depth) = arg.strip_prefix("--max-call-depth=") {
+                options.max_call_depth = Some(parse_max_call_depth(depth)?);
+            } else if arg == "--max-call-depth" {
+                let depth = args
+                    .next()
+                    .ok_or("missing value for `--max-call-depth`".to_string())?;
+                options.max_call_depth = Some(parse_max_call_depth(&depth)?);
+            } else if let Some(allocator) = arg.strip_prefix("--allocator=") {
+                options.allocator = parse_allocator(allocator)?;
+            } else if arg == "--allocator" {
+                let allocator = args
+                    .next()
+                    .ok_or("missing value for `--allocator`".to_string())?;
+                options.allocator = parse_allocator(&allocator)?;
+            } else if let Some(level) = arg.strip_prefix("-O") {
+                options.opt_level = OptLevel::from_name(level).ok_or(format!(
+                    "invalid optimization level `{}`, expected `-O0` or `-O1`",
+                    arg
+                ))?;
+            } else if arg.starts_with("--") {
+                return Err(format!("unknown option `{}`", arg));
+            } else {
+                paths.push(arg);
+            }
+        }
+
+        options.command = match subcommand.as_deref() {
+            Some("lsp") => Command::Lsp,
+            Some(subcommand) if paths.is_empty() => {
+                return Err(format!(
+                    "missing the files to {}, e.g: `eheh {} main.eheh`",
+                    subcommand, subcommand
+                ))
+            }
+            Some("fix") => Command::Fix { paths, dry_run },
+            Some("fmt") => Command::Fmt { paths, check },
+            Some(_) => Command::Check { paths },
+            None => Command::Run { paths },
+        };
+        Ok(options)
+    }
+}
+
+/// The flag and level of `-A name`, `-W name` and `-D name`, the name may be glued to the flag.
+fn lint_flag(arg: &str) -> Option<(&'static str, Level)> {
+    [Level::Allow, Level::Warn, Level::Deny]
+        .into_iter()
+        .map(|level| (level.flag(), level))
+        .find(|(flag, _)| arg.starts_with(flag))
+}
+
+fn parse_message_format(format: &str) -> Result<MessageFormat, String> {
+    MessageFormat::from_name(format).ok_or(format!(
+        "invalid message format `{}`, expected one of `human`, `json` or `sarif`",
+        format
+    ))
+}
+
+fn parse_max_call_depth(depth: &str) -> Result<usize, String> {
+    depth
+        .parse::<usize>()
+        .ok()
+        .filter(|depth| *depth > 0)
+        .ok_or(format!(
+            "invalid call depth `{}`, expected a positive integer",
+            depth
+        ))
+}
+
+fn parse_allocator(allocator: &str) -> Result<Strategy, String> {
+    Strategy::from_name(allocator).ok_or(format!(
+        "invalid allocator `{}`, expected one of `heap`, `arena` or `pool`",
+        allocator
+    ))
+}
+
+/// Colors are only written to terminals, and never when `NO_COLOR` is set to a non empty value
+/// (see https://no-color.org).
+pub fn should_colorize(options: &Options) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let is_terminal = if options.writes_to_stderr() {
+        std::io::stderr().is_terminal()
+    } else {
+        std::io::stdout().is_terminal()
+    };
+
+    colors_enabled(no_color, is_terminal)
+}
+
+fn colors_enabled(no_color: bool, is_terminal: bool) -> bool {
+    is_terminal && !no_color
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        allocator::Strategy, lint::Level, log::emitter::MessageFormat, optimizer::OptLevel,
+    };
+
+    use super::{colors_enabled, Command, Options};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse(&["a.eh", "--message-format=json", "b.eh"]).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Run {
+                paths: vec!["a.eh".to_string(), "b.eh".to_string()]
+            }
+        );
+        assert_eq!(options.message_format, MessageFormat::Json);
+
+        let options = parse(&["--message-format", "sarif", "a.eh"]).unwrap();
+        assert_eq!(options.message_format, MessageFormat::Sarif);
+
+        assert_eq!(parse(&[]).unwrap().message_format, MessageFormat::Human);
+        assert!(parse(&["--message-format=xml"]).is_err());
+        assert!(parse(&["--message-format"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+
+        assert_eq!(
+            parse(&["explain", "E01000"]).unwrap().command,
+            Command::Explain {
+                code: "E01000".to_string()
+            }
+        );
+        assert!(parse(&["explain"]).is_err());
+        assert!(parse(&["explain", "E01000", "E01001"]).is_err());
+
+        assert_eq!(
+            parse(&["fix", "--dry-run", "a.eh"]).unwrap().command,
+            Command::Fix {
+                paths: vec!["a.eh".to_string()],
+                dry_run: true
+            }
+        );
+        assert!(parse(&["fix"]).is_err());
+        assert!(parse(&["a.eh", "--dry-run"]).is_err());
+
+        assert_eq!(
+            parse(&["check", "a.eh"]).unwrap().command,
+            Command::Check {
+                paths: vec!["a.eh".to_string()]
+            }
+        );
+        assert!(parse(&["check"]).is_err());
+
+        assert_eq!(
+            parse(&["fmt", "a.eh", "--check"]).unwrap().command,
+            Command::Fmt {
+                paths: vec!["a.eh".to_string()],
+                check: true
+            }
+        );
+        assert!(parse(&["fmt"]).is_err());
+        assert!(parse(&["check", "--check", "a.eh"]).is_err());
+
+        assert_eq!(parse(&["lsp"]).unwrap().command, Command::Lsp);
+        let options = parse(&["lsp", "--stdio", "-D", "warnings"]).unwrap();
+        assert_eq!(options.command, Command::Lsp);
+        assert_eq!(options.lints, vec![("warnings".to_string(), Level::Deny)]);
+        assert!(parse(&["lsp", "a.eh"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_max_call_depth() {
+        assert_eq!(parse(&["a.eh"]).unwrap().max_call_depth, None);
+        assert_eq!(
+            parse(&["--max-call-depth=100000", "a.eh"])
+                .unwrap()
+                .max_call_depth,
+            Some(100000)
+        );
+        assert_eq!(
+            parse(&["--max-call-depth", "50"]).unwrap().max_call_depth,
+            Some(50)
+        );
+
+        assert!(parse(&["--max-call-depth"]).is_err());
+        assert!(parse(&["--max-call-depth=0"]).is_err());
+        assert_eq!(
+            parse(&["--max-call-depth=deep"]),
+            Err("invalid call depth `deep`, expected a positive integer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_allocator() {
+        assert_eq!(parse(&["a.eh"]).unwrap().allocator, Strategy::Heap);
+        assert_eq!(
+            parse(&["--allocator=pool", "a.eh"]).unwrap().allocator,
+            Strategy::Pool
+        );
+        assert_eq!(
+            parse(&["--allocator", "arena"]).unwrap().allocator,
+            Strategy::Arena
+        );
+
+        assert!(parse(&["--allocator"]).is_err());
+        assert_eq!(
+            parse(&["--allocator=slab"]),
+            Err("invalid allocator `slab`, expected one of `heap`, `arena` or `pool`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_opt_level() {
+        assert_eq!(parse(&["a.eh"]).unwrap().opt_level, OptLevel::O0);
+        assert_eq!(parse(&["-O1", "a.eh"]).unwrap().opt_level, OptLevel::O1);
+        assert_eq!(parse(&["-O1", "-O0"]).unwrap().opt_level, OptLevel::O0);
+
+        assert_eq!(
+            parse(&["-O2"]),
+            Err("invalid optimization level `-O2`, expected `-O0` or `-O1`".to_string())
+        );
+        assert!(parse(&["-O"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_lint_flags() {
+        let options = parse(&["check", "-D", "warnings", "a.eh", "-Aunnecessary_parens"]).unwrap();
+        assert_eq!(
+            options.lints,
+            vec![
+                ("warnings".to_string(), Level::Deny),
+                ("unnecessary_parens".to_string(), Level::Allow),
+            ]
+        );
+
+        assert!(parse(&["-D"]).is_err());
+        assert!(parse(&["-W", "unused"]).is_err());
+    }
+
+    #[test]
+    fn test_colors_enabled() {
+        assert!(colors_enabled(false, true));
+        assert!(!colors_enabled(true, true));
+        assert!(!colors_enabled(false, false));
+    }
+
+    #[test]
+    fn test_writes_to_stderr() {
+        assert!(parse(&["main.eheh"]).unwrap().writes_to_stderr());
+        assert!(parse(&["check", "main.eheh"]).unwrap().writes_to_stderr());
+        assert!(!parse(&[]).unwrap().writes_to_stderr());
+        assert!(!parse(&["explain", "E01000"]).unwrap().writes_to_stderr());
+        assert!(!parse(&["--message-format=json", "main.eheh"])
+            .unwrap()
+            .writes_to_stderr());
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    lint::{self, Level, LevelSource, LintLevels},
+    log::diagnostic::{Diagnostic, Label},
+    source::{SourceFile, Span},
+};
+
+/// The name of the project config file, looked up from the current directory upwards.
+pub const FILE_NAME: &str = "eheh.toml";
+
+/// The project config, written in a subset of TOML:
+/// ```toml
+/// [lints]
+/// unnecessary_parens = "allow"
+/// warnings = "deny"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    pub path: String,
+    /// The levels of the `[lints]` table, in order.
+    pub lints: Vec<(String, Level)>,
+}
+
+impl Config {
+    /// The config of the project `dir` belongs to, if there is one.
+    pub fn load(dir: &Path) -> Result<Option<Config>, Box<Diagnostic>> {
+        let Some(path) = Config::find(dir) else {
+            return Ok(None);
+        };
+
+        let name = path.to_string_lossy().to_string();
+        let src = fs::read_to_string(&path).map_err(|err| {
+            let message = format!("could not read `{}`: {}", name, err);
+            Box::new(Diagnostic::error(message))
+        })?;
+
+        Config::parse(&name, &src).map(Some)
+    }
+
+    /// The closest config file in `dir` or one of its ancestors.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    pub fn parse(path: &str, src: &str) -> Result<Self, Box<Diagnostic>> {
+        let file = Rc::new(SourceFile::new(0, path, src));
+        let mut config = Config {
+            path: path.to_string(),
+            lints: Vec::new(),
+        };
+
+        let mut section: Option<&str> = None;
+        for (index, line) in src.lines().enumerate() {
+            let start = file.line_starts()[index];
+            let line = strip_comment(line);
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let offset = start + line.len() - line.trim_start().len();
+            let span = Span::new(Rc::clone(&file), offset, offset + trimmed.len());
+
+            if let Some(name) = trimmed
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+            {
+                if name.trim() != "lints" {
+                    return Err(invalid(span, "unknown section, expected `[lints]`"));
+                }
+                section = Some("lints");
+                continue;
+            }
+
+            let Some((key, value)) = trimmed.split_once('=') else {
+                return Err(invalid(span, "expected `name = \"level\"`"));
+            };
+            if section.is_none() {
+                return Err(invalid(span, "expected a `[lints]` section first"));
+            }
+
+            let key = key.trim();
+            if !lint::is_known(key) {
+                return Err(invalid(span, &format!("unknown lint `{}`", key)));
+            }
+
+            let level = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .and_then(Level::from_name)
+                .ok_or_else(|| {
+                    invalid(
+                        span,
+                        "expected one of `\"allow\"`, `\"warn\"` or `\"deny\"`",
+                    )
+                })?;
+
+            config.lints.push((key.to_string(), level));
+        }
+
+        Ok(config)
+    }
+
+    /// Sets the levels of the config, they are overridden by the command line and attributes.
+    pub fn apply(&self, levels: &mut LintLevels) {
+        for (name, level) in &self.lints {
+            levels.set(name, *level, LevelSource::Config(self.path.clone()));
+        }
+    }
+}
+
+/// Values are plain strings without `#`, everything after the first one is a comment.
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+fn invalid(span: Span, message: &str) -> Box<Diagnostic> {
+    Box::new(
+        Diagnostic::error(format!("invalid `{}`", span.file.name))
+            .with_label(Label::primary(span).with_message(message)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lint::Level;
+
+    use super::Config;
+
+    #[test]
+    fn test_parse_config() {
+        const CONFIG: &str = r#"
+# the parentheses make long conditions easier to read
+[lints]
+unnecessary_parens = "allow" # trailing comment
+  warnings = "deny"
+"#;
+
+        let config = Config::parse("eheh.toml", CONFIG).unwrap();
+        assert_eq!(
+            config.lints,
+            vec![
+                ("unnecessary_parens".to_string(), Level::Allow),
+                ("warnings".to_string(), Level::Deny),
+            ]
+        );
+
+        assert!(Config::parse("eheh.toml", "[lint]").is_err());
+        assert!(Config::parse("eheh.toml", "warnings = \"deny\"").is_err());
+        assert!(Config::parse("eheh.toml", "[lints]\nunused = \"deny\"").is_err());
+        assert!(Config::parse("eheh.toml", "[lints]\nwarnings = deny").is_err());
+        assert!(Config::parse("eheh.toml", "[lints]\nwarnings").is_err());
+    }
+}
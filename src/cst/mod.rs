@@ -0,0 +1,418 @@
+use std::{any::TypeId, collections::HashMap, fmt::Display, rc::Rc};
+
+use crate::{
+    ast::{
+        expressions::{
+            boolean_expression::BooleanExpression, call_expression::CallExpression,
+            function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+            infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+            named_argument::NamedArgument, null_expression::NullExpression,
+            prefix_expression::PrefixExpression, string_literal::StringLiteral,
+            typed_identifier::TypedIdentifier,
+        },
+        statements::{
+            attributed_statement::{Attribute, AttributedStatement},
+            block_statement::BlockStatement,
+            declare_statement::DeclareStatement,
+            defer_statement::DeferStatement,
+            expression_statement::ExpressionStatement,
+            return_statement::ReturnStatement,
+        },
+    },
+    lexer::Lexer,
+    log::error::parser::ParserError,
+    parser::Parser,
+    program::Program,
+    source::{SourceFile, Span},
+    token::{token_type::TokenType, trivia::SyntaxToken},
+    traits::node::Node,
+};
+
+/// The syntax nodes, one per AST node plus the parentheses the AST drops.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyntaxKind {
+    Program,
+    AttributedStatement,
+    Attribute,
+    DeclareStatement,
+    ReturnStatement,
+    DeferStatement,
+    BlockStatement,
+    ExpressionStatement,
+    Identifier,
+    TypedIdentifier,
+    IntegerLiteral,
+    StringLiteral,
+    BooleanExpression,
+    NullExpression,
+    PrefixExpression,
+    InfixExpression,
+    CallExpression,
+    NamedArgument,
+    IfExpression,
+    FunctionLiteral,
+    ParenthesizedExpression,
+}
+
+impl SyntaxKind {
+    /// Whether the `;` following the node belongs to it.
+    fn takes_semicolon(&self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::DeclareStatement
+                | SyntaxKind::ReturnStatement
+                | SyntaxKind::DeferStatement
+                | SyntaxKind::ExpressionStatement
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl Display for SyntaxElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyntaxElement::Node(node) => write!(f, "{}", node),
+            SyntaxElement::Token(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// A node of the concrete syntax tree, its children are its nodes and its own tokens in source
+/// order. Tokens the AST has no place for, e.g: the `;` ending a statement or the tokens skipped
+/// after a syntax error, belong to the innermost node around them.
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    pub fn new(kind: SyntaxKind) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Every token of the node and of its descendants, in source order.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+
+        tokens
+    }
+
+    /// From the first to the last token of the node, trivia excluded. `None` for a node without
+    /// tokens.
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.tokens();
+        let first = tokens.first()?;
+        let last = tokens.last()?;
+
+        Some(first.token.span.to(&last.token.span))
+    }
+}
+
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.children
+            .iter()
+            .try_for_each(|child| write!(f, "{}", child))
+    }
+}
+
+/// A lossless syntax tree: printing its root gives the source back byte for byte, whitespace and
+/// comments included, even when the source does not parse.
+#[derive(Debug)]
+pub struct SyntaxTree {
+    pub root: SyntaxNode,
+    /// The AST the tree was built from, every syntax node but the parenthesized expressions
+    /// covers exactly the tokens of one of its nodes.
+    pub program: Program,
+    pub errors: Vec<ParserError>,
+}
+
+impl SyntaxTree {
+    pub fn parse(file: Rc<SourceFile>) -> Self {
+        let mut lexer = Lexer::lossless(file);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        let errors = std::mem::take(&mut parser.errors);
+        let groups = std::mem::take(&mut parser.groups);
+
+        // the parser gives up after too many errors, the rest of the source is still needed
+        while lexer.tokens.last().map(|token| &token.token.t) != Some(&TokenType::EOF) {
+            lexer.next_token();
+        }
+
+        let root = Builder::new(lexer.tokens, &groups).program(&program);
+
+        Self {
+            root,
+            program,
+            errors,
+        }
+    }
+}
+
+impl Display for SyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
+
+/// Hands the tokens out to the AST nodes covering them, in source order so that every token ends
+/// up in the tree exactly once.
+struct Builder {
+    tokens: Vec<SyntaxToken>,
+    /// Index of the next token to hand out.
+    next: usize,
+    /// Index of the closing parenthesis of every group by the index of its opening one.
+    groups: HashMap<usize, usize>,
+    /// The extent of the nodes by address and type, a node is measured again by each of its
+    /// ancestors. The type is needed since a node can start with a child node, e.g: the
+    /// identifier of a typed identifier.
+    extents: HashMap<(*const (), TypeId), (usize, usize, usize)>,
+}
+
+impl Builder {
+    fn new(tokens: Vec<SyntaxToken>, groups: &[Span]) -> Self {
+        let mut builder = Self {
+            tokens,
+            next: 0,
+            groups: HashMap::new(),
+            extents: HashMap::new(),
+        };
+
+        builder.groups = groups
+            .iter()
+            .map(|group| builder.token_range(group))
+            .collect();
+
+        builder
+    }
+
+    fn program(mut self, program: &Program) -> SyntaxNode {
+        let mut root = SyntaxNode::new(SyntaxKind::Program);
+
+        for statement in &program.statements {
+            self.child(&mut root, statement.as_node());
+        }
+        self.take_until(self.tokens.len(), &mut root);
+
+        root
+    }
+
+    /// Indices of the first and of the last token inside of `span`.
+    fn token_range(&self, span: &Span) -> (usize, usize) {
+        let first = self
+            .tokens
+            .partition_point(|token| token.token.span.start < span.start);
+        let end = self
+            .tokens
+            .partition_point(|token| token.token.span.end <= span.end);
+
+        (first, end.saturating_sub(1).max(first))
+    }
+
+    /// Indices of the first and of the last token of the node and its number of parentheses.
+    /// The span of an AST node does not always cover its children, e.g: the lhs of an infix
+    /// expression can be parenthesized, nor its `;`.
+    fn extent(&mut self, node: &dyn Node) -> (usize, usize, usize) {
+        let key = (
+            node as *const dyn Node as *const (),
+            node.as_any().type_id(),
+        );
+        if let Some(&extent) = self.extents.get(&key) {
+            return extent;
+        }
+
+        let (mut first, mut last) = self.token_range(&node.span());
+        let (kind, children) = describe(node);
+
+        for child in children {
+            let (child_first, child_last, _) = self.extent(child);
+            first = first.min(child_first);
+            last = last.max(child_last);
+        }
+
+        if kind.takes_semicolon()
+            && self
+                .tokens
+                .get(last + 1)
+                .is_some_and(|token| token.token.t == TokenType::SEMICOLON)
+        {
+            last += 1;
+        }
+
+        let mut parentheses = 0;
+        while first > 0 && self.groups.get(&(first - 1)) == Some(&(last + 1)) {
+            first -= 1;
+            last += 1;
+            parentheses += 1;
+        }
+
+        self.extents.insert(key, (first, last, parentheses));
+        (first, last, parentheses)
+    }
+
+    fn child(&mut self, parent: &mut SyntaxNode, node: &dyn Node) {
+        let (first, _, parentheses) = self.extent(node);
+        self.take_until(first, parent);
+
+        let child = self.parenthesized(node, parentheses);
+        parent.children.push(SyntaxElement::Node(child));
+    }
+
+    fn parenthesized(&mut self, node: &dyn Node, parentheses: usize) -> SyntaxNode {
+        if parentheses == 0 {
+            return self.node(node);
+        }
+
+        let mut group = SyntaxNode::new(SyntaxKind::ParenthesizedExpression);
+        self.take_until(self.next + 1, &mut group);
+
+        let inner = self.parenthesized(node, parentheses - 1);
+        group.children.push(SyntaxElement::Node(inner));
+
+        self.take_until(self.next + 1, &mut group);
+        group
+    }
+
+    fn node(&mut self, node: &dyn Node) -> SyntaxNode {
+        let (kind, children) = describe(node);
+        let (_, last, parentheses) = self.extent(node);
+        let mut syntax = SyntaxNode::new(kind);
+
+        for child in children {
+            self.child(&mut syntax, child);
+        }
+        // the closing parentheses are handed out by `parenthesized`
+        self.take_until(last + 1 - parentheses, &mut syntax);
+
+        syntax
+    }
+
+    /// Hands the tokens before the `end`th one out to `node`.
+    fn take_until(&mut self, end: usize, node: &mut SyntaxNode) {
+        while self.next < end.min(self.tokens.len()) {
+            node.children
+                .push(SyntaxElement::Token(self.tokens[self.next].clone()));
+            self.next += 1;
+        }
+    }
+}
+
+/// The kind of the node and its children in source order.
+fn describe(node: &dyn Node) -> (SyntaxKind, Vec<&dyn Node>) {
+    let any = node.as_any();
+
+    if let Some(attributed) = any.downcast_ref::<AttributedStatement>() {
+        let mut children: Vec<&dyn Node> = attributed
+            .attributes
+            .iter()
+            .map(|attribute| attribute as &dyn Node)
+            .collect();
+        children.push(attributed.statement.as_node());
+
+        (SyntaxKind::AttributedStatement, children)
+    } else if let Some(attribute) = any.downcast_ref::<Attribute>() {
+        let mut children: Vec<&dyn Node> = vec![&attribute.name];
+        children.extend(attribute.arguments.iter().map(|a| a as &dyn Node));
+
+        (SyntaxKind::Attribute, children)
+    } else if let Some(declare) = any.downcast_ref::<DeclareStatement>() {
+        let mut children: Vec<&dyn Node> = vec![&declare.name];
+        children.extend(declare.value.iter().map(|value| value.as_node()));
+
+        (SyntaxKind::DeclareStatement, children)
+    } else if let Some(return_statement) = any.downcast_ref::<ReturnStatement>() {
+        let children = return_statement.value.iter().map(|v| v.as_node()).collect();
+
+        (SyntaxKind::ReturnStatement, children)
+    } else if let Some(defer) = any.downcast_ref::<DeferStatement>() {
+        (SyntaxKind::DeferStatement, vec![defer.value.as_node()])
+    } else if let Some(block) = any.downcast_ref::<BlockStatement>() {
+        let children = block.statements.iter().map(|s| s.as_node()).collect();
+
+        (SyntaxKind::BlockStatement, children)
+    } else if let Some(statement) = any.downcast_ref::<ExpressionStatement>() {
+        (
+            SyntaxKind::ExpressionStatement,
+            vec![statement.expression.as_node()],
+        )
+    } else if any.is::<Identifier>() {
+        (SyntaxKind::Identifier, Vec::new())
+    } else if let Some(typed) = any.downcast_ref::<TypedIdentifier>() {
+        let mut children: Vec<&dyn Node> = vec![&typed.identifier];
+        children.extend(typed.t.iter().map(|t| t as &dyn Node));
+        children.extend(typed.default.iter().map(|default| default.as_node()));
+
+        (SyntaxKind::TypedIdentifier, children)
+    } else if let Some(argument) = any.downcast_ref::<NamedArgument>() {
+        (
+            SyntaxKind::NamedArgument,
+            vec![&argument.name, argument.value.as_node()],
+        )
+    } else if any.is::<IntegerLiteral>() {
+        (SyntaxKind::IntegerLiteral, Vec::new())
+    } else if any.is::<StringLiteral>() {
+        (SyntaxKind::StringLiteral, Vec::new())
+    } else if any.is::<BooleanExpression>() {
+        (SyntaxKind::BooleanExpression, Vec::new())
+    } else if any.is::<NullExpression>() {
+        (SyntaxKind::NullExpression, Vec::new())
+    } else if let Some(prefix) = any.downcast_ref::<PrefixExpression>() {
+        (SyntaxKind::PrefixExpression, vec![prefix.rhs.as_node()])
+    } else if let Some(infix) = any.downcast_ref::<InfixExpression>() {
+        (
+            SyntaxKind::InfixExpression,
+            vec![infix.lhs.as_node(), infix.rhs.as_node()],
+        )
+    } else if let Some(call) = any.downcast_ref::<CallExpression>() {
+        let mut children = vec![call.function.as_node()];
+        children.extend(call.args.iter().map(|arg| arg.as_node()));
+        children.extend(call.named_args.iter().map(|arg| arg as &dyn Node));
+
+        (SyntaxKind::CallExpression, children)
+    } else if let Some(if_expression) = any.downcast_ref::<IfExpression>() {
+        let mut children: Vec<&dyn Node> = vec![
+            if_expression.condition.as_node(),
+            &if_expression.consequence,
+        ];
+        children.extend(if_expression.alternative.iter().map(|a| a as &dyn Node));
+
+        (SyntaxKind::IfExpression, children)
+    } else if let Some(function) = any.downcast_ref::<FunctionLiteral>() {
+        let mut children: Vec<&dyn Node> = Vec::new();
+        children.extend(function.name.iter().map(|name| name as &dyn Node));
+        children.extend(function.parameters.iter().map(|p| p as &dyn Node));
+        children.extend(function.return_type.iter().map(|t| t as &dyn Node));
+        children.push(&function.body);
+
+        (SyntaxKind::FunctionLiteral, children)
+    } else {
+        unreachable!("no syntax kind for {}", node.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test;
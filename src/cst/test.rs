@@ -0,0 +1,124 @@
+mod tests {
+    use std::{fs, rc::Rc};
+
+    use crate::{
+        cst::{SyntaxElement, SyntaxNode, SyntaxTree},
+        fixtures,
+        lexer::Lexer,
+        parser::Parser,
+        source::SourceFile,
+        token::token_type::TokenType,
+    };
+
+    fn parse(code: &str) -> SyntaxTree {
+        SyntaxTree::parse(Rc::new(SourceFile::new(0, "main.eheh", code)))
+    }
+
+    /// The kinds of the nodes of the tree, the tokens of a node are written after its kind.
+    fn outline(node: &SyntaxNode) -> String {
+        let children: Vec<String> = node
+            .children
+            .iter()
+            .map(|child| match child {
+                SyntaxElement::Node(node) => outline(node),
+                SyntaxElement::Token(token) => token.token.literal().to_string(),
+            })
+            .filter(|child| !child.is_empty())
+            .collect();
+
+        format!("{:?}({})", node.kind, children.join(" "))
+    }
+
+    #[test]
+    fn test_print_back_fixtures() {
+        for path in fixtures::paths(&fixtures::root()) {
+            let src = fs::read_to_string(&path).unwrap();
+            let tree = parse(&src);
+
+            assert_eq!(tree.to_string(), src, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_print_back_source_with_errors() {
+        const CODES: [&str; 4] = [
+            "let = 2; // lost\nlet x = 1;",
+            "fn f( { \t/* unterminated",
+            "let s = \"unterminated\n",
+            "  let x = § 1;\r\n\r\n",
+        ];
+
+        for code in CODES {
+            let tree = parse(code);
+
+            assert!(!tree.errors.is_empty(), "{}", code);
+            assert_eq!(tree.to_string(), code);
+        }
+    }
+
+    #[test]
+    fn test_syntax_nodes() {
+        const CODE: &str = "@allow(unused_variables)\nlet x: i32 = (a + b) * f(c, -1);";
+
+        let tree = parse(CODE);
+        assert_eq!(
+            outline(&tree.root),
+            "Program(AttributedStatement(\
+                Attribute(@ Identifier(allow) ( Identifier(unused_variables) )) \
+                DeclareStatement(let Identifier(x) : i32 = \
+                    InfixExpression(\
+                        ParenthesizedExpression(( InfixExpression(Identifier(a) + Identifier(b)) )) \
+                        * \
+                        CallExpression(Identifier(f) ( Identifier(c) , \
+                            PrefixExpression(- IntegerLiteral(1)) )\
+                    )) ;\
+            )))"
+        );
+
+        const FUNCTION: &str =
+            "fn add(a: i32, b) -> i32 { return ((a)); } if (x) { 1 } else { 2 };";
+
+        let tree = parse(FUNCTION);
+        assert_eq!(
+            outline(&tree.root),
+            "Program(\
+                ExpressionStatement(FunctionLiteral(fn Identifier(add) ( \
+                    TypedIdentifier(Identifier(a) : Identifier(i32)) , \
+                    TypedIdentifier(Identifier(b)) ) -> Identifier(i32) \
+                    BlockStatement({ ReturnStatement(return \
+                        ParenthesizedExpression(( ParenthesizedExpression(( Identifier(a) )) )) ;\
+                    ) }))) \
+                ExpressionStatement(IfExpression(if ( Identifier(x) ) \
+                    BlockStatement({ ExpressionStatement(IntegerLiteral(1)) }) else \
+                    BlockStatement({ ExpressionStatement(IntegerLiteral(2)) })) ;\
+            ))"
+        );
+    }
+
+    #[test]
+    fn test_syntax_tree_matches_the_ast() {
+        const CODE: &str = "let a = 1; // one\n/* two */ let b = (a) + 2;\n\nb";
+
+        let tree = parse(CODE);
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        assert_eq!(tree.program.to_string(), program.to_string());
+
+        let statements: Vec<String> = tree
+            .root
+            .nodes()
+            .map(|node| node.span().unwrap().source_text().to_string())
+            .collect();
+        assert_eq!(statements, vec!["let a = 1;", "let b = (a) + 2;", "b"]);
+
+        // trivia stays with the tokens around it
+        let tokens = tree.root.tokens();
+        assert_eq!(tokens[4].to_string(), "; // one");
+        assert_eq!(tokens[5].to_string(), "\n/* two */ let ");
+        assert_eq!(tokens.last().unwrap().token.t, TokenType::EOF);
+        assert_eq!(tokens.last().unwrap().to_string(), "");
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    allocator, gc,
+    objects::{builtin::Builtin, integer::Integer},
+    traits::object::Object,
+};
+
+/// The functions every program can call, looked up after its own bindings.
+pub const BUILTINS: &[Builtin] = &[
+    Builtin::new("gc_stats", 0, gc_stats),
+    Builtin::new("gc_collect", 0, gc_collect),
+    Builtin::new("alloc_stats", 0, alloc_stats),
+];
+
+pub fn get(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.name == name)
+}
+
+/// The statistics of the heap.
+fn gc_stats(_: Vec<Box<dyn Object>>) -> Box<dyn Object> {
+    Box::new(gc::stats())
+}
+
+/// Runs a collection, returns the number of environments freed.
+fn gc_collect(_: Vec<Box<dyn Object>>) -> Box<dyn Object> {
+    Box::new(Integer::new(gc::collect() as i64))
+}
+
+/// The statistics of the allocator of the evaluation.
+fn alloc_stats(_: Vec<Box<dyn Object>>) -> Box<dyn Object> {
+    Box::new(allocator::stats())
+}
@@ -1,43 +1,49 @@
 use core::panic;
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+    thread,
+};
 
 use crate::{
+    allocator,
     ast::{
         expressions::{
-            boolean_expression::BooleanExpression,
-            call_expression::CallExpression,
-            function_literal::{self, FunctionLiteral},
-            identifier::Identifier,
-            if_expression::IfExpression,
-            infix_expression::InfixExpression,
-            integer_literal::IntegerLiteral,
-            prefix_expression::PrefixExpression, string_literal::StringLiteral,
-        },
-        statements::{
-            block_statement::BlockStatement, declare_statement::DeclareStatement,
-            expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+            call_expression::CallExpression, identifier::Identifier, if_expression::IfExpression,
         },
+        kind::{ExpressionKind, StatementKind},
+    },
+    gc,
+    log::{
+        error::runtime::{RuntimeError, RuntimeErrorCode},
+        suggestion::find_best_match,
     },
-    log::error::runtime::{RuntimeError, RuntimeErrorCode},
     objects::{
+        array::Array,
         boolean::Boolean,
+        builtin::Builtin,
         environment::{self, Environment},
-        error::Error,
+        error::{Error, Frame},
         function::Function,
         integer::Integer,
         null::Null,
         return_::Return, string_::StringObj,
+        tail_call::{Arguments, TailCall},
     },
     program::Program,
+    resolver,
     token::{
         token_type::{KeywordTokenType, TokenType},
         Token,
     },
     traits::{
+        expression::Expression,
         node::Node,
         object::{Object, ObjectType},
+        statement::Statement,
     },
-    types::{ASTExpression, ASTStatement, EvaluatorResult, Result},
+    types::{ASTExpression, ASTStatement, EvaluatorResult, Result, RuntimeResult},
 };
 
 #[derive(Debug, Clone)]
@@ -51,158 +57,515 @@ static ONE: Integer = Integer::new(1);
 
 static NULL: Null = Null::new();
 
+/// The operators `eval_infix_expression` supports for some operands, the most common first.
+const INFIX_OPERATORS: &[&str] = &[
+    "==", "!=", "<", "<=", ">", ">=", "+", "-", "*", "/", "%", "^", "**", "&&", "||", "..", "..=",
+];
+const PREFIX_OPERATORS: &[&str] = &["!", "-", "++", "--", "..", "..="];
+
+/// The calls an evaluation may nest before failing, usually endless recursion. See
+/// `Evaluator::set_max_call_depth`.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
+/// The stack of the threads `Evaluator::on_large_stack` spawns. It is only reserved, pages are
+/// used as the recursion gets deeper.
+pub const STACK_SIZE: usize = 1 << 30;
+
+/// The stack left when an evaluation gives up instead of recursing further, enough for the
+/// frames between two checks in unoptimized builds. The calls a stack fits depend on how deeply
+/// their bodies nest, not only on their number.
+const STACK_RED_ZONE: usize = 256 << 10;
+
+thread_local! {
+    /// The calls being evaluated on this thread.
+    static CALL_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static MAX_CALL_DEPTH: Cell<usize> = const { Cell::new(DEFAULT_MAX_CALL_DEPTH) };
+}
+
 impl Evaluator {
-    pub fn eval(node: Box<&dyn Node>, environment: Rc<RefCell<Environment>>) -> EvaluatorResult {
-        if let Some(program) = node.as_any().downcast_ref::<Program>() {
-            return Evaluator::eval_program(&program.statements, environment);
-        }
+    /// Sets the calls the evaluations of this thread may nest. Deeper recursion than the stack
+    /// of the thread fits fails before reaching it.
+    pub fn set_max_call_depth(depth: usize) {
+        MAX_CALL_DEPTH.set(depth);
+    }
 
-        if let Some(block) = node.as_any().downcast_ref::<BlockStatement>() {
-            return Evaluator::eval_block_statement(&block.statements, environment);
+    /// The error of an evaluation recursing deeper than the stack of its thread fits.
+    fn stack_exhausted() -> EvaluatorResult {
+        Evaluator::new_error(Box::new(RuntimeError {
+            code: RuntimeErrorCode::StackExhausted {
+                depth: CALL_DEPTH.get(),
+            },
+            source: None,
+        }))
+    }
+
+    /// Runs `f` on a thread with a `STACK_SIZE` stack, `eval` recursing once per level of the
+    /// tree and per call.
+    pub fn on_large_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+        thread::scope(|scope| {
+            let thread = thread::Builder::new()
+                .name("eheh".to_string())
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, f)
+                .expect("failed to spawn the evaluator thread");
+
+            match thread.join() {
+                Ok(value) => value,
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        })
+    }
+
+    /// Evaluates `program`, giving the locals of its functions their slot first.
+    pub fn eval(program: &Program, environment: Rc<RefCell<Environment>>) -> EvaluatorResult {
+        resolver::resolve(program);
+        let evaluated = Evaluator::eval_program(&program.statements, environment);
+        allocator::reset();
+
+        Evaluator::locate(evaluated?, program)
+    }
+
+    /// Evaluates an expression on its own, the identifiers it reads must be bound by name in
+    /// `environment`.
+    pub fn eval_expression(
+        expression: &dyn Expression,
+        environment: Rc<RefCell<Environment>>,
+    ) -> EvaluatorResult {
+        if stacker::remaining_stack().is_some_and(|left| left < STACK_RED_ZONE) {
+            return Evaluator::stack_exhausted();
         }
 
-        if let Some(exp_stmt) = node.as_any().downcast_ref::<ExpressionStatement>() {
-            return Evaluator::eval(Box::new(exp_stmt.expression.as_node()), environment);
+        let evaluated = match expression.kind() {
+            ExpressionKind::Boolean(boolean) => allocator::boolean(boolean.value),
+            ExpressionKind::Call(call_exp) => {
+                Evaluator::eval_call_expression(call_exp, environment)?
+            }
+            ExpressionKind::Function(function_literal) => {
+                gc::track(&environment);
+                allocator::allocate(Box::new(Function::new(
+                    function_literal.parameters.as_slice().into(),
+                    Rc::new(function_literal.body.clone()),
+                    function_literal.return_type.clone(),
+                    Rc::clone(&function_literal.locals.borrow()),
+                    environment,
+                    function_literal.span(),
+                )))
+            }
+            ExpressionKind::Identifier(identifier) => {
+                Evaluator::eval_identifier(identifier, environment)?
+            }
+            ExpressionKind::If(if_exp) => Evaluator::eval_if_expression(if_exp, environment)?,
+            ExpressionKind::Infix(infix_expression) => {
+                let lhs = Evaluator::eval_expression(
+                    infix_expression.lhs.as_ref(),
+                    Rc::clone(&environment),
+                )?;
+
+                if Evaluator::is_error(&lhs) {
+                    return Ok(lhs);
+                }
+
+                let rhs = Evaluator::eval_expression(infix_expression.rhs.as_ref(), environment)?;
+                if Evaluator::is_error(&rhs) {
+                    return Ok(rhs);
+                }
+                Evaluator::eval_infix_expression(
+                    infix_expression.operator.as_str(),
+                    &infix_expression.token,
+                    lhs,
+                    rhs,
+                )?
+            }
+            ExpressionKind::Integer(integer_literal) => allocator::integer(integer_literal.value),
+            ExpressionKind::Prefix(prefix_expression) => {
+                let rhs = Evaluator::eval_expression(prefix_expression.rhs.as_ref(), environment)?;
+                if Evaluator::is_error(&rhs) {
+                    return Ok(rhs);
+                }
+
+                Evaluator::eval_prefix_expression(
+                    prefix_expression.operator.as_str(),
+                    &prefix_expression.token,
+                    rhs,
+                )?
+            }
+            ExpressionKind::Null(_) => Box::new(NULL.clone()),
+            ExpressionKind::String(string_literal) => {
+                allocator::allocate(Box::new(StringObj::new(string_literal.content.as_str())))
+            }
+            // only found in calls and parameters, which evaluate their value
+            ExpressionKind::NamedArgument(_) | ExpressionKind::TypedIdentifier(_) => {
+                Box::new(NULL.clone())
+            }
+        };
+
+        Evaluator::locate(evaluated, expression.as_node())
+    }
+
+    fn eval_statement(
+        statement: &dyn Statement,
+        environment: Rc<RefCell<Environment>>,
+    ) -> EvaluatorResult {
+        if stacker::remaining_stack().is_some_and(|left| left < STACK_RED_ZONE) {
+            return Evaluator::stack_exhausted();
         }
 
-        if let Some(declare_stmt) = node.as_any().downcast_ref::<DeclareStatement>() {
-            if let Some(exp) = &declare_stmt.value {
+        let evaluated = match statement.kind() {
+            StatementKind::Attributed(attributed_stmt) => {
+                Evaluator::eval_statement(attributed_stmt.statement.as_ref(), environment)?
+            }
+            StatementKind::Block(block) => {
+                Evaluator::eval_block_statement(&block.statements, environment)?
+            }
+            StatementKind::Declare(declare_stmt) => {
+                let Some(exp) = &declare_stmt.value else {
+                    return Ok(Box::new(NULL.clone()));
+                };
+
                 let declare_value =
-                    Evaluator::eval(Box::new(exp.as_node()), Rc::clone(&environment))?;
+                    Evaluator::eval_expression(exp.as_ref(), Rc::clone(&environment))?;
 
                 if Evaluator::is_error(&declare_value) {
                     return Ok(declare_value);
                 }
 
-                environment
-                    .borrow_mut()
-                    .set(declare_stmt.name.value.clone(), declare_value.clone());
-            } else {
-                return Ok(Box::new(NULL.clone()));
+                Evaluator::declare(&environment, &declare_stmt.name, declare_value);
+                Box::new(NULL.clone())
             }
-        }
+            StatementKind::Expression(exp_stmt) => {
+                Evaluator::eval_expression(exp_stmt.expression.as_ref(), environment)?
+            }
+            StatementKind::Return(return_stmt) => {
+                let Some(exp) = &return_stmt.value else {
+                    return Ok(Box::new(NULL.clone()));
+                };
 
-        if let Some(return_stmt) = node.as_any().downcast_ref::<ReturnStatement>() {
-            if let Some(exp) = &return_stmt.value {
-                let return_value = Evaluator::eval(Box::new(exp.as_node()), environment)?;
+                let return_value = Evaluator::eval_expression(exp.as_ref(), environment)?;
 
                 if Evaluator::is_error(&return_value) {
                     return Ok(return_value);
                 }
 
-                return Ok(Box::new(Return::new(Some(return_value))));
-            } else {
-                return Ok(Box::new(NULL.clone()));
+                allocator::allocate(Box::new(Return::new(Some(return_value))))
+            }
+            // not evaluated yet, see the roadmap
+            StatementKind::Defer(_) => Box::new(NULL.clone()),
+        };
+
+        Evaluator::locate(evaluated, statement.as_node())
+    }
+
+    /// Points an error at `node` when no node inside it failed, the innermost node that failed
+    /// is the one the error points at.
+    fn locate(mut evaluated: Box<dyn Object>, node: &dyn Node) -> EvaluatorResult {
+        if let Some(error) = evaluated.as_any_mut().downcast_mut::<Error>() {
+            if error.span.is_none() {
+                error.span = Some(node.span());
             }
         }
 
-        if let Some(identifier) = node.as_any().downcast_ref::<Identifier>() {
-            return Evaluator::eval_identifier(&identifier, environment);
+        Ok(evaluated)
+    }
+
+    fn eval_call_expression(
+        call_exp: &CallExpression,
+        environment: Rc<RefCell<Environment>>,
+    ) -> EvaluatorResult {
+        let function =
+            Evaluator::eval_expression(call_exp.function.as_ref(), Rc::clone(&environment))?;
+
+        if Evaluator::is_error(&function) {
+            return Ok(function);
         }
 
-        if let Some(if_exp) = node.as_any().downcast_ref::<IfExpression>() {
-            return Evaluator::eval_if_expression(&if_exp, environment);
+        let args = Evaluator::eval_expressions(&call_exp.args, Rc::clone(&environment))?;
+
+        if args.len() == 1 && Evaluator::is_error(&args[0]) {
+            return Ok(args[0].clone());
         }
 
-        if let Some(call_exp) = node.as_any().downcast_ref::<CallExpression>() {
-            let function = Evaluator::eval(
-                Box::new(call_exp.function.as_node()),
-                Rc::clone(&environment),
-            )?;
+        let mut named_args: Vec<(&str, Box<dyn Object>)> = Vec::new();
+        for arg in &call_exp.named_args {
+            let value = Evaluator::eval_expression(arg.value.as_ref(), Rc::clone(&environment))?;
 
-            if Evaluator::is_error(&function) {
-                return Ok(function);
+            if Evaluator::is_error(&value) {
+                return Ok(value);
             }
 
-            let args = Evaluator::eval_expressions(&call_exp.args, environment)?;
+            named_args.push((arg.name.value.as_str(), value));
+        }
 
-            if args.len() == 1 && Evaluator::is_error(&args[0]) {
-                return Ok(args[0].clone());
-            }
+        let name = match call_exp.function.kind() {
+            ExpressionKind::Identifier(identifier) => identifier.value.clone(),
+            _ => String::from("<anonymous>"),
+        };
 
-            return Evaluator::apply_function(function, args);
+        if let Some(builtin) = function.as_any_ref().downcast_ref::<Builtin>() {
+            return match Evaluator::match_builtin_arguments(&name, builtin, args, named_args) {
+                Ok(args) => Ok((builtin.function)(args)),
+                Err(error) => Evaluator::new_error(Box::new(error)),
+            };
         }
 
-        if let Some(integer_literal) = node.as_any().downcast_ref::<IntegerLiteral>() {
-            return Ok(Box::new(Integer::new(integer_literal.value)));
+        let Some(fun) = function.as_any_ref().downcast_ref::<Function>() else {
+            return Evaluator::new_error(Box::new(RuntimeError {
+                code: RuntimeErrorCode::NotCallable {
+                    callee: call_exp.function.to_string(),
+                    found: function.t().to_string(),
+                },
+                source: None,
+            }));
+        };
+        let arguments = match Evaluator::match_arguments(&name, fun, args, named_args) {
+            Ok(arguments) => arguments,
+            Err(error) => return Evaluator::new_error(Box::new(error)),
+        };
+
+        let frame = Frame {
+            function: name,
+            call_site: call_exp.span(),
+        };
+        if call_exp.tail.get() {
+            return Ok(Box::new(TailCall::new(fun.clone(), arguments, frame)));
         }
 
-        if let Some(string_literal) = node.as_any().downcast_ref::<StringLiteral>() {
-            return Ok(Box::new(StringObj::new(string_literal.content.as_str())));
+        let depth = CALL_DEPTH.get();
+        if depth >= MAX_CALL_DEPTH.get() {
+            return Evaluator::new_error(Box::new(RuntimeError {
+                code: RuntimeErrorCode::CallDepthExceeded {
+                    limit: MAX_CALL_DEPTH.get(),
+                },
+                source: None,
+            }));
         }
 
-        if let Some(function_literal) = node.as_any().downcast_ref::<FunctionLiteral>() {
-            return Ok(Box::new(Function::new(
-                function_literal.parameters.clone(),
-                function_literal.body.clone(),
-                function_literal.return_type.clone(),
-                environment,
-            )));
+        CALL_DEPTH.set(depth + 1);
+        let evaluated = Evaluator::apply_function(fun, arguments);
+        CALL_DEPTH.set(depth);
+        let mut evaluated = evaluated?;
+
+        if let Some(error) = evaluated.as_any_mut().downcast_mut::<Error>() {
+            error.trace.push(frame);
         }
 
-        if let Some(boolean) = node.as_any().downcast_ref::<BooleanExpression>() {
-            if boolean.value {
-                return Ok(Box::new(TRUE.clone()));
+        Ok(evaluated)
+    }
+
+    /// Matches the arguments of a call to `fun`, the ones passed by name included, with its
+    /// parameters.
+    fn match_arguments(
+        name: &str,
+        fun: &Function,
+        args: Vec<Box<dyn Object>>,
+        named_args: Vec<(&str, Box<dyn Object>)>,
+    ) -> RuntimeResult<Arguments> {
+        let (rest, parameters) = match fun.parameters.split_last() {
+            Some((last, parameters)) if last.rest.is_some() => (Some(last), parameters),
+            _ => (None, &fun.parameters[..]),
+        };
+        let found = args.len() + named_args.len();
+
+        let arity_mismatch = |missing: Vec<String>| {
+            let required = parameters
+                .iter()
+                .filter(|parameter| parameter.default.is_none())
+                .count();
+            let expected = if rest.is_some() {
+                format!("at least {}", required)
+            } else if required == parameters.len() {
+                required.to_string()
             } else {
-                return Ok(Box::new(FALSE.clone()));
+                format!("{} to {}", required, parameters.len())
+            };
+            let count = if rest.is_some() {
+                required
+            } else {
+                parameters.len()
+            };
+
+            RuntimeError {
+                code: RuntimeErrorCode::ArityMismatch {
+                    function: name.to_string(),
+                    expected: format!("{} argument{}", expected, if count == 1 { "" } else { "s" }),
+                    found,
+                    missing,
+                },
+                source: None,
             }
-        }
+        };
 
-        if let Some(infix_expression) = node.as_any().downcast_ref::<InfixExpression>() {
-            let lhs = Evaluator::eval(
-                Box::new(infix_expression.lhs.as_node()),
-                Rc::clone(&environment),
-            )?;
+        let mut values: Vec<Option<Box<dyn Object>>> = vec![None; parameters.len()];
+        let mut collected: Vec<Box<dyn Object>> = Vec::new();
 
-            if Evaluator::is_error(&lhs) {
-                return Ok(lhs);
+        for (i, arg) in args.into_iter().enumerate() {
+            if i < values.len() {
+                values[i] = Some(arg);
+            } else if rest.is_some() {
+                collected.push(arg);
+            } else {
+                return Err(arity_mismatch(Vec::new()));
             }
+        }
 
-            let rhs = Evaluator::eval(Box::new(infix_expression.rhs.as_node()), environment)?;
-            if Evaluator::is_error(&rhs) {
-                return Ok(rhs);
+        for (argument, value) in named_args {
+            let position = parameters
+                .iter()
+                .position(|parameter| parameter.identifier.value == argument);
+
+            match position {
+                Some(i) if values[i].is_some() => {
+                    return Err(RuntimeError {
+                        code: RuntimeErrorCode::DuplicateArgument {
+                            function: name.to_string(),
+                            argument: argument.to_string(),
+                        },
+                        source: None,
+                    })
+                }
+                Some(i) => values[i] = Some(value),
+                None => {
+                    let names = parameters
+                        .iter()
+                        .map(|parameter| parameter.identifier.value.as_str());
+
+                    return Err(RuntimeError {
+                        code: RuntimeErrorCode::UnknownArgument {
+                            function: name.to_string(),
+                            argument: argument.to_string(),
+                            suggestion: find_best_match(argument, names).map(str::to_string),
+                        },
+                        source: None,
+                    });
+                }
             }
-            return Evaluator::eval_infix_expression(infix_expression.operator.as_str(), lhs, rhs);
         }
 
-        if let Some(prefix_expression) = node.as_any().downcast_ref::<PrefixExpression>() {
-            let rhs = Evaluator::eval(Box::new(prefix_expression.rhs.as_node()), environment)?;
-            if Evaluator::is_error(&rhs) {
-                return Ok(rhs);
-            }
+        let missing: Vec<String> = parameters
+            .iter()
+            .zip(&values)
+            .filter(|(parameter, value)| value.is_none() && parameter.default.is_none())
+            .map(|(parameter, _)| parameter.identifier.value.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(arity_mismatch(missing));
+        }
 
-            return Evaluator::eval_prefix_expression(prefix_expression.operator.as_str(), rhs);
+        Ok(Arguments {
+            values,
+            rest: collected,
+        })
+    }
+
+    /// Checks the arguments of a call to `builtin`, which has no parameter to pass by name.
+    fn match_builtin_arguments(
+        name: &str,
+        builtin: &Builtin,
+        args: Vec<Box<dyn Object>>,
+        named_args: Vec<(&str, Box<dyn Object>)>,
+    ) -> RuntimeResult<Vec<Box<dyn Object>>> {
+        if let Some((argument, _)) = named_args.first() {
+            return Err(RuntimeError {
+                code: RuntimeErrorCode::UnknownArgument {
+                    function: name.to_string(),
+                    argument: argument.to_string(),
+                    suggestion: None,
+                },
+                source: None,
+            });
         }
 
-        return Ok(Box::new(NULL.clone()));
+        if args.len() != builtin.arity {
+            return Err(RuntimeError {
+                code: RuntimeErrorCode::ArityMismatch {
+                    function: name.to_string(),
+                    expected: format!(
+                        "{} argument{}",
+                        builtin.arity,
+                        if builtin.arity == 1 { "" } else { "s" }
+                    ),
+                    found: args.len(),
+                    missing: Vec::new(),
+                },
+                source: None,
+            });
+        }
+
+        Ok(args)
     }
 
-    fn apply_function(function: Box<dyn Object>, args: Vec<Box<dyn Object>>) -> EvaluatorResult {
-        let mut fun = Evaluator::downcast_ref_object::<Function>(&function);
+    fn apply_function(fun: &Function, arguments: Arguments) -> EvaluatorResult {
+        let mut evaluated = Evaluator::eval_function_body(fun, arguments)?;
 
-        let mut extended_env = Evaluator::extend_environment(fun, args);
-        let evaluated = Evaluator::eval(Box::new(fun.body.as_node()), extended_env)?;
+        // the calls ending the body are made once its frame is gone
+        let mut frame = None;
+        while let Some(tail_call) = evaluated.as_any_mut().downcast_mut::<TailCall>() {
+            let function = tail_call.function.clone();
+            let arguments = std::mem::take(&mut tail_call.arguments);
+            frame = Some(tail_call.frame.clone());
 
-        return Evaluator::unwrap_return_value(evaluated);
+            evaluated = Evaluator::eval_function_body(&function, arguments)?;
+        }
+
+        if let Some(error) = evaluated.as_any_mut().downcast_mut::<Error>() {
+            error.trace.extend(frame);
+        }
+
+        Ok(evaluated)
     }
 
-    fn extend_environment<'a>(
-        fun: &'a Function,
-        args: Vec<Box<dyn Object>>,
-    ) -> Rc<RefCell<Environment>> {
-        let mut env = Rc::new(RefCell::new(Environment::new(Some(Rc::clone(&fun.env)))));
+    fn eval_function_body(fun: &Function, arguments: Arguments) -> EvaluatorResult {
+        let Arguments { values, mut rest } = arguments;
+        let env = Rc::new(RefCell::new(Environment::frame(
+            Rc::clone(&fun.locals),
+            Rc::clone(&fun.env),
+        )));
+
+        let mut values = values.into_iter();
+        for param in fun.parameters.iter() {
+            let value = match (values.next().flatten(), &param.default) {
+                (Some(value), _) => value,
+                _ if param.rest.is_some() => {
+                    allocator::allocate(Box::new(Array::new(std::mem::take(&mut rest))))
+                }
+                // evaluated in the frame of the call, after the parameters before it
+                (None, Some(default)) => {
+                    let value = Evaluator::eval_expression(default.as_ref(), Rc::clone(&env))?;
+                    if Evaluator::is_error(&value) {
+                        return Ok(value);
+                    }
 
-        for (i, param) in fun.parameters.iter().enumerate() {
-            env.borrow_mut()
-                .set(param.identifier.value.clone(), args[i].clone());
+                    value
+                }
+                (None, None) => Box::new(NULL.clone()),
+            };
+
+            Evaluator::declare(&env, &param.identifier, value);
         }
 
-        return env;
+        let evaluated = Evaluator::eval_statement(fun.body.as_ref(), env)?;
+
+        Evaluator::unwrap_return_value(evaluated)
+    }
+
+    /// Binds `name` in `environment`, in its slot when it has one.
+    fn declare(environment: &Rc<RefCell<Environment>>, name: &Identifier, value: Box<dyn Object>) {
+        match name.slot.get() {
+            Some(slot) => environment
+                .borrow_mut()
+                .set_slot(slot.index, &name.value, value),
+            None => {
+                environment.borrow_mut().set(name.value.clone(), value);
+            }
+        }
     }
 
+    #[allow(clippy::needless_return)]
     fn unwrap_return_value(object: Box<dyn Object>) -> EvaluatorResult {
         match object.as_any_ref().downcast_ref::<Return>() {
-            Some(obj) => Ok(Box::new(obj.clone())),
+            Some(obj) => match &obj.value {
+                Some(value) => Ok(allocator::clone_object(value.as_ref())),
+                None => Ok(Box::new(NULL.clone())),
+            },
             None => {
                 return Ok(object);
             }
@@ -220,14 +583,14 @@ impl Evaluator {
         let mut object: Option<Box<dyn Object>> = None;
 
         for (stmt) in statements {
-            let evaluated = Evaluator::eval(Box::new(stmt.as_node()), Rc::clone(&environment))?;
+            let evaluated = Evaluator::eval_statement(stmt.as_ref(), Rc::clone(&environment))?;
 
             match evaluated.t() {
                 ObjectType::Return => {
                     if let Some(return_value) =
                         &Evaluator::downcast_ref_object::<Return>(&evaluated).value
                     {
-                        return Ok(return_value.clone());
+                        return Ok(allocator::clone_object(return_value.as_ref()));
                     } else {
                         return Ok(Box::new(NULL.clone()));
                     }
@@ -238,7 +601,9 @@ impl Evaluator {
                 _ => (),
             }
 
-            object = Some(evaluated);
+            if let Some(previous) = object.replace(evaluated) {
+                allocator::free(previous);
+            }
         }
 
         if let Some(obj) = object {
@@ -259,13 +624,15 @@ impl Evaluator {
         let mut object: Option<Box<dyn Object>> = None;
 
         for (stmt) in statements {
-            let evaluated = Evaluator::eval(Box::new(stmt.as_node()), Rc::clone(&environment))?;
+            let evaluated = Evaluator::eval_statement(stmt.as_ref(), Rc::clone(&environment))?;
 
             if evaluated.t() == ObjectType::Return || evaluated.t() == ObjectType::Error {
                 return Ok(evaluated);
             }
 
-            object = Some(evaluated);
+            if let Some(previous) = object.replace(evaluated) {
+                allocator::free(previous);
+            }
         }
 
         if let Some(obj) = object {
@@ -275,6 +642,7 @@ impl Evaluator {
         }
     }
 
+    #[allow(clippy::needless_return)]
     fn eval_expressions(
         exps: &Vec<ASTExpression>,
         environment: Rc<RefCell<Environment>>,
@@ -282,7 +650,7 @@ impl Evaluator {
         let mut objects: Vec<Box<dyn Object>> = Vec::new();
 
         for (exp) in exps {
-            let evaluated = Evaluator::eval(Box::new(exp.as_node()), Rc::clone(&environment))?;
+            let evaluated = Evaluator::eval_expression(exp.as_ref(), Rc::clone(&environment))?;
 
             if evaluated.t() == ObjectType::Return || evaluated.t() == ObjectType::Error {
                 return Ok(vec![evaluated]);
@@ -298,13 +666,37 @@ impl Evaluator {
         identifier: &Identifier,
         environment: Rc<RefCell<Environment>>,
     ) -> EvaluatorResult {
-        if let Some(value) = environment.borrow().get(identifier.value.clone().as_str()) {
-            Ok(value.borrow().clone())
+        // a local read before its declaration may name a binding of an outer environment
+        let value = match identifier.slot.get() {
+            Some(slot) => environment.borrow().get_slot(slot),
+            None => None,
+        };
+
+        if let Some(value) = value.or_else(|| environment.borrow().get(&identifier.value)) {
+            Ok(value)
+        } else if let Some(builtin) = builtins::get(&identifier.value) {
+            Ok(Box::new(builtin.clone()))
         } else {
+            let names = environment.borrow().names();
+            let keywords: Vec<String> = KeywordTokenType::ALL
+                .iter()
+                .map(KeywordTokenType::to_literal)
+                .collect();
+
+            let suggestion = find_best_match(
+                &identifier.value,
+                names
+                    .iter()
+                    .chain(keywords.iter())
+                    .map(String::as_str)
+                    .chain(builtins::BUILTINS.iter().map(|builtin| builtin.name)),
+            );
+
             Evaluator::new_error(Box::new(RuntimeError {
                 code: RuntimeErrorCode::IdentifierNotFound {
                     context: Some(identifier.value.clone()),
                     identifier: identifier.value.clone(),
+                    suggestion: suggestion.map(str::to_string),
                 },
                 source: None,
             }))
@@ -315,32 +707,48 @@ impl Evaluator {
         if_exp: &IfExpression,
         environment: Rc<RefCell<Environment>>,
     ) -> EvaluatorResult {
-        let condition = Evaluator::eval(
-            Box::new(if_exp.condition.as_node()),
-            Rc::clone(&environment),
-        )?;
+        let condition =
+            Evaluator::eval_expression(if_exp.condition.as_ref(), Rc::clone(&environment))?;
+        if Evaluator::is_error(&condition) {
+            return Ok(condition);
+        }
 
-        if Evaluator::is_truthy(&condition) {
-            return Evaluator::eval(Box::new(if_exp.consequence.as_node()), environment);
+        let truthy = Evaluator::is_truthy(&condition);
+        allocator::free(condition);
+
+        if truthy {
+            Evaluator::eval_statement(&if_exp.consequence, environment)
         } else if let Some(alt) = &if_exp.alternative {
-            return Evaluator::eval(Box::new(alt.as_node()), environment);
+            Evaluator::eval_statement(alt, environment)
         } else {
-            return Ok(Box::new(NULL.clone()));
+            Ok(Box::new(NULL.clone()))
         }
     }
 
-    fn eval_prefix_expression(operator: &str, mut rhs: Box<dyn Object>) -> EvaluatorResult {
+    #[allow(clippy::to_string_in_format_args)]
+    fn eval_prefix_expression(
+        operator: &str,
+        location: &Token,
+        mut rhs: Box<dyn Object>,
+    ) -> EvaluatorResult {
         match operator {
             "!" => Evaluator::eval_bang_prefix_expression(rhs),
             "-" => Evaluator::eval_minus_prefix_expression(rhs),
             "--" => Evaluator::eval_decr_prefix_expression(rhs),
             "++" => Evaluator::eval_incr_prefix_expression(rhs),
             ".." | "..=" => match rhs.t() {
-                ObjectType::Integer(_) => Evaluator::eval_integer_to_integer_infix_expression(
-                    operator,
-                    Box::new(Integer::new(0)),
-                    rhs,
-                ),
+                ObjectType::Integer(_) => {
+                    let lhs = allocator::integer(0);
+                    let evaluated = Evaluator::eval_integer_to_integer_infix_expression(
+                        operator,
+                        location,
+                        lhs.as_ref(),
+                        rhs.as_ref(),
+                    );
+                    allocator::free(lhs);
+                    allocator::free(rhs);
+                    evaluated
+                }
                 _ => Evaluator::new_error(Box::new(RuntimeError {
                     code: RuntimeErrorCode::InvalidOperation {
                         operation: format!("{}{}", operator, rhs.to_string()),
@@ -358,6 +766,7 @@ impl Evaluator {
                         rhs.to_string(),
                         rhs.t().to_string(),
                     )),
+                    suggestion: Evaluator::suggest_operator(operator, PREFIX_OPERATORS),
                 },
                 source: None,
             })),
@@ -373,13 +782,11 @@ impl Evaluator {
             }
             ObjectType::Integer(_) => {
                 let mut integer = Evaluator::downcast_ref_object::<Integer>(&rhs);
-                if integer.value == 0 {
-                    Ok(Box::new(TRUE.clone()))
-                } else {
-                    Ok(Box::new(FALSE.clone()))
-                }
+                let evaluated = allocator::boolean(integer.value == 0);
+                allocator::free(rhs);
+                Ok(evaluated)
             }
-            ObjectType::Null => Ok(Box::new(TRUE.clone())),
+            ObjectType::Null => Ok(allocator::boolean(true)),
             _ => Evaluator::new_error(Box::new(RuntimeError {
                 code: RuntimeErrorCode::InvalidOperation {
                     operation: format!("!{}", rhs.to_string()),
@@ -394,10 +801,15 @@ impl Evaluator {
         match rhs.t() {
             ObjectType::Integer(_) => {
                 let mut integer = Evaluator::downcast_mut_object::<Integer>(&mut rhs);
-                integer.value = -integer.value;
-                Ok(rhs)
+                match integer.value.checked_neg() {
+                    Some(value) => {
+                        integer.value = value;
+                        Ok(rhs)
+                    }
+                    None => Evaluator::out_of_range(false),
+                }
             }
-            ObjectType::Null => Ok(Box::new(TRUE.clone())),
+            ObjectType::Null => Ok(allocator::boolean(true)),
             _ => Evaluator::new_error(Box::new(RuntimeError {
                 code: RuntimeErrorCode::InvalidOperation {
                     operation: format!("-{}", rhs.to_string()),
@@ -412,15 +824,12 @@ impl Evaluator {
         match rhs.t() {
             ObjectType::Integer(_) => {
                 let mut integer = Evaluator::downcast_mut_object::<Integer>(&mut rhs);
-                let (value, overflowed) = integer.value.overflowing_add(1);
-                if overflowed {
-                    Evaluator::new_error(Box::new(RuntimeError {
-                        code: RuntimeErrorCode::OverflowError,
-                        source: None,
-                    }))
-                } else {
-                    integer.value = value;
-                    Ok(rhs)
+                match integer.value.checked_add(1) {
+                    Some(value) => {
+                        integer.value = value;
+                        Ok(rhs)
+                    }
+                    None => Evaluator::out_of_range(false),
                 }
             }
             _ => Evaluator::new_error(Box::new(RuntimeError {
@@ -437,15 +846,12 @@ impl Evaluator {
         match rhs.t() {
             ObjectType::Integer(_) => {
                 let mut integer = Evaluator::downcast_mut_object::<Integer>(&mut rhs);
-                let (value, overflowed) = integer.value.overflowing_sub(1);
-                if overflowed {
-                    Evaluator::new_error(Box::new(RuntimeError {
-                        code: RuntimeErrorCode::OverflowError,
-                        source: None,
-                    }))
-                } else {
-                    integer.value = value;
-                    Ok(rhs)
+                match integer.value.checked_sub(1) {
+                    Some(value) => {
+                        integer.value = value;
+                        Ok(rhs)
+                    }
+                    None => Evaluator::out_of_range(true),
                 }
             }
             _ => Evaluator::new_error(Box::new(RuntimeError {
@@ -458,37 +864,53 @@ impl Evaluator {
         }
     }
 
+    #[allow(clippy::to_string_in_format_args)]
     fn eval_infix_expression(
         operator: &str,
-        mut lhs: Box<dyn Object>,
-        mut rhs: Box<dyn Object>,
+        location: &Token,
+        lhs: Box<dyn Object>,
+        rhs: Box<dyn Object>,
     ) -> EvaluatorResult {
-        match (lhs.t(), rhs.t()) {
+        let evaluated = match (lhs.t(), rhs.t()) {
             (ObjectType::Boolean, ObjectType::Boolean) => {
-                Evaluator::eval_boolean_to_boolean_infix_expression(operator, lhs, rhs)
+                Evaluator::eval_boolean_to_boolean_infix_expression(
+                    operator,
+                    lhs.as_ref(),
+                    rhs.as_ref(),
+                )
             }
             (ObjectType::Integer(_), ObjectType::Integer(_)) => {
-                Evaluator::eval_integer_to_integer_infix_expression(operator, lhs, rhs)
+                Evaluator::eval_integer_to_integer_infix_expression(
+                    operator,
+                    location,
+                    lhs.as_ref(),
+                    rhs.as_ref(),
+                )
             }
             (ObjectType::Boolean, ObjectType::Integer(_)) => {
                 let rhs_integer = Evaluator::downcast_ref_object::<Integer>(&rhs);
                 match operator {
                     "&&" | "||" | "==" | "!=" | ">" | "<" | ">=" | "<=" => {
-                        Evaluator::eval_boolean_to_boolean_infix_expression(
+                        let rhs_boolean = allocator::boolean(rhs_integer.value != 0);
+                        let evaluated = Evaluator::eval_boolean_to_boolean_infix_expression(
                             operator,
-                            lhs,
-                            Box::new(Boolean::new(rhs_integer.value != 0)),
-                        )
+                            lhs.as_ref(),
+                            rhs_boolean.as_ref(),
+                        );
+                        allocator::free(rhs_boolean);
+                        evaluated
                     }
                     _ => {
                         let lhs_boolean = Evaluator::downcast_ref_object::<Boolean>(&lhs);
-                        let lhs_integer = Integer::new(if lhs_boolean.value { 1 } else { 0 });
-
-                        Evaluator::eval_integer_to_integer_infix_expression(
+                        let lhs_integer = allocator::integer(if lhs_boolean.value { 1 } else { 0 });
+                        let evaluated = Evaluator::eval_integer_to_integer_infix_expression(
                             operator,
-                            Box::new(lhs_integer),
-                            rhs,
-                        )
+                            location,
+                            lhs_integer.as_ref(),
+                            rhs.as_ref(),
+                        );
+                        allocator::free(lhs_integer);
+                        evaluated
                     }
                 }
             }
@@ -507,137 +929,143 @@ impl Evaluator {
                 },
                 source: None,
             })),
-        }
+        };
+
+        allocator::free(lhs);
+        allocator::free(rhs);
+        evaluated
     }
 
     fn eval_boolean_to_boolean_infix_expression(
         operator: &str,
-        mut lhs: Box<dyn Object>,
-        mut rhs: Box<dyn Object>,
+        lhs: &dyn Object,
+        rhs: &dyn Object,
     ) -> EvaluatorResult {
-        let lhs_boolean = Evaluator::downcast_ref_object::<Boolean>(&lhs);
-        let rhs_boolean = Evaluator::downcast_ref_object::<Boolean>(&rhs);
+        let lhs_boolean = lhs.as_any_ref().downcast_ref::<Boolean>().unwrap();
+        let rhs_boolean = rhs.as_any_ref().downcast_ref::<Boolean>().unwrap();
 
         match operator {
-            "&&" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value && rhs_boolean.value,
-            ))),
-            "||" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value || rhs_boolean.value,
-            ))),
-            "==" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value == rhs_boolean.value,
-            ))),
-            "!=" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value != rhs_boolean.value,
-            ))),
-            "<" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value < rhs_boolean.value,
-            ))),
-            ">" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value > rhs_boolean.value,
-            ))),
-            "<=" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value <= rhs_boolean.value,
-            ))),
-            ">=" => Ok(Box::new(Boolean::new(
-                lhs_boolean.value >= rhs_boolean.value,
-            ))),
+            "&&" => Ok(allocator::boolean(lhs_boolean.value && rhs_boolean.value)),
+            "||" => Ok(allocator::boolean(lhs_boolean.value || rhs_boolean.value)),
+            "==" => Ok(allocator::boolean(lhs_boolean.value == rhs_boolean.value)),
+            "!=" => Ok(allocator::boolean(lhs_boolean.value != rhs_boolean.value)),
+            "<" => Ok(allocator::boolean(!lhs_boolean.value & rhs_boolean.value)),
+            ">" => Ok(allocator::boolean(lhs_boolean.value & !rhs_boolean.value)),
+            "<=" => Ok(allocator::boolean(lhs_boolean.value <= rhs_boolean.value)),
+            ">=" => Ok(allocator::boolean(lhs_boolean.value >= rhs_boolean.value)),
             _ => Evaluator::new_error(Box::new(RuntimeError {
                 code: RuntimeErrorCode::UnknownInfixOperator {
                     operator: operator.to_string(),
                     context: Some(format!(
                         "{}({}){}{}({})",
                         lhs.to_string(),
-                        lhs.t().to_string(),
+                        lhs.t(),
                         operator,
                         rhs.to_string(),
-                        rhs.t().to_string()
+                        rhs.t()
                     )),
+                    suggestion: Evaluator::suggest_operator(operator, INFIX_OPERATORS),
                 },
                 source: None,
             })),
         }
     }
 
+    /// Arithmetic is checked, `location` is the operator the division by zero errors point at.
     fn eval_integer_to_integer_infix_expression(
         operator: &str,
-        mut lhs: Box<dyn Object>,
-        mut rhs: Box<dyn Object>,
+        location: &Token,
+        lhs: &dyn Object,
+        rhs: &dyn Object,
     ) -> EvaluatorResult {
-        let lhs_integer = Evaluator::downcast_ref_object::<Integer>(&lhs);
-        let rhs_integer = Evaluator::downcast_ref_object::<Integer>(&rhs);
+        let lhs_integer = lhs.as_any_ref().downcast_ref::<Integer>().unwrap();
+        let rhs_integer = rhs.as_any_ref().downcast_ref::<Integer>().unwrap();
+        let (a, b) = (lhs_integer.value, rhs_integer.value);
 
         match operator {
             "&&" | "||" => {
-                let lhs_boolean = Boolean::new(lhs_integer.value != 0);
-                let rhs_boolean = Boolean::new(rhs_integer.value != 0);
+                let lhs_boolean = allocator::boolean(lhs_integer.value != 0);
+                let rhs_boolean = allocator::boolean(rhs_integer.value != 0);
 
-                Evaluator::eval_boolean_to_boolean_infix_expression(
+                let evaluated = Evaluator::eval_boolean_to_boolean_infix_expression(
                     operator,
-                    Box::new(lhs_boolean),
-                    Box::new(rhs_boolean),
-                )
+                    lhs_boolean.as_ref(),
+                    rhs_boolean.as_ref(),
+                );
+                allocator::free(lhs_boolean);
+                allocator::free(rhs_boolean);
+                evaluated
             }
-            "==" => Ok(Box::new(Boolean::new(
-                lhs_integer.value == rhs_integer.value,
-            ))),
-            "!=" => Ok(Box::new(Boolean::new(
-                lhs_integer.value != rhs_integer.value,
-            ))),
-            ">" => Ok(Box::new(Boolean::new(
-                lhs_integer.value > rhs_integer.value,
-            ))),
-            "<" => Ok(Box::new(Boolean::new(
-                lhs_integer.value < rhs_integer.value,
-            ))),
-            ">=" => Ok(Box::new(Boolean::new(
-                lhs_integer.value >= rhs_integer.value,
-            ))),
-            "<=" => Ok(Box::new(Boolean::new(
-                lhs_integer.value <= rhs_integer.value,
-            ))),
-            ".." => Ok(Box::new(Integer::new(
-                rhs_integer.value - lhs_integer.value,
-            ))),
-            "..=" => Ok(Box::new(Integer::new(
-                rhs_integer.value - lhs_integer.value + 1,
-            ))),
-            "+" => Ok(Box::new(Integer::new(
-                lhs_integer.value + rhs_integer.value,
-            ))),
-            "-" => Ok(Box::new(Integer::new(
-                lhs_integer.value - rhs_integer.value,
-            ))),
-            "*" => Ok(Box::new(Integer::new(
-                lhs_integer.value * rhs_integer.value,
-            ))),
-            "%" => Ok(Box::new(Integer::new(
-                lhs_integer.value % rhs_integer.value,
-            ))),
-            "^" | "**" => Ok(Box::new(Integer::new(
-                lhs_integer.value.pow(rhs_integer.value as u32),
-            ))),
-            "/" => Ok(Box::new(Integer::new(
-                lhs_integer.value / rhs_integer.value,
-            ))),
+            "==" => Ok(allocator::boolean(lhs_integer.value == rhs_integer.value)),
+            "!=" => Ok(allocator::boolean(lhs_integer.value != rhs_integer.value)),
+            ">" => Ok(allocator::boolean(lhs_integer.value > rhs_integer.value)),
+            "<" => Ok(allocator::boolean(lhs_integer.value < rhs_integer.value)),
+            ">=" => Ok(allocator::boolean(lhs_integer.value >= rhs_integer.value)),
+            "<=" => Ok(allocator::boolean(lhs_integer.value <= rhs_integer.value)),
+            ".." => Evaluator::checked(b.checked_sub(a), a > 0),
+            "..=" => Evaluator::checked(b.checked_sub(a).and_then(|n| n.checked_add(1)), a > 0),
+            "+" => Evaluator::checked(a.checked_add(b), a < 0),
+            "-" => Evaluator::checked(a.checked_sub(b), b > 0),
+            "*" => Evaluator::checked(a.checked_mul(b), (a < 0) != (b < 0)),
+            "/" | "%" if b == 0 => Evaluator::new_error(Box::new(RuntimeError {
+                code: RuntimeErrorCode::DivisionByZero {
+                    location: location.clone(),
+                },
+                source: None,
+            })),
+            // only `i64::MIN / -1` is out of range
+            "/" => Evaluator::checked(a.checked_div(b), false),
+            "%" => Evaluator::checked(a.checked_rem(b), false),
+            "^" | "**" if b < 0 => Evaluator::new_error(Box::new(RuntimeError {
+                code: RuntimeErrorCode::InvalidOperation {
+                    operation: format!("{} {} {}", a, operator, b),
+                    context: None,
+                },
+                source: None,
+            })),
+            "^" | "**" => Evaluator::checked(
+                u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+                a < 0 && b % 2 == 1,
+            ),
             _ => Evaluator::new_error(Box::new(RuntimeError {
                 code: RuntimeErrorCode::UnknownInfixOperator {
                     operator: operator.to_string(),
                     context: Some(format!(
                         "{}({}){}{}({})",
                         lhs.to_string(),
-                        lhs.t().to_string(),
+                        lhs.t(),
                         operator,
                         rhs.to_string(),
-                        rhs.t().to_string(),
+                        rhs.t(),
                     )),
+                    suggestion: Evaluator::suggest_operator(operator, INFIX_OPERATORS),
                 },
                 source: None,
             })),
         }
     }
 
+    /// The integer `value` of a checked operation, or the error of the result not fitting in
+    /// 64 bits, an underflow when it is `too_small`.
+    fn checked(value: Option<i64>, too_small: bool) -> EvaluatorResult {
+        match value {
+            Some(value) => Ok(allocator::integer(value)),
+            None => Evaluator::out_of_range(too_small),
+        }
+    }
+
+    fn out_of_range(too_small: bool) -> EvaluatorResult {
+        Evaluator::new_error(Box::new(RuntimeError {
+            code: if too_small {
+                RuntimeErrorCode::UnderflowError
+            } else {
+                RuntimeErrorCode::OverflowError
+            },
+            source: None,
+        }))
+    }
+
+    #[allow(clippy::borrowed_box)]
     pub fn downcast_ref_object<T: 'static>(object: &Box<dyn Object>) -> &T {
         match object.as_any_ref().downcast_ref::<T>() {
             Some(obj) => obj,
@@ -656,6 +1084,7 @@ impl Evaluator {
         }
     }
 
+    #[allow(clippy::borrowed_box, clippy::needless_borrow)]
     pub fn is_truthy(object: &Box<dyn Object>) -> bool {
         match object.t() {
             ObjectType::Boolean => Evaluator::downcast_ref_object::<Boolean>(&object).value,
@@ -664,6 +1093,17 @@ impl Evaluator {
         }
     }
 
+    /// A supported operator close to an unknown one. Operators that exist but do not apply to
+    /// the operands get no suggestion, `+` is not a typo of `<` on booleans.
+    fn suggest_operator(operator: &str, operators: &[&'static str]) -> Option<String> {
+        if INFIX_OPERATORS.contains(&operator) || PREFIX_OPERATORS.contains(&operator) {
+            return None;
+        }
+
+        find_best_match(operator, operators.iter().copied()).map(str::to_string)
+    }
+
+    #[allow(clippy::borrowed_box)]
     pub fn is_error(object: &Box<dyn Object>) -> bool {
         object.t() == ObjectType::Error
     }
@@ -675,5 +1115,8 @@ impl Evaluator {
     }
 }
 
+pub mod builtins;
+
 #[cfg(test)]
+#[allow(clippy::borrowed_box)]
 pub mod test;
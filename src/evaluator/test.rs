@@ -2,6 +2,7 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::{
     lexer::Lexer,
+    log::error::runtime::{RuntimeError, RuntimeErrorCode},
     objects::{
         boolean::Boolean,
         environment::{self, Environment},
@@ -9,11 +10,9 @@ use crate::{
         integer::Integer,
         null::Null,
     },
+    optimizer::{self, OptLevel},
     parser::Parser,
-    traits::{
-        node::Node,
-        object::{IntegerType, Object, ObjectType},
-    },
+    traits::object::{IntegerType, Object, ObjectType},
     types::EvaluatorResult,
 };
 
@@ -214,6 +213,83 @@ fn test_error_handling() {
     }
 }
 
+#[test]
+fn test_error_location_and_trace() {
+    const CODE: &str = r#"
+        let inner = fn(x) {
+            x + missing
+        };
+        let outer = fn(y) { inner(y * 2) };
+
+        outer(21);
+    "#;
+
+    let object = test_eval_helper(CODE).unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+
+    let span = error.span.as_ref().expect("the error should point at the failing node");
+    assert_eq!(span.source_text(), "missing");
+    assert_eq!(span.get_location(), "<anonymous>:3:17");
+
+    let trace: Vec<(&str, String)> = error
+        .trace
+        .iter()
+        .map(|frame| (frame.function.as_str(), frame.call_site.get_location()))
+        .collect();
+    assert_eq!(
+        trace,
+        vec![
+            ("inner", "<anonymous>:5:29".to_string()),
+            ("outer", "<anonymous>:7:9".to_string()),
+        ]
+    );
+
+    let object = test_eval_helper("let a = 1;\nlet b = -true;").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+
+    assert_eq!(error.span.as_ref().unwrap().source_text(), "-true");
+    assert!(error.trace.is_empty());
+
+    // an error raised by a condition is returned instead of taking the `else` branch
+    let object = test_eval_helper("if missing { 1 } else { 2 }").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+
+    assert_eq!(error.to_diagnostic().code, Some(3000));
+    assert_eq!(error.span.as_ref().unwrap().source_text(), "missing");
+}
+
+#[test]
+fn test_typo_suggestions() {
+    let expected: Vec<(&str, Option<&str>)> = vec![
+        ("let total = 1; totl + 1", Some("total")),
+        ("let counter = 1; let f = fn() { countr }; f()", Some("counter")),
+        ("let a = tru;", Some("true")),
+        ("retrun;", Some("return")),
+        ("let total = 1; xyz", None),
+    ];
+
+    for (input, suggestion) in expected {
+        let object = test_eval_helper(input).unwrap();
+        let error = test_downcast_object_helper::<Error>(&object);
+        let err = error.err.downcast_ref::<RuntimeError>().unwrap();
+
+        match &err.code {
+            RuntimeErrorCode::IdentifierNotFound {
+                suggestion: actual, ..
+            } => assert_eq!(actual.as_deref(), suggestion, "{}", input),
+            code => panic!("Unexpected error for {}: {:?}", input, code),
+        }
+    }
+
+    let object = test_eval_helper("let x = 1; x = 2;").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    assert_eq!(error.to_diagnostic().helps, vec!["did you mean `==`?"]);
+
+    let object = test_eval_helper("true + false").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    assert!(error.to_diagnostic().helps.is_empty());
+}
+
 #[test]
 fn test_eval_declare_statements() {
     let expected: Vec<(&str, i64)> = vec![
@@ -229,6 +305,483 @@ fn test_eval_declare_statements() {
     }
 }
 
+#[test]
+fn test_eval_locals() {
+    let expected: Vec<(&str, i64)> = vec![
+        ("let f = fn(x) { let y = x * 2; y + 1 }; f(3);", 7),
+        (
+            "let f = fn(x) { let x = x + 1; let x = x * 2; x }; f(1);",
+            4,
+        ),
+        ("let f = fn(x) { if (x) { let y = 5; }; y }; f(1);", 5),
+        // closures read the frames they were created in
+        (
+            "let adder = fn(x) { fn(y) { x + y } }; let add = adder(2); add(3);",
+            5,
+        ),
+        (
+            "let f = fn(a) { let g = fn(b) { fn(c) { a + b + c }(3) }; g(2) }; f(1);",
+            6,
+        ),
+        // a local read before its declaration is the global of the same name
+        (
+            "let x = 1; let f = fn() { let y = x; let x = 10; y + x }; f();",
+            11,
+        ),
+        (
+            "let fib = fn(n) { if (n < 2) { n } else { fib(n - 1) + fib(n - 2) } }; fib(10);",
+            55,
+        ),
+    ];
+
+    for (input, value) in expected {
+        let object = test_eval_helper(input).unwrap();
+        test_eval_integer_helper(object, value, None);
+    }
+}
+
+#[test]
+fn test_eval_arguments() {
+    let expected: Vec<(&str, &str)> = vec![
+        ("let add = fn(x, y = 10) { x + y }; add(1);", "11"),
+        ("let add = fn(x, y = 10) { x + y }; add(1, 2);", "3"),
+        // defaults are evaluated on each call, after the parameters before them
+        ("let f = fn(x, y = x * 2) { x + y }; f(3) + f(4);", "21"),
+        ("let sub = fn(x, y) { x - y }; sub(y = 1, x = 3);", "2"),
+        ("let f = fn(x = 1, y = 2) { x * 10 + y }; f(y = 5);", "15"),
+        ("let f = fn(first, ..rest) { rest }; f(1, 2, 3);", "[2, 3]"),
+        ("let f = fn(first, ..rest) { rest }; f(first = 1);", "[]"),
+    ];
+
+    for (input, value) in expected {
+        let object = test_eval_helper(input).unwrap();
+        assert_eq!(object.to_string(), value, "{}", input);
+    }
+}
+
+#[test]
+fn test_argument_errors() {
+    let expected: Vec<(&str, usize, &str, Vec<&str>)> = vec![
+        (
+            "let add = fn(x, y) { x + y }; add(1);",
+            3024,
+            "`add` takes 2 arguments but 1 was given.",
+            vec!["missing `y`"],
+        ),
+        (
+            "let add = fn(x, y = 0) { x + y }; add(1, 2, 3);",
+            3024,
+            "`add` takes 1 to 2 arguments but 3 were given.",
+            vec![],
+        ),
+        (
+            "let f = fn(x, ..rest) { x }; f();",
+            3024,
+            "`f` takes at least 1 argument but 0 were given.",
+            vec!["missing `x`"],
+        ),
+        (
+            "let f = fn(x, factor) { x }; f(1, factr = 2);",
+            3025,
+            "`f` has no parameter named `factr`.",
+            vec![],
+        ),
+        (
+            "let f = fn(x) { x }; f(1, x = 2);",
+            3026,
+            "`f` is passed `x` more than once.",
+            vec![],
+        ),
+    ];
+
+    for (input, code, message, notes) in expected {
+        let object = test_eval_helper(input).unwrap();
+        let error = test_downcast_object_helper::<Error>(&object);
+        let diagnostic = error.to_diagnostic();
+
+        assert_eq!(diagnostic.code, Some(code), "{}", input);
+        assert_eq!(diagnostic.message, message, "{}", input);
+        assert_eq!(diagnostic.notes, notes, "{}", input);
+    }
+
+    // the error points at the call, which is not part of the trace
+    let object = test_eval_helper("let f = fn(x) { x };\nlet g = fn() { f() };\ng();").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    assert_eq!(error.span.as_ref().unwrap().source_text(), "f()");
+    let trace: Vec<&str> = error
+        .trace
+        .iter()
+        .map(|frame| frame.function.as_str())
+        .collect();
+    assert_eq!(trace, vec!["g"]);
+
+    let object = test_eval_helper("let f = fn(x, factor) { x }; f(1, factr = 2);").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    assert_eq!(error.to_diagnostic().helps, vec!["did you mean `factor`?"]);
+}
+
+#[test]
+fn test_runtime_errors() {
+    let expected: Vec<(&str, usize, &str)> = vec![
+        ("let zero = 0; 10 / zero;", 3001, "Division by zero."),
+        ("let zero = 0; 10 % zero;", 3001, "Division by zero."),
+        (
+            "let f = fn(x) { x(1) }; f(5);",
+            3027,
+            "`x` is a value of type `i64`, it cannot be called.",
+        ),
+        (
+            "let f = fn(x) { x() }; f(\"five\");",
+            3027,
+            "`x` is a value of type `string`, it cannot be called.",
+        ),
+        ("2 ** 64;", 3018, "Integer overflow."),
+        ("(-2) ** 63 * 2;", 3019, "Integer underflow."),
+        ("2 ** -1;", 3002, "Invalid operation: `2 ** -1`."),
+        ("9_223_372_036_854_775_807 + 1;", 3018, "Integer overflow."),
+        (
+            "-9_223_372_036_854_775_807 - 2;",
+            3019,
+            "Integer underflow.",
+        ),
+        (
+            "let min = -9_223_372_036_854_775_807 - 1; -min;",
+            3018,
+            "Integer overflow.",
+        ),
+        (
+            "let min = -9_223_372_036_854_775_807 - 1; min / -1;",
+            3018,
+            "Integer overflow.",
+        ),
+        (
+            "let min = -9_223_372_036_854_775_807 - 1; --min;",
+            3019,
+            "Integer underflow.",
+        ),
+    ];
+
+    for (input, code, message) in expected {
+        let object = test_eval_helper(input).unwrap();
+        let error = test_downcast_object_helper::<Error>(&object);
+        let diagnostic = error.to_diagnostic();
+
+        assert_eq!(diagnostic.code, Some(code), "{}", input);
+        assert_eq!(diagnostic.message, message, "{}", input);
+    }
+
+    // division by zero points at the operator
+    let object = test_eval_helper("let zero = 0; 10 / zero;").unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    assert_eq!(error.to_diagnostic().labels[0].span.source_text(), "/");
+}
+
+#[test]
+fn test_call_depth() {
+    const SUM: &str = "let sum = fn(n) { if n == 0 { return 0; } n + sum(n - 1) }; sum(5000);";
+
+    let sum = Evaluator::on_large_stack(|| test_eval_helper(SUM).unwrap().to_string());
+    assert_eq!(sum, "12502500");
+
+    Evaluator::on_large_stack(|| {
+        Evaluator::set_max_call_depth(100);
+
+        let object = test_eval_helper("let f = fn(n) { 1 + f(n + 1) };\nf(0);").unwrap();
+        let error = test_downcast_object_helper::<Error>(&object);
+        let diagnostic = error.to_diagnostic();
+        assert_eq!(diagnostic.code, Some(3028));
+        assert_eq!(error.trace.len(), 100);
+        assert_eq!(
+            diagnostic.notes,
+            vec![
+                "stack trace, most recent call first:
+   0: `f`, called at <anonymous>:1:21
+      [the call above is repeated 98 more times]
+  99: `f`, called at <anonymous>:2:1"
+            ]
+        );
+
+        // the depth is back to zero once the error is returned
+        let object =
+            test_eval_helper("let f = fn(n) { if n == 0 { 0 } else { f(n - 1) } }; f(99);");
+        assert_eq!(object.unwrap().to_string(), "0");
+
+        // a limit deeper than the stack fits fails once the stack runs low instead of
+        // overflowing it
+        Evaluator::set_max_call_depth(usize::MAX);
+        let object = test_eval_helper("let f = fn(n) { 1 + f(n + 1) }; f(0);").unwrap();
+        let error = test_downcast_object_helper::<Error>(&object);
+        assert_eq!(error.to_diagnostic().code, Some(3028));
+        assert!(error.trace.len() > 1000);
+    });
+}
+
+#[test]
+fn test_stack_exhausted() {
+    // each call nests many expressions, the stack runs out long before the call depth limit
+    let ifs = "if true { ".repeat(10);
+    let parens = "(".repeat(30);
+    let code = format!(
+        "let f = fn(n) {{ {}1 + {}f(n + 1){} {} }}; f(0);",
+        ifs,
+        parens,
+        ")".repeat(30),
+        "}".repeat(10)
+    );
+
+    for depth in [super::DEFAULT_MAX_CALL_DEPTH, usize::MAX] {
+        let code = code.clone();
+        let code = Evaluator::on_large_stack(move || {
+            Evaluator::set_max_call_depth(depth);
+            let object = test_eval_helper(&code).unwrap();
+            test_downcast_object_helper::<Error>(&object)
+                .to_diagnostic()
+                .code
+        });
+        assert_eq!(code, Some(3028));
+    }
+
+    // on the small stack of a test thread too
+    let object = test_eval_helper(&code).unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    let message = error.to_diagnostic().message;
+    assert!(
+        message.starts_with("The stack ran out after"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_tail_calls() {
+    // deeper than even the stack of `Evaluator::on_large_stack` fits, without a call depth limit
+    // and on the small stack of a test thread
+    Evaluator::set_max_call_depth(usize::MAX);
+    let expected: Vec<(&str, &str)> = vec![
+        (
+            "let count = fn(n, total) { if n == 0 { total } else { count(n - 1, total + 2) } };
+            count(1000000, 0);",
+            "2000000",
+        ),
+        (
+            "let count = fn(n) { if n == 0 { return n; } return count(n - 1); }; count(1000000);",
+            "0",
+        ),
+        (
+            "let is_even = fn(n) { if n == 0 { true } else { is_odd(n - 1) } };
+            let is_odd = fn(n) { if n == 0 { false } else { is_even(n - 1) } };
+            is_even(1000001);",
+            "false",
+        ),
+    ];
+
+    for (input, value) in expected {
+        let object = test_eval_helper(input).unwrap();
+        assert_eq!(object.to_string(), value, "{}", input);
+    }
+
+    // only the last tail call is left in the trace
+    let object = test_eval_helper(
+        "let fail = fn(n) { if n == 0 { missing } else { fail(n - 1) } };\nfail(3);",
+    )
+    .unwrap();
+    let error = test_downcast_object_helper::<Error>(&object);
+    let trace: Vec<(&str, String)> = error
+        .trace
+        .iter()
+        .map(|frame| (frame.function.as_str(), frame.call_site.get_location()))
+        .collect();
+    assert_eq!(
+        trace,
+        vec![
+            ("fail", "<anonymous>:1:49".to_string()),
+            ("fail", "<anonymous>:2:1".to_string()),
+        ]
+    );
+}
+
+/// Random token streams, and random programs that parse, must be reported as errors, never
+/// crash the interpreter.
+#[test]
+fn test_fuzz() {
+    const FRAGMENTS: &str = "\
+let const var fn return if else defer true false null x y f print 0 1 -1 42
+9_223_372_036_854_775_807 \"s\" = + - * / % ** ^ ! == != < <= > >= && || .. ..= ++ -- -> :
+i32 bool , ; ( ) { } @ allow deny warn unused_variables #";
+
+    let fragments: Vec<&str> = FRAGMENTS.split_whitespace().collect();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..5000 {
+        let len = 1 + rng.below(24);
+        let code: Vec<&str> = (0..len).map(|_| rng.pick(&fragments)).collect();
+        fuzz_eval(&code.join(" "));
+
+        let code: Vec<String> = (0..1 + rng.below(4))
+            .map(|_| fuzz_statement(&mut rng, 3))
+            .collect();
+        fuzz_eval(&code.join(" "));
+    }
+}
+
+/// A xorshift generator, seeded for reproducible failures.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+fn fuzz_eval(code: &str) {
+    let result = std::panic::catch_unwind(|| {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        if let Ok(program) = parser.parse() {
+            let environment = Rc::new(RefCell::new(Environment::new(None)));
+            let _ = Evaluator::eval(&program, environment);
+        }
+    });
+
+    assert!(result.is_ok(), "panicked on `{}`", code);
+}
+
+fn fuzz_statement(rng: &mut Rng, depth: usize) -> String {
+    let expression = fuzz_expression(rng, depth);
+
+    match rng.below(4) {
+        0 => format!("let {} = {};", rng.pick(&["x", "y", "f"]), expression),
+        1 => format!("return {};", expression),
+        _ => format!("{};", expression),
+    }
+}
+
+fn fuzz_expression(rng: &mut Rng, depth: usize) -> String {
+    const ATOMS: &str = "0 1 2 -1 63 64 9_223_372_036_854_775_807 true false null \"s\" x y f";
+    const INFIX: &str = "+ - * / % ** == != < >= && || .. ..=";
+
+    let atoms: Vec<&str> = ATOMS.split_whitespace().collect();
+    let infix: Vec<&str> = INFIX.split_whitespace().collect();
+
+    if depth == 0 {
+        return rng.pick(&atoms).to_string();
+    }
+
+    match rng.below(7) {
+        0 => format!(
+            "({} {} {})",
+            fuzz_expression(rng, depth - 1),
+            rng.pick(&infix),
+            fuzz_expression(rng, depth - 1)
+        ),
+        1 => format!(
+            "{}{}",
+            rng.pick(&["-", "!", "++", "--", ".."]),
+            fuzz_expression(rng, depth - 1)
+        ),
+        2 => format!(
+            "{}({})",
+            fuzz_expression(rng, depth - 1),
+            fuzz_expression(rng, depth - 1)
+        ),
+        3 => format!(
+            "fn({}) {{ {} }}",
+            rng.pick(&["", "x", "x, y", "x, y = 2", "x, ..y"]),
+            fuzz_statement(rng, depth - 1)
+        ),
+        4 => format!(
+            "if ({}) {{ {} }} else {{ {} }}",
+            fuzz_expression(rng, depth - 1),
+            fuzz_statement(rng, depth - 1),
+            fuzz_statement(rng, depth - 1)
+        ),
+        _ => rng.pick(&atoms).to_string(),
+    }
+}
+
+/// Optimized programs must evaluate to what they do as written, errors and their location
+/// included.
+#[test]
+fn test_optimizer_differential() {
+    const PROGRAMS: &[&str] = &[
+        "const n = 10; let f = fn(x) { if x == 0 { n * 2 } else { f(x - 1) + 1 } }; f(n);",
+        "const debug = false; if debug { missing } else { 2 ** 10 };",
+        "let f = fn(x, y = 2 * 3) { return x * y; x / 0; }; f(7);",
+        "const limit = 9_223_372_036_854_775_807; limit + 1;",
+        "const zero = 0; let f = fn(x) { x / zero }; f(1);",
+        "const n = 1; n(2);",
+        "if (1 > 2) { 1 };",
+        "-(2 - 3) * !false;",
+    ];
+
+    Evaluator::on_large_stack(|| {
+        for code in PROGRAMS {
+            assert_eq!(
+                eval_at(code, OptLevel::O0),
+                eval_at(code, OptLevel::O1),
+                "{}",
+                code
+            );
+        }
+
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..3000 {
+            let code: Vec<String> = (0..1 + rng.below(4))
+                .map(|_| match fuzz_statement(&mut rng, 3) {
+                    statement if rng.below(2) == 0 => statement.replacen("let ", "const ", 1),
+                    statement => statement,
+                })
+                .collect();
+            let code = code.join(" ");
+
+            assert_eq!(
+                eval_at(&code, OptLevel::O0),
+                eval_at(&code, OptLevel::O1),
+                "{}",
+                code
+            );
+        }
+    });
+}
+
+/// The value of `code` optimized at `level`, `None` if it does not parse.
+fn eval_at(code: &str, level: OptLevel) -> Option<String> {
+    let mut lexer = Lexer::new(code);
+    let mut parser = Parser::new(&mut lexer);
+    let mut program = parser.parse().ok()?;
+    optimizer::optimize(&mut program, level);
+
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    match Evaluator::eval(&program, environment) {
+        Ok(value) => Some(value.to_string()),
+        Err(error) => Some(error.to_string()),
+    }
+}
+
+#[test]
+fn test_eval_globals_across_programs() {
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let mut eval = |input: &str| {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        Evaluator::eval(&program, Rc::clone(&environment)).unwrap()
+    };
+
+    // like in the REPL, functions see the globals declared by later programs
+    eval("let f = fn(x) { g(x) + n };");
+    eval("let n = 1; let g = fn(y) { y * 2 };");
+    test_eval_integer_helper(eval("f(20);"), 41, None);
+}
+
 fn test_eval_helper(input: &str) -> EvaluatorResult {
     let mut lexer = Lexer::new(input);
     let mut parser = Parser::new(&mut lexer);
@@ -236,7 +789,7 @@ fn test_eval_helper(input: &str) -> EvaluatorResult {
 
     let mut environment = Rc::new(RefCell::new(Environment::new(None)));
 
-    Evaluator::eval(Box::new(program.as_node()), environment)
+    Evaluator::eval(&program, environment)
 }
 
 fn test_eval_integer_helper(object: Box<dyn Object>, value: i64, t: Option<ObjectType>) -> Integer {
@@ -0,0 +1,124 @@
+/// Unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line {
+    Unchanged(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// The changes between `before` and `after` in the unified format, empty when they are equal.
+pub fn unified_diff(name: &str, before: &str, after: &str) -> String {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let lines = diff_lines(&old, &new);
+
+    let mut output = String::new();
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Unchanged(..)))
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        return output;
+    }
+
+    output.push_str(&format!("--- {}\n+++ {}\n", name, name));
+
+    // changes closer than twice the context share the same hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changes {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+
+        // position of the hunk in both files, when a side is empty it is the line before it
+        let old_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+
+        for line in hunk {
+            match line {
+                Line::Unchanged(i, _) => output.push_str(&format!(" {}\n", old[*i])),
+                Line::Removed(i) => output.push_str(&format!("-{}\n", old[*i])),
+                Line::Added(j) => output.push_str(&format!("+{}\n", new[*j])),
+            }
+        }
+    }
+
+    output
+}
+
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+/// Lines of both sides matched through their longest common subsequence, removed lines come
+/// before the lines added in their place.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Line> {
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Unchanged(i, j));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(Line::Removed(i));
+            i += 1;
+        } else {
+            lines.push(Line::Added(j));
+            j += 1;
+        }
+    }
+    lines.extend((i..old.len()).map(Line::Removed));
+    lines.extend((j..new.len()).map(Line::Added));
+
+    lines
+}
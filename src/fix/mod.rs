@@ -0,0 +1,108 @@
+use std::rc::Rc;
+
+use crate::{
+    lexer::Lexer,
+    lint::{self, LintLevels},
+    log::diagnostic::{Applicability, Diagnostic, Suggestion, TextEdit},
+    parser::Parser,
+    source::SourceFile,
+};
+
+pub mod diff;
+
+/// The source is parsed again after every pass since fixing a problem can reveal another one,
+/// e.g: a nested if expression whose edits overlapped the ones of its parent. Fixes never undo
+/// each other so this bound is only a safety net.
+const MAX_PASSES: usize = 16;
+
+/// The outcome of `fix_source`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixed {
+    pub src: String,
+    /// Number of suggestions applied over all the passes.
+    pub applied: usize,
+}
+
+/// Applies every machine applicable suggestion of the warnings raised on `src` until there are
+/// none left, allowed lints are left alone. Nothing is fixed in a file that does not parse, its
+/// errors are returned instead.
+pub fn fix_source(
+    name: &str,
+    src: &str,
+    lint_levels: &LintLevels,
+) -> Result<Fixed, Vec<Diagnostic>> {
+    let mut fixed = Fixed {
+        src: src.to_string(),
+        applied: 0,
+    };
+
+    for _ in 0..MAX_PASSES {
+        let file = Rc::new(SourceFile::new(0, name, &fixed.src));
+        let mut lexer = Lexer::from_source(file);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        if !parser.errors.is_empty() {
+            return Err(parser.errors.iter().map(|e| e.to_diagnostic()).collect());
+        }
+        parser.warnings.extend(lint::semantic::check(&program));
+
+        let diagnostics = lint_levels.report(&parser.warnings, &parser.lint_attributes);
+        let suggestions: Vec<&Suggestion> = diagnostics
+            .iter()
+            .flat_map(|diagnostic| &diagnostic.suggestions)
+            .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+            .collect();
+
+        let (src, applied) = apply_suggestions(&fixed.src, &suggestions);
+        if applied == 0 {
+            break;
+        }
+
+        fixed.src = src;
+        fixed.applied += applied;
+    }
+
+    Ok(fixed)
+}
+
+/// Applies the suggestions in order, a suggestion whose edits overlap the ones of a suggestion
+/// already applied is skipped as a whole. Returns the new source and the number of suggestions
+/// applied.
+pub fn apply_suggestions(src: &str, suggestions: &[&Suggestion]) -> (String, usize) {
+    let mut edits: Vec<&TextEdit> = Vec::new();
+    let mut applied = 0;
+
+    for suggestion in suggestions {
+        let conflicts = suggestion
+            .edits
+            .iter()
+            .any(|edit| edits.iter().any(|other| overlap(edit, other)));
+
+        if !conflicts && !suggestion.edits.is_empty() {
+            edits.extend(&suggestion.edits);
+            applied += 1;
+        }
+    }
+
+    edits.sort_by_key(|edit| edit.span.start);
+
+    let mut fixed = String::with_capacity(src.len());
+    let mut position = 0;
+    for edit in edits {
+        fixed.push_str(&src[position..edit.span.start]);
+        fixed.push_str(&edit.replacement);
+        position = edit.span.end;
+    }
+    fixed.push_str(&src[position..]);
+
+    (fixed, applied)
+}
+
+/// Two insertions at the same position overlap since their order would be ambiguous.
+fn overlap(a: &TextEdit, b: &TextEdit) -> bool {
+    a.span.start == b.span.start || (a.span.start < b.span.end && b.span.start < a.span.end)
+}
+
+#[cfg(test)]
+mod test;
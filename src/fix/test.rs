@@ -0,0 +1,108 @@
+mod tests {
+    use std::{fs, rc::Rc};
+
+    use crate::{
+        fix::{apply_suggestions, diff::unified_diff, fix_source},
+        fixtures,
+        lint::LintLevels,
+        log::diagnostic::{Applicability, Suggestion, TextEdit},
+        source::{SourceFile, Span},
+    };
+
+    /// Every `name.eheh` of the fixtures directory must become `name.fixed.eheh` once fixed.
+    #[test]
+    fn test_fix_fixtures() {
+        let fixtures = fixtures::root().join("fix");
+        let mut checked = 0;
+
+        for entry in fs::read_dir(&fixtures).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            if !name.ends_with(".eheh") || name.ends_with(".fixed.eheh") {
+                continue;
+            }
+
+            let before = fs::read_to_string(&path).unwrap();
+            let expected =
+                fs::read_to_string(path.with_extension("fixed.eheh")).unwrap_or_else(|_| {
+                    panic!("{} has no `.fixed.eheh` counterpart", name);
+                });
+
+            let fixed = fix_source(&name, &before, &LintLevels::new()).unwrap();
+            assert_eq!(fixed.src, expected, "unexpected fixes in {}", name);
+            assert!(fixed.applied > 0, "nothing was fixed in {}", name);
+
+            // fixing twice changes nothing
+            let again = fix_source(&name, &fixed.src, &LintLevels::new()).unwrap();
+            assert_eq!(again.applied, 0, "{} is not fixed in a single run", name);
+            assert_eq!(again.src, fixed.src);
+
+            checked += 1;
+        }
+
+        assert!(checked > 0, "no fixture found in {}", fixtures.display());
+    }
+
+    #[test]
+    fn test_fix_source_with_errors() {
+        const CODE: &str = r#"if (a) { 1 } else {}
+let = 2;"#;
+
+        let errors = fix_source("main.eheh", CODE, &LintLevels::new()).unwrap_err();
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_suggestions() {
+        const CODE: &str = "abcdef";
+        let file = Rc::new(SourceFile::anonymous(CODE));
+        let edit = |start, end, replacement: &str| {
+            TextEdit::new(Span::new(Rc::clone(&file), start, end), replacement)
+        };
+
+        let first = Suggestion::new(
+            "",
+            vec![edit(0, 2, "AB"), edit(4, 4, "_")],
+            Applicability::MachineApplicable,
+        );
+        let overlapping = Suggestion::new(
+            "",
+            vec![edit(5, 6, "F"), edit(1, 3, "")],
+            Applicability::MachineApplicable,
+        );
+        let last = Suggestion::new("", vec![edit(5, 6, "F")], Applicability::MachineApplicable);
+
+        let (fixed, applied) = apply_suggestions(CODE, &[&first, &overlapping, &last]);
+        assert_eq!(fixed, "ABcd_eF");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        const BEFORE: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        const AFTER: &str = "a\nB\nc\nd\ne\nf\ng\nh\ni\n";
+
+        assert_eq!(
+            unified_diff("main.eheh", BEFORE, AFTER),
+            [
+                "--- main.eheh",
+                "+++ main.eheh",
+                "@@ -1,5 +1,5 @@",
+                " a",
+                "-b",
+                "+B",
+                " c",
+                " d",
+                " e",
+                "@@ -7,4 +7,3 @@",
+                " g",
+                " h",
+                " i",
+                "-j",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(unified_diff("main.eheh", BEFORE, BEFORE), "");
+    }
+}
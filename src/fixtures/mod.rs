@@ -0,0 +1,30 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The `tests/fixtures` directory of the crate: `fmt` and `fix` hold the expected outputs of the
+/// formatter and of `eheh fix`, every `.eheh` file of it is a program parsing without errors.
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// Every `.eheh` file of `dir` and of its subdirectories, sorted.
+pub fn paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            paths.extend(self::paths(&path));
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "eheh")
+        {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    paths
+}
@@ -0,0 +1,207 @@
+/// A document to pretty-print: text and the places where lines may be broken. A group is
+/// printed on a single line when it fits in the remaining width, every line of the group is
+/// broken otherwise, e.g: the arguments of a call go one per line once they are too long.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Doc {
+    Text(String),
+    /// A space, or a line break when the enclosing group is broken.
+    Line,
+    /// Nothing, or a line break when the enclosing group is broken.
+    SoftLine,
+    /// Always a line break, the groups containing it are always broken.
+    HardLine,
+    /// The lines broken inside of the document are indented one more level.
+    Indent(Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+impl Doc {
+    pub fn text(text: impl Into<String>) -> Self {
+        Doc::Text(text.into())
+    }
+
+    pub fn indent(doc: Doc) -> Self {
+        Doc::Indent(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Self {
+        Doc::Group(Box::new(doc))
+    }
+
+    /// The documents with `separator` between each of them.
+    pub fn join(docs: Vec<Doc>, separator: Doc) -> Self {
+        let mut joined = Vec::with_capacity(docs.len() * 2);
+
+        for (index, doc) in docs.into_iter().enumerate() {
+            if index > 0 {
+                joined.push(separator.clone());
+            }
+            joined.push(doc);
+        }
+
+        Doc::Concat(joined)
+    }
+
+    fn has_hard_line(&self) -> bool {
+        match self {
+            Doc::HardLine => true,
+            Doc::Indent(doc) | Doc::Group(doc) => doc.has_hard_line(),
+            Doc::Concat(docs) => docs.iter().any(Doc::has_hard_line),
+            Doc::Text(_) | Doc::Line | Doc::SoftLine => false,
+        }
+    }
+
+    /// Prints the document in lines of at most `width` characters when possible, nested lines
+    /// are indented by `indent` spaces per level. Lines never end with whitespace.
+    pub fn render(&self, width: usize, indent: usize) -> String {
+        let mut output = String::new();
+        let mut column = 0;
+        // the indentation of a new line is only written along with its first text
+        let mut pending_indent: Option<usize> = None;
+
+        let mut stack: Vec<(usize, Mode, &Doc)> = vec![(0, Mode::Break, self)];
+        while let Some((level, mode, doc)) = stack.pop() {
+            let text = match doc {
+                Doc::Text(text) => text.as_str(),
+                Doc::Line if mode == Mode::Flat => " ",
+                Doc::SoftLine if mode == Mode::Flat => "",
+                Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                    output.push('\n');
+                    column = level;
+                    pending_indent = Some(level);
+                    continue;
+                }
+                Doc::Indent(doc) => {
+                    stack.push((level + indent, mode, doc));
+                    continue;
+                }
+                Doc::Group(doc) => {
+                    // the groups inside of a flat one are flat too
+                    let flat = mode == Mode::Flat
+                        || !doc.has_hard_line()
+                            && fits(width as isize - column as isize, (level, doc), &stack);
+                    let mode = if flat { Mode::Flat } else { Mode::Break };
+
+                    stack.push((level, mode, doc));
+                    continue;
+                }
+                Doc::Concat(docs) => {
+                    stack.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+                    continue;
+                }
+            };
+
+            if text.is_empty() {
+                continue;
+            }
+
+            if let Some(level) = pending_indent.take() {
+                output.push_str(&" ".repeat(level));
+            }
+            output.push_str(text);
+
+            column = match text.rfind('\n') {
+                Some(index) => text[index + 1..].chars().count(),
+                None => column + text.chars().count(),
+            };
+        }
+
+        output
+    }
+}
+
+/// Whether `doc` printed flat, followed by the documents of `rest` up to their next line break,
+/// fits in `remaining` characters.
+fn fits(mut remaining: isize, (level, doc): (usize, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut stack: Vec<(usize, Mode, &Doc)> = vec![(level, Mode::Flat, doc)];
+    let mut rest = rest.iter().rev();
+
+    loop {
+        if remaining < 0 {
+            return false;
+        }
+
+        let Some((level, mode, doc)) = stack.pop().or_else(|| rest.next().copied()) else {
+            return true;
+        };
+
+        match doc {
+            Doc::Text(text) => match text.find('\n') {
+                Some(index) => return remaining >= text[..index].chars().count() as isize,
+                None => remaining -= text.chars().count() as isize,
+            },
+            Doc::Line if mode == Mode::Flat => remaining -= 1,
+            Doc::SoftLine if mode == Mode::Flat => {}
+            Doc::Line | Doc::SoftLine | Doc::HardLine => return true,
+            Doc::Indent(doc) | Doc::Group(doc) => stack.push((level, mode, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Doc;
+
+    /// `f(a, b, c)`, with the arguments one per line when they do not fit.
+    fn call(args: &[&str]) -> Doc {
+        let args = args.iter().map(|arg| Doc::text(*arg)).collect();
+
+        Doc::group(Doc::Concat(vec![
+            Doc::text("f("),
+            Doc::indent(Doc::Concat(vec![
+                Doc::SoftLine,
+                Doc::join(args, Doc::Concat(vec![Doc::text(","), Doc::Line])),
+            ])),
+            Doc::SoftLine,
+            Doc::text(")"),
+        ]))
+    }
+
+    #[test]
+    fn test_render_groups() {
+        assert_eq!(call(&["a", "b"]).render(10, 4), "f(a, b)");
+        assert_eq!(call(&["a", "b"]).render(6, 4), "f(\n    a,\n    b\n)");
+
+        // the text following the group counts
+        let statement = Doc::Concat(vec![call(&["a", "b"]), Doc::text(";")]);
+        assert_eq!(statement.render(8, 4), "f(a, b);");
+        assert_eq!(statement.render(7, 4), "f(\n    a,\n    b\n);");
+
+        // a nested group stays flat when it fits on its own line
+        let nested = Doc::group(Doc::Concat(vec![
+            Doc::text("g("),
+            Doc::indent(Doc::Concat(vec![Doc::SoftLine, call(&["a", "b"])])),
+            Doc::SoftLine,
+            Doc::text(")"),
+        ]));
+        assert_eq!(nested.render(10, 2), "g(f(a, b))");
+        assert_eq!(nested.render(9, 2), "g(\n  f(a, b)\n)");
+    }
+
+    #[test]
+    fn test_render_hard_lines() {
+        let block = Doc::group(Doc::Concat(vec![
+            Doc::text("{"),
+            Doc::indent(Doc::Concat(vec![
+                Doc::Line,
+                Doc::text("a;"),
+                Doc::HardLine,
+                Doc::HardLine,
+                Doc::text("b"),
+            ])),
+            Doc::Line,
+            Doc::text("}"),
+        ]));
+
+        // blank lines are not indented
+        assert_eq!(block.render(80, 4), "{\n    a;\n\n    b\n}");
+    }
+}
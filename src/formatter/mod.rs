@@ -0,0 +1,520 @@
+use std::rc::Rc;
+
+use crate::{
+    ast::{
+        expressions::{
+            boolean_expression::BooleanExpression, call_expression::CallExpression,
+            function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+            infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+            null_expression::NullExpression, prefix_expression::PrefixExpression,
+            string_literal::StringLiteral,
+        },
+        precedence::Precedence,
+        statements::{
+            attributed_statement::AttributedStatement, block_statement::BlockStatement,
+            declare_statement::DeclareStatement, defer_statement::DeferStatement,
+            expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+        },
+    },
+    lexer::Lexer,
+    log::diagnostic::Diagnostic,
+    parser::{starts_bare_condition, Parser},
+    source::SourceFile,
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+    traits::node::Node,
+    types::{ASTExpression, ASTStatement},
+};
+
+use self::doc::Doc;
+
+pub mod doc;
+
+/// Lines are broken once longer than this, unless there is nowhere to break them.
+pub const MAX_WIDTH: usize = 100;
+
+/// Number of spaces per indentation level.
+pub const INDENT: usize = 4;
+
+/// The canonical formatting of `src`. Nothing is formatted in a file that does not parse, its
+/// errors are returned instead.
+///
+/// Comments are kept: between statements they stay where they are, a comment written inside of
+/// an expression is moved on its own line before the statement.
+pub fn format_source(name: &str, src: &str) -> Result<String, Vec<Diagnostic>> {
+    let file = Rc::new(SourceFile::new(0, name, src));
+    let mut lexer = Lexer::from_source(Rc::clone(&file));
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+
+    if !parser.errors.is_empty() {
+        return Err(parser.errors.iter().map(|e| e.to_diagnostic()).collect());
+    }
+
+    let mut formatter = Formatter {
+        file,
+        printed: vec![false; parser.lexer.comments.len()],
+        comments: parser.lexer.comments.clone(),
+    };
+
+    let formatted = formatter
+        .statements(&program.statements, 0, src.len(), false)
+        .finish()
+        .render(MAX_WIDTH, INDENT);
+
+    if formatted.is_empty() {
+        Ok(formatted)
+    } else {
+        Ok(formatted + "\n")
+    }
+}
+
+struct Formatter {
+    file: Rc<SourceFile>,
+    comments: Vec<Token>,
+    /// Whether each comment was printed already.
+    printed: Vec<bool>,
+}
+
+impl Formatter {
+    /// The comments starting in `start..end` that were not printed yet, they are considered
+    /// printed.
+    fn take_comments(&mut self, start: usize, end: usize) -> Vec<Token> {
+        let mut taken = Vec::new();
+
+        for (comment, printed) in self.comments.iter().zip(self.printed.iter_mut()) {
+            if !*printed && start <= comment.span.start && comment.span.start < end {
+                *printed = true;
+                taken.push(comment.clone());
+            }
+        }
+
+        taken
+    }
+
+    /// The statements written in `start..end` with their comments, one per line. At most one
+    /// blank line is kept between them.
+    ///
+    /// A `block` only ends with a `;` when its last statement is not an expression.
+    fn statements(
+        &mut self,
+        statements: &[ASTStatement],
+        start: usize,
+        end: usize,
+        block: bool,
+    ) -> Lines {
+        let mut lines = Lines::new(Rc::clone(&self.file));
+        let mut cursor = start;
+
+        for (index, statement) in statements.iter().enumerate() {
+            let span = statement.span();
+            let next = statements.get(index + 1);
+
+            for comment in self.take_comments(cursor, span.start) {
+                lines.push_comment(&comment);
+            }
+
+            let tail = block && next.is_none();
+            let mut doc = self.statement(statement, tail, next);
+
+            for comment in self.take_comments(span.start, span.end) {
+                lines.push_comment(&comment);
+            }
+
+            // comments following the statement on the same line stay there
+            let line_end = self.file.src[span.end..]
+                .find('\n')
+                .map_or(self.file.src.len(), |offset| span.end + offset);
+            let next_start = next.map_or(end, |next| next.span().start);
+
+            let mut statement_end = span.end;
+            for comment in self.take_comments(span.end, line_end.min(next_start)) {
+                doc = Doc::Concat(vec![doc, Doc::text(" "), comment_doc(&comment)]);
+                lines.comments = true;
+                statement_end = comment.span.end;
+            }
+
+            lines.push(span.start, statement_end, doc);
+            cursor = statement_end;
+        }
+
+        for comment in self.take_comments(cursor, end) {
+            lines.push_comment(&comment);
+        }
+
+        lines
+    }
+
+    /// `tail` is the last statement of a block, `next` the statement following this one.
+    fn statement(
+        &mut self,
+        statement: &ASTStatement,
+        tail: bool,
+        next: Option<&ASTStatement>,
+    ) -> Doc {
+        let any = statement.as_any();
+
+        if let Some(attributed) = any.downcast_ref::<AttributedStatement>() {
+            let mut docs: Vec<Doc> = attributed
+                .attributes
+                .iter()
+                .flat_map(|attribute| [Doc::text(attribute.to_string()), Doc::HardLine])
+                .collect();
+            docs.push(self.statement(&attributed.statement, tail, next));
+
+            Doc::Concat(docs)
+        } else if let Some(declare) = any.downcast_ref::<DeclareStatement>() {
+            let mut docs = vec![Doc::text(format!(
+                "{} {}",
+                declare.token.literal(),
+                declare.name.value
+            ))];
+            if let Some(t) = &declare.type_specifier {
+                docs.push(Doc::text(format!(": {}", t)));
+            }
+            if let Some(value) = &declare.value {
+                docs.push(Doc::text(" = "));
+                docs.push(self.expression(value));
+            }
+            docs.push(Doc::text(";"));
+
+            Doc::Concat(docs)
+        } else if let Some(return_statement) = any.downcast_ref::<ReturnStatement>() {
+            match &return_statement.value {
+                Some(value) => Doc::Concat(vec![
+                    Doc::text("return "),
+                    self.expression(value),
+                    Doc::text(";"),
+                ]),
+                None => Doc::text("return;"),
+            }
+        } else if let Some(defer) = any.downcast_ref::<DeferStatement>() {
+            Doc::Concat(vec![
+                Doc::text(format!("{} ", defer.token.literal())),
+                self.expression(&defer.value),
+                Doc::text(";"),
+            ])
+        } else if let Some(block) = any.downcast_ref::<BlockStatement>() {
+            Doc::group(self.block(block))
+        } else if let Some(statement) = any.downcast_ref::<ExpressionStatement>() {
+            let expression = &statement.expression;
+            let ends_with_block = expression.as_any().is::<IfExpression>()
+                || expression.as_any().is::<FunctionLiteral>();
+
+            // an expression ending with a block only needs a `;` when the next statement would
+            // otherwise continue it, e.g: `if a { b } (c)` is a call
+            let semicolon = if tail {
+                false
+            } else if ends_with_block {
+                next.is_some_and(|next| {
+                    next.as_any()
+                        .downcast_ref::<ExpressionStatement>()
+                        .is_some_and(|next| {
+                            matches!(
+                                leading_token(&next.expression),
+                                TokenType::LPAREN
+                                    | TokenType::MINUS
+                                    | TokenType::DECR
+                                    | TokenType::INCR
+                                    | TokenType::RANGE
+                                    | TokenType::IRANGE
+                            )
+                        })
+                })
+            } else {
+                true
+            };
+
+            let doc = self.expression(expression);
+            if semicolon {
+                Doc::Concat(vec![doc, Doc::text(";")])
+            } else {
+                doc
+            }
+        } else {
+            Doc::text(statement.to_string())
+        }
+    }
+
+    /// The braces and statements of a block, its lines are broken by the enclosing group. A
+    /// block holding more than a single expression, or comments, is always broken.
+    fn block(&mut self, block: &BlockStatement) -> Doc {
+        let lines = self.statements(
+            &block.statements,
+            block.span.start + 1,
+            block.span.end - 1,
+            true,
+        );
+        if lines.docs.is_empty() {
+            return Doc::text("{}");
+        }
+
+        let inline = !lines.comments
+            && matches!(
+                block.statements.as_slice(),
+                [statement] if statement.as_any().is::<ExpressionStatement>()
+            );
+        let line = if inline { Doc::Line } else { Doc::HardLine };
+
+        Doc::Concat(vec![
+            Doc::text("{"),
+            Doc::indent(Doc::Concat(vec![line.clone(), lines.finish()])),
+            line,
+            Doc::text("}"),
+        ])
+    }
+
+    fn expression(&mut self, expression: &ASTExpression) -> Doc {
+        let any = expression.as_any();
+
+        if let Some(identifier) = any.downcast_ref::<Identifier>() {
+            Doc::text(identifier.value.clone())
+        } else if let Some(integer) = any.downcast_ref::<IntegerLiteral>() {
+            Doc::text(integer.token.literal())
+        } else if let Some(string) = any.downcast_ref::<StringLiteral>() {
+            Doc::text(string.token.span.source_text())
+        } else if let Some(boolean) = any.downcast_ref::<BooleanExpression>() {
+            Doc::text(boolean.token.literal())
+        } else if let Some(null) = any.downcast_ref::<NullExpression>() {
+            Doc::text(null.token.literal())
+        } else if let Some(prefix) = any.downcast_ref::<PrefixExpression>() {
+            // `- -a` must not become `--a`
+            let glued = prefix.operator.ends_with('-')
+                && prefix
+                    .rhs
+                    .as_any()
+                    .downcast_ref::<PrefixExpression>()
+                    .is_some_and(|rhs| rhs.operator.starts_with('-'));
+            let parenthesized = glued || prefix.rhs.as_any().is::<InfixExpression>();
+
+            Doc::Concat(vec![
+                Doc::text(prefix.operator.clone()),
+                self.operand(&prefix.rhs, parenthesized),
+            ])
+        } else if let Some(infix) = any.downcast_ref::<InfixExpression>() {
+            // every operator is left associative
+            let operator = precedence(expression);
+
+            Doc::Concat(vec![
+                self.operand(&infix.lhs, precedence(&infix.lhs) < operator),
+                Doc::text(format!(" {} ", infix.operator)),
+                self.operand(&infix.rhs, precedence(&infix.rhs) <= operator),
+            ])
+        } else if let Some(call) = any.downcast_ref::<CallExpression>() {
+            let function = self.operand(&call.function, function_needs_parentheses(&call.function));
+            let mut args: Vec<Doc> = call.args.iter().map(|arg| self.expression(arg)).collect();
+            for arg in &call.named_args {
+                args.push(Doc::Concat(vec![
+                    Doc::text(format!("{} = ", arg.name.value)),
+                    self.expression(&arg.value),
+                ]));
+            }
+
+            Doc::Concat(vec![function, self.delimited(args)])
+        } else if let Some(if_expression) = any.downcast_ref::<IfExpression>() {
+            self.if_expression(if_expression)
+        } else if let Some(function) = any.downcast_ref::<FunctionLiteral>() {
+            self.function_literal(function)
+        } else {
+            Doc::text(expression.to_string())
+        }
+    }
+
+    fn operand(&mut self, expression: &ASTExpression, parenthesized: bool) -> Doc {
+        let doc = self.expression(expression);
+
+        if parenthesized {
+            Doc::Concat(vec![Doc::text("("), doc, Doc::text(")")])
+        } else {
+            doc
+        }
+    }
+
+    /// `(a, b)`, with one item per line when they do not fit.
+    fn delimited(&mut self, items: Vec<Doc>) -> Doc {
+        if items.is_empty() {
+            return Doc::text("()");
+        }
+
+        Doc::group(Doc::Concat(vec![
+            Doc::text("("),
+            Doc::indent(Doc::Concat(vec![
+                Doc::SoftLine,
+                Doc::join(items, Doc::Concat(vec![Doc::text(","), Doc::Line])),
+            ])),
+            Doc::SoftLine,
+            Doc::text(")"),
+        ]))
+    }
+
+    /// Both branches are broken when one of them is.
+    fn if_expression(&mut self, if_expression: &IfExpression) -> Doc {
+        let condition = self.expression(&if_expression.condition);
+
+        // a condition starting with `(` must be wrapped as a whole
+        let condition = if starts_bare_condition(&leading_token(&if_expression.condition)) {
+            condition
+        } else {
+            Doc::Concat(vec![Doc::text("("), condition, Doc::text(")")])
+        };
+
+        let mut docs = vec![
+            Doc::text("if "),
+            condition,
+            Doc::text(" "),
+            self.block(&if_expression.consequence),
+        ];
+        if let Some(alternative) = &if_expression.alternative {
+            docs.push(Doc::text(" else "));
+            docs.push(self.block(alternative));
+        }
+
+        Doc::group(Doc::Concat(docs))
+    }
+
+    fn function_literal(&mut self, function: &FunctionLiteral) -> Doc {
+        let mut docs = vec![Doc::text(match &function.name {
+            Some(name) => format!("fn {}", name.value),
+            None => "fn".to_string(),
+        })];
+
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let mut docs = vec![Doc::text(match &parameter.t {
+                    Some(t) => format!("{}: {}", parameter.identifier.value, t.value),
+                    None => parameter.identifier.value.clone(),
+                })];
+                if parameter.rest.is_some() {
+                    docs.insert(0, Doc::text(".."));
+                }
+                if let Some(default) = &parameter.default {
+                    docs.push(Doc::text(" = "));
+                    docs.push(self.expression(default));
+                }
+
+                Doc::Concat(docs)
+            })
+            .collect();
+        docs.push(self.delimited(parameters));
+
+        if let Some(return_type) = &function.return_type {
+            docs.push(Doc::text(format!(" -> {}", return_type.value)));
+        }
+
+        docs.push(Doc::text(" "));
+        docs.push(Doc::group(self.block(&function.body)));
+
+        Doc::Concat(docs)
+    }
+}
+
+/// The lines of a sequence of statements and comments, blank lines between them are kept.
+struct Lines {
+    file: Rc<SourceFile>,
+    docs: Vec<Doc>,
+    /// Where the previous line ends in the source.
+    previous_end: Option<usize>,
+    /// Whether a comment was printed, a comment ending a line cannot be followed by code.
+    comments: bool,
+}
+
+impl Lines {
+    fn new(file: Rc<SourceFile>) -> Self {
+        Self {
+            file,
+            docs: Vec::new(),
+            previous_end: None,
+            comments: false,
+        }
+    }
+
+    /// `start..end` is where the line was written in the source.
+    fn push(&mut self, start: usize, end: usize, doc: Doc) {
+        if let Some(previous_end) = self.previous_end {
+            self.docs.push(Doc::HardLine);
+
+            let blank_line = previous_end < start
+                && self.file.src[previous_end..start].matches('\n').count() > 1;
+            if blank_line {
+                self.docs.push(Doc::HardLine);
+            }
+        }
+
+        self.docs.push(doc);
+        self.previous_end = Some(end);
+    }
+
+    fn push_comment(&mut self, comment: &Token) {
+        self.push(comment.span.start, comment.span.end, comment_doc(comment));
+        self.comments = true;
+    }
+
+    fn finish(self) -> Doc {
+        Doc::Concat(self.docs)
+    }
+}
+
+fn comment_doc(comment: &Token) -> Doc {
+    Doc::text(comment.span.source_text().trim_end())
+}
+
+/// The precedence of the operator of `expression`, operands bind tighter than any operator.
+fn precedence(expression: &ASTExpression) -> Precedence {
+    let any = expression.as_any();
+
+    if let Some(infix) = any.downcast_ref::<InfixExpression>() {
+        Precedence::from_token_type(&infix.token.t)
+    } else if any.is::<PrefixExpression>() {
+        Precedence::PREFIX
+    } else if any.is::<CallExpression>() {
+        Precedence::CALL
+    } else {
+        Precedence::PATH
+    }
+}
+
+fn function_needs_parentheses(function: &ASTExpression) -> bool {
+    !(function.as_any().is::<Identifier>() || function.as_any().is::<CallExpression>())
+}
+
+/// The type of the first token of `expression` once formatted.
+fn leading_token(expression: &ASTExpression) -> TokenType {
+    let any = expression.as_any();
+
+    if let Some(infix) = any.downcast_ref::<InfixExpression>() {
+        if precedence(&infix.lhs) < precedence(expression) {
+            TokenType::LPAREN
+        } else {
+            leading_token(&infix.lhs)
+        }
+    } else if let Some(call) = any.downcast_ref::<CallExpression>() {
+        if function_needs_parentheses(&call.function) {
+            TokenType::LPAREN
+        } else {
+            leading_token(&call.function)
+        }
+    } else if let Some(prefix) = any.downcast_ref::<PrefixExpression>() {
+        prefix.token.t.clone()
+    } else if let Some(identifier) = any.downcast_ref::<Identifier>() {
+        identifier.token.t.clone()
+    } else if let Some(boolean) = any.downcast_ref::<BooleanExpression>() {
+        boolean.token.t.clone()
+    } else if any.is::<IntegerLiteral>() {
+        TokenType::INT
+    } else if any.is::<StringLiteral>() {
+        TokenType::STRING
+    } else if any.is::<IfExpression>() {
+        TokenType::KEYWORD(KeywordTokenType::IF)
+    } else if any.is::<FunctionLiteral>() {
+        TokenType::KEYWORD(KeywordTokenType::FUN)
+    } else {
+        TokenType::KEYWORD(KeywordTokenType::NULL)
+    }
+}
+
+#[cfg(test)]
+mod test;
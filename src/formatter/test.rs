@@ -0,0 +1,110 @@
+mod tests {
+    use std::fs;
+
+    use crate::{fixtures, formatter::format_source, lexer::Lexer, parser::Parser};
+
+    /// The statements of `code` as written back by the AST, and its comments.
+    fn parse(code: &str) -> (Vec<String>, Vec<String>) {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+
+        let statements = program
+            .statements
+            .iter()
+            .map(|statement| statement.to_string())
+            .collect();
+        let comments = parser
+            .lexer
+            .comments
+            .iter()
+            .map(|comment| comment.span.source_text().trim_end().to_string())
+            .collect();
+
+        (statements, comments)
+    }
+
+    /// Formatting keeps the meaning and the comments of every fixture, and formatting twice
+    /// changes nothing.
+    #[test]
+    fn test_format_round_trip() {
+        let root = fixtures::root();
+        let paths = fixtures::paths(&root);
+        assert!(!paths.is_empty(), "no fixture found in {}", root.display());
+
+        for path in paths {
+            let name = path.to_string_lossy().to_string();
+            let src = fs::read_to_string(&path).unwrap();
+
+            let formatted = format_source(&name, &src).unwrap();
+            let (statements, mut comments) = parse(&src);
+            let (formatted_statements, mut formatted_comments) = parse(&formatted);
+
+            assert_eq!(statements, formatted_statements, "{} changed meaning", name);
+
+            // comments written inside of expressions are moved before their statement
+            comments.sort();
+            formatted_comments.sort();
+            assert_eq!(comments, formatted_comments, "{} lost comments", name);
+
+            let again = format_source(&name, &formatted).unwrap();
+            assert_eq!(again, formatted, "formatting {} is not idempotent", name);
+        }
+    }
+
+    /// Every `name.eheh` of the formatter fixtures must become `name.formatted.eheh`.
+    #[test]
+    fn test_format_fixtures() {
+        let fixtures = fixtures::root().join("fmt");
+        let mut checked = 0;
+
+        for entry in fs::read_dir(&fixtures).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            if !name.ends_with(".eheh") || name.ends_with(".formatted.eheh") {
+                continue;
+            }
+
+            let src = fs::read_to_string(&path).unwrap();
+            let expected = fs::read_to_string(path.with_extension("formatted.eheh"))
+                .unwrap_or_else(|_| panic!("{} has no `.formatted.eheh` counterpart", name));
+
+            assert_eq!(format_source(&name, &src).unwrap(), expected, "{}", name);
+            checked += 1;
+        }
+
+        assert!(checked > 0, "no fixture found in {}", fixtures.display());
+    }
+
+    #[test]
+    fn test_format_blocks() {
+        const CODE: &str = "let f = fn() { a; }; let g = fn() { let a = 1; a };";
+
+        assert_eq!(
+            format_source("main.eheh", CODE).unwrap(),
+            "let f = fn() { a };\nlet g = fn() {\n    let a = 1;\n    a\n};\n"
+        );
+        assert_eq!(format_source("main.eheh", "").unwrap(), "");
+        assert_eq!(
+            format_source("main.eheh", "  // only\n\n").unwrap(),
+            "// only\n"
+        );
+    }
+
+    #[test]
+    fn test_format_statements_ending_with_a_block() {
+        // the `;` keeps the next statement from being read as a subtraction
+        const CODE: &str = "if a { b }; -c; if a { b }; e";
+
+        assert_eq!(
+            format_source("main.eheh", CODE).unwrap(),
+            "if a { b };\n-c;\nif a { b }\ne;\n"
+        );
+    }
+
+    #[test]
+    fn test_format_source_with_errors() {
+        assert!(format_source("main.eheh", "let = 2;").is_err());
+    }
+}
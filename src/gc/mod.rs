@@ -0,0 +1,204 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Display,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    objects::environment::Environment,
+    traits::object::{Object, ObjectType},
+};
+
+/// Collections run once this many environments were tracked since the last one, or as many as
+/// survived it when there are more.
+const MIN_THRESHOLD: usize = 1024;
+
+/// The environments captured by functions, the only ones a reference cycle can go through: a
+/// function stored in the environment it captures, or in a frame it outlives, keeps it alive.
+struct Heap {
+    environments: Vec<Weak<RefCell<Environment>>>,
+    /// Tracked since the last collection.
+    allocated: usize,
+    collections: usize,
+    freed: usize,
+}
+
+thread_local! {
+    static HEAP: RefCell<Heap> = const {
+        RefCell::new(Heap {
+            environments: Vec::new(),
+            allocated: 0,
+            collections: 0,
+            freed: 0,
+        })
+    };
+}
+
+/// The state of the heap of the thread, see `gc_stats()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// The tracked environments alive, unreachable ones included until they are collected.
+    pub environments: usize,
+    pub collections: usize,
+    /// The environments freed by the collections.
+    pub freed: usize,
+}
+
+/// Tracks an environment captured by a function, collecting the cycles of the heap when enough
+/// environments were tracked since the last collection.
+pub fn track(environment: &Rc<RefCell<Environment>>) {
+    let mut env = environment.borrow_mut();
+    if env.tracked {
+        return;
+    }
+    env.tracked = true;
+    drop(env);
+
+    let collect_now = HEAP.with_borrow_mut(|heap| {
+        heap.environments.push(Rc::downgrade(environment));
+        heap.allocated += 1;
+
+        heap.allocated >= MIN_THRESHOLD.max(heap.environments.len() - heap.allocated)
+    });
+
+    if collect_now {
+        collect();
+    }
+}
+
+/// Frees the tracked environments only reachable from each other, returns how many there were.
+///
+/// The references to an environment that do not come from the tracked ones are counted by
+/// subtracting the ones that do from its reference count: the environments referenced from
+/// anywhere else, e.g: the stack of the evaluator, and everything they reach are alive. The
+/// collection is skipped when an environment is borrowed.
+pub fn collect() -> usize {
+    let weak = HEAP.with_borrow_mut(|heap| {
+        heap.environments.retain(|env| env.strong_count() > 0);
+        heap.allocated = 0;
+        heap.collections += 1;
+        heap.environments.clone()
+    });
+
+    let environments: Vec<Rc<RefCell<Environment>>> =
+        weak.iter().filter_map(Weak::upgrade).collect();
+    let index: HashMap<*const RefCell<Environment>, usize> = environments
+        .iter()
+        .enumerate()
+        .map(|(i, env)| (Rc::as_ptr(env), i))
+        .collect();
+
+    let Ok(borrowed) = environments
+        .iter()
+        .map(|env| env.try_borrow())
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return 0;
+    };
+
+    // `environments` holds one of the references
+    let mut external: Vec<isize> = environments
+        .iter()
+        .map(|env| Rc::strong_count(env) as isize - 1)
+        .collect();
+    for env in &borrowed {
+        env.trace(false, &mut |held| {
+            if let Some(&i) = index.get(&Rc::as_ptr(held)) {
+                external[i] -= 1;
+            }
+        });
+    }
+
+    let mut reachable = vec![false; environments.len()];
+    let mut pending: Vec<usize> = (0..environments.len())
+        .filter(|&i| external[i] > 0)
+        .collect();
+    while let Some(i) = pending.pop() {
+        if reachable[i] {
+            continue;
+        }
+        reachable[i] = true;
+
+        borrowed[i].trace(true, &mut |held| {
+            if let Some(&j) = index.get(&Rc::as_ptr(held)) {
+                pending.push(j);
+            }
+        });
+    }
+    drop(borrowed);
+
+    // emptying the unreachable environments breaks their cycles, the values are dropped once
+    // no environment is borrowed anymore
+    let mut garbage = Vec::new();
+    for (env, _) in environments
+        .iter()
+        .zip(&reachable)
+        .filter(|(_, reachable)| !**reachable)
+    {
+        let mut env = env.borrow_mut();
+        garbage.push((
+            std::mem::take(&mut env.store),
+            std::mem::take(&mut env.slots),
+            env.outer.take(),
+        ));
+    }
+
+    let freed = garbage.len();
+    drop(garbage);
+    drop(environments);
+
+    HEAP.with_borrow_mut(|heap| heap.freed += freed);
+    freed
+}
+
+impl Object for Stats {
+    fn t(&self) -> ObjectType {
+        ObjectType::GcStats
+    }
+
+    fn inspect(&self) -> String {
+        self.to_string()
+    }
+
+    fn as_any_ref(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(self) -> Box<dyn std::any::Any> {
+        Box::new(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Object> {
+        Box::new(*self)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "gc_stats {{ environments: {}, collections: {}, freed: {} }}",
+            self.environments, self.collections, self.freed
+        )
+    }
+}
+
+pub fn stats() -> Stats {
+    HEAP.with_borrow(|heap| Stats {
+        environments: heap
+            .environments
+            .iter()
+            .filter(|env| env.strong_count() > 0)
+            .count(),
+        collections: heap.collections,
+        freed: heap.freed,
+    })
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        evaluator::Evaluator,
+        gc::{self, Stats},
+        lexer::Lexer,
+        objects::environment::Environment,
+        parser::Parser,
+        traits::object::{Object, ObjectType},
+    };
+
+    fn eval(input: &str, environment: Rc<RefCell<Environment>>) -> Box<dyn Object> {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        Evaluator::eval(&program, environment).unwrap()
+    }
+
+    #[test]
+    fn test_collect_cycles() {
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+        eval(
+            "let f = fn() { f }; let make = fn() { let g = fn() { g }; 0 }; make();",
+            Rc::clone(&environment),
+        );
+
+        // the global environment is still in use, the frame of `make` is not
+        assert_eq!(gc::stats().environments, 2);
+        assert_eq!(gc::collect(), 1);
+        let f = eval("f()", Rc::clone(&environment));
+        assert_eq!(f.t(), ObjectType::Function);
+        drop(f);
+
+        drop(environment);
+        assert_eq!(gc::collect(), 1);
+        assert_eq!(
+            gc::stats(),
+            Stats {
+                environments: 0,
+                collections: 2,
+                freed: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_keep_reachable() {
+        const CODE: &str = "
+            let make = fn(n) { let get = fn() { get }; get };
+            let keep = make(1);
+            let f = fn() { keep };";
+
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+        eval(CODE, Rc::clone(&environment));
+
+        // the frame of `make` is only reachable through the global environment
+        assert_eq!(gc::collect(), 0);
+        let value = eval("keep", Rc::clone(&environment));
+        assert_eq!(gc::collect(), 0);
+        drop(environment);
+
+        // `value` captures the frame, whose outer environment is the global one
+        assert_eq!(gc::collect(), 0);
+        assert_eq!(gc::stats().environments, 2);
+        drop(value);
+        assert_eq!(gc::collect(), 2);
+        assert_eq!(gc::stats().environments, 0);
+    }
+
+    /// A million closures each in a cycle with the frame they capture.
+    #[test]
+    fn test_bounded_memory() {
+        const CODE: &str = "
+            let make = fn(n) { let self_ref = fn() { self_ref }; n };
+            let run = fn(n) { if n == 0 { 0 } else { make(n); run(n - 1) } };
+            run(1000000);";
+
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+        assert_eq!(eval(CODE, environment).to_string(), "0");
+
+        let stats = gc::stats();
+        assert!(stats.environments <= 2048, "{:?}", stats);
+        assert!(stats.freed >= 1000000 - 2048, "{:?}", stats);
+    }
+}
@@ -0,0 +1,416 @@
+use std::fmt::{Display, Write};
+
+/// A JSON value, used to export diagnostics for other tools and to talk to editors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    /// The fields keep their insertion order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Parses a JSON document, the fraction and the exponent of numbers are dropped since only
+    /// integers are represented.
+    pub fn parse(src: &str) -> Result<Json, String> {
+        let mut parser = JsonParser {
+            chars: src.chars().collect(),
+            index: 0,
+        };
+
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(c) => Err(parser.unexpected(c)),
+            None => Ok(value),
+        }
+    }
+
+    pub fn object() -> Self {
+        Json::Object(Vec::new())
+    }
+
+    /// Adds a field to an object, does nothing on other values.
+    pub fn with(mut self, key: &str, value: impl Into<Json>) -> Self {
+        if let Json::Object(fields) = &mut self {
+            fields.push((key.to_string(), value.into()));
+        }
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(value) => write!(f, "{}", value),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl JsonParser {
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => {
+                self.index += 1;
+                let mut values = Vec::new();
+                if !self.eat(']') {
+                    loop {
+                        values.push(self.value()?);
+                        if self.eat(']') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+
+                Ok(Json::Array(values))
+            }
+            Some('{') => {
+                self.index += 1;
+                let mut fields = Vec::new();
+                if !self.eat('}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.expect(':')?;
+                        fields.push((key, self.value()?));
+                        if self.eat('}') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+
+                Ok(Json::Object(fields))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(self.unexpected(c)),
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, String> {
+        for expected in keyword.chars() {
+            match self.next() {
+                Some(c) if c == expected => {}
+                Some(c) => return Err(self.unexpected(c)),
+                None => return Err("unexpected end of input".to_string()),
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.index;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.index += 1;
+        }
+
+        let digits: String = self.chars[start..self.index].iter().collect();
+        let value = digits
+            .parse()
+            .map_err(|_| format!("invalid number `{}`", digits))?;
+
+        if self.eat('.') {
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.index += 1;
+            }
+        }
+        if self.eat('e') || self.eat('E') {
+            if !self.eat('+') {
+                self.eat('-');
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.index += 1;
+            }
+        }
+
+        Ok(Json::Number(value))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let mut code = self.code_unit()?;
+                        // a surrogate pair encodes a character outside of the basic plane
+                        if (0xd800..0xdc00).contains(&code) && self.eat('\\') {
+                            self.expect('u')?;
+                            let low = self.code_unit()?;
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00));
+                        }
+                        string.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(c) if c == '"' || c == '\\' || c == '/' => string.push(c),
+                    Some(c) => return Err(format!("invalid escape `\\{}`", c)),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(c) => string.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn code_unit(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or("invalid unicode escape".to_string())?;
+            code = code * 16 + digit;
+        }
+
+        Ok(code)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+        {
+            self.index += 1;
+        }
+    }
+
+    /// Consumes `c` if it is the next character, whitespace excluded.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.index += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            return Ok(());
+        }
+
+        match self.peek() {
+            Some(found) => Err(self.unexpected(found)),
+            None => Err(format!("expected `{}`, found the end of input", c)),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.index += 1;
+        c
+    }
+
+    fn unexpected(&self, c: char) -> String {
+        format!("unexpected `{}` at {}", c, self.index)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value as i64)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Json;
+
+    #[test]
+    fn test_serialize() {
+        let json = Json::object()
+            .with("name", "eheh \"lang\"\n")
+            .with("version", 1usize)
+            .with("tags", vec!["fast", "fun"])
+            .with("license", None::<String>)
+            .with("stable", false);
+
+        assert_eq!(
+            json.to_string(),
+            r#"{"name":"eheh \"lang\"\n","version":1,"tags":["fast","fun"],"license":null,"stable":false}"#
+        );
+        assert_eq!(json.get("tags").unwrap().as_array().unwrap().len(), 2);
+        assert_eq!(Json::from("\u{1}").to_string(), r#""\u0001""#);
+    }
+
+    #[test]
+    fn test_parse() {
+        const CODE: &str = r#" {"id": -12, "ok": true, "none": null,
+            "text": "a\"b\\c\n\u00e9\ud83d\ude00", "list": [1.5, 2e3, []], "empty": {}} "#;
+
+        let json = Json::parse(CODE).unwrap();
+        assert_eq!(json.get("id").unwrap().as_i64(), Some(-12));
+        assert_eq!(json.get("ok").unwrap().as_bool(), Some(true));
+        assert_eq!(json.get("none"), Some(&Json::Null));
+        assert_eq!(json.get("text").unwrap().as_str(), Some("a\"b\\c\né😀"));
+        assert_eq!(
+            json.get("list"),
+            Some(&Json::Array(vec![
+                Json::Number(1),
+                Json::Number(2),
+                Json::Array(Vec::new())
+            ]))
+        );
+        assert_eq!(json.get("empty"), Some(&Json::object()));
+
+        // what is serialized parses back
+        let serialized = Json::object()
+            .with("message", "tab\t\u{1}")
+            .with("n", 3usize);
+        assert_eq!(Json::parse(&serialized.to_string()), Ok(serialized));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        const CODES: [&str; 6] = ["", "[1,]", "{\"a\" 1}", "\"open", "nul", "1 2"];
+
+        for code in CODES {
+            assert!(Json::parse(code).is_err(), "{}", code);
+        }
+    }
+}
@@ -1,369 +1,380 @@
-use crate::token::{
-    token_type::{KeywordTokenType, TokenType},
-    Token,
+use std::rc::Rc;
+
+use crate::{
+    log::error::lexer::{LexerError, LexerErrorCode},
+    source::{SourceFile, Span},
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        trivia::{SyntaxToken, Trivia, TriviaKind},
+        Token,
+    },
 };
 
+/// Scans the bytes of a `SourceFile` into tokens, the literal of every token is a span of the
+/// source so nothing gets copied.
 #[derive(Debug, Clone)]
 pub struct Lexer {
-    pub input: Vec<char>,
-    pub raw_input: String,
-    pub line: usize,
-    pub last_new_line: usize,
+    pub file: Rc<SourceFile>,
+    /// Byte offset in `file.src` of the next character to scan.
     pub position: usize,
-    pub next_position: usize,
-    pub c: char,
+    pub errors: Vec<LexerError>,
+    /// The comments skipped so far, in order. The parser never sees them but the formatter
+    /// prints them back.
+    pub comments: Vec<Token>,
+    /// Every token scanned so far along with its trivia, only filled in lossless mode.
+    pub tokens: Vec<SyntaxToken>,
+    lossless: bool,
+    /// Set once the iterator handed out the `EOF` token.
+    exhausted: bool,
 }
 
 impl Lexer {
     pub fn new(input: &str) -> Self {
-        let mut new_lexer = Self {
-            input: input.chars().into_iter().collect(),
-            raw_input: input.to_owned(),
-            position: 0,
-            next_position: 0,
-            c: '\0',
-            line: 1,
-            last_new_line: 0,
-        };
+        Self::from_source(Rc::new(SourceFile::anonymous(input)))
+    }
 
-        new_lexer.advance();
+    pub fn from_source(file: Rc<SourceFile>) -> Self {
+        Self {
+            file,
+            position: 0,
+            errors: Vec::new(),
+            comments: Vec::new(),
+            tokens: Vec::new(),
+            lossless: false,
+            exhausted: false,
+        }
+    }
 
-        new_lexer
+    /// A lexer keeping the whitespace and the comments: `next_token` still hands out bare tokens
+    /// so it can feed the parser, but `tokens` records them with their trivia.
+    pub fn lossless(file: Rc<SourceFile>) -> Self {
+        Self {
+            lossless: true,
+            ..Self::from_source(file)
+        }
     }
 
     pub fn get_line(&self, n: usize) -> Option<String> {
-        let splitted_code = self.raw_input.split('\n').collect::<Vec<&str>>();
+        self.file.line(n).map(str::to_string)
+    }
 
-        if n - 1 >= splitted_code.len() {
-            None
-        } else {
-            Some(splitted_code[n - 1].to_string())
+    /// Scans the next token, once the end of the input is reached every call returns `EOF`.
+    pub fn next_token(&mut self) -> Token {
+        if self.lossless {
+            let token = self.next_syntax_token();
+            self.tokens.push(token.clone());
+
+            return token.token;
         }
+
+        while self.scan_trivia(true).is_some() {}
+
+        let start = self.position;
+        let t = self.scan_token_type();
+        self.token(t, start)
     }
 
-    pub fn scan(&mut self) -> Token {
-        self.eat_whitespace();
-
-        let mut token = Token::new(
-            TokenType::ILLEGAL,
-            self.c.to_string(),
-            self.line,
-            self.position - self.last_new_line + 1,
-            Some("src/main.rs".to_string()),
-        );
-
-        match self.c {
-            '+' => {
-                if self.scan_compound_token(&mut token, "+", TokenType::INCR) {
-                    return token;
-                }
-                token.t = TokenType::PLUS;
-            }
-            '-' => {
-                if self.scan_compound_token(&mut token, "-", TokenType::DECR) {
-                    return token;
-                }
+    /// Scans the next token along with its trivia, see `SyntaxToken`.
+    pub fn next_syntax_token(&mut self) -> SyntaxToken {
+        let mut leading = Vec::new();
+        while let Some(trivia) = self.scan_trivia(true) {
+            leading.push(trivia);
+        }
 
-                if self.scan_compound_token(&mut token, ">", TokenType::ARROW) {
-                    return token;
-                }
+        let start = self.position;
+        let t = self.scan_token_type();
+        let token = self.token(t, start);
 
-                token.t = TokenType::MINUS;
-            }
-            '^' => {
-                token.t = TokenType::EXPONENT;
-            }
-            '#' => {
-                token.t = TokenType::HASH;
-            }
-            '&' => {
-                if self.scan_compound_token(&mut token, "&", TokenType::AND) {
-                    return token;
-                }
-                token.t = TokenType::AMPERSAND;
-            }
-            '=' => {
-                if self.scan_compound_token(&mut token, "=", TokenType::EQ) {
-                    return token;
-                }
-                token.t = TokenType::ASSIGN;
-            }
-            '*' => {
-                if self.scan_compound_token(&mut token, "*", TokenType::DASTERISK) {
-                    return token;
-                }
-                token.t = TokenType::ASTERISK;
-            }
-            '@' => {
-                token.t = TokenType::AT;
-            }
-            '\\' => {
-                token.t = TokenType::BACKSLASH;
-            }
-            '!' => {
-                token.t = TokenType::BANG;
-                if self.scan_compound_token(&mut token, "=", TokenType::NEQ) {
-                    return token;
-                }
-            }
-            '$' => {
-                token.t = TokenType::DOLLAR;
-            }
-            '.' => {
-                if self.scan_compound_token(&mut token, ".=", TokenType::IRANGE) {
-                    return token;
-                }
-                if self.scan_compound_token(&mut token, ".", TokenType::RANGE) {
-                    return token;
-                }
-                token.t = TokenType::DOT;
-            }
-            '"' => {
-                token.t = TokenType::STRING;
-                token.literal = self.scan_delimiter('"');
-            }
-            '/' => {
-                if self.scan_compound_token(&mut token, "/", TokenType::COMMENT) {
-                    self.eat_comment();
-                    return self.scan();
-                } else if self.scan_compound_token(&mut token, "*", TokenType::COMMENTBLOCK) {
-                    self.eat_comment_block();
-                    self.advance();
-                    return self.scan();
-                }
-                token.t = TokenType::FORWARDSLASH;
-            }
-            '%' => {
-                token.t = TokenType::PERCENT;
-            }
-            '|' => {
-                if self.scan_compound_token(&mut token, "|", TokenType::OR) {
-                    return token;
+        let mut trailing = Vec::new();
+        while let Some(trivia) = self.scan_trivia(false) {
+            trailing.push(trivia);
+        }
+
+        SyntaxToken::new(token, leading, trailing)
+    }
+
+    /// Consumes the whitespace or the comment starting at the current position, line breaks
+    /// are only trivia when `multiline` is set.
+    fn scan_trivia(&mut self, multiline: bool) -> Option<Trivia> {
+        let start = self.position;
+
+        let kind = match self.peek()? {
+            b' ' | b'\t' => {
+                self.eat_whitespace(multiline);
+                TriviaKind::Whitespace
+            }
+            b'\n' | b'\r' if multiline => {
+                self.eat_whitespace(multiline);
+                TriviaKind::Whitespace
+            }
+            b'/' if self.bytes()[start..].starts_with(b"//") => {
+                self.position += 2;
+                self.eat_comment();
+                self.comments.push(self.token(TokenType::COMMENT, start));
+                TriviaKind::Comment
+            }
+            b'/' if self.bytes()[start..].starts_with(b"/*") => {
+                self.position += 2;
+                self.eat_comment_block(start);
+                self.comments
+                    .push(self.token(TokenType::COMMENTBLOCK, start));
+                TriviaKind::CommentBlock
+            }
+            _ => return None,
+        };
+
+        Some(Trivia::new(
+            kind,
+            Span::new(Rc::clone(&self.file), start, self.position),
+        ))
+    }
+
+    /// Consumes the token starting at the current position.
+    fn scan_token_type(&mut self) -> TokenType {
+        let start = self.position;
+
+        let Some(c) = self.peek() else {
+            return TokenType::EOF;
+        };
+        self.position += 1;
+
+        match c {
+            b'+' => self.either(b'+', TokenType::INCR, TokenType::PLUS),
+            b'-' => {
+                if self.eat(b'-') {
+                    TokenType::DECR
+                } else {
+                    self.either(b'>', TokenType::ARROW, TokenType::MINUS)
                 }
-                token.t = TokenType::PIPE;
-            }
-            '?' => {
-                token.t = TokenType::QUESTION;
-            }
-            '~' => {
-                token.t = TokenType::TILDE;
-            }
-            '\'' => {
-                token.t = TokenType::SQUOTE;
-            }
-            '(' => {
-                token.t = TokenType::LPAREN;
-            }
-            ')' => {
-                token.t = TokenType::RPAREN;
             }
-            '{' => {
-                token.t = TokenType::LBRACE;
-            }
-            '}' => {
-                token.t = TokenType::RBRACE;
-            }
-            '[' => {
-                token.t = TokenType::LBRACK;
-            }
-            ']' => {
-                token.t = TokenType::RBRACK;
-            }
-            ',' => {
-                token.t = TokenType::COMMA;
-            }
-            ':' => {
-                if self.scan_compound_token(&mut token, ":", TokenType::SCOPE) {
-                    return token;
+            b'^' => TokenType::EXPONENT,
+            b'#' => TokenType::HASH,
+            b'&' => self.either(b'&', TokenType::AND, TokenType::AMPERSAND),
+            b'=' => self.either(b'=', TokenType::EQ, TokenType::ASSIGN),
+            b'*' => self.either(b'*', TokenType::DASTERISK, TokenType::ASTERISK),
+            b'@' => TokenType::AT,
+            b'\\' => TokenType::BACKSLASH,
+            b'!' => self.either(b'=', TokenType::NEQ, TokenType::BANG),
+            b'$' => TokenType::DOLLAR,
+            b'.' => {
+                if self.bytes()[self.position..].starts_with(b".=") {
+                    self.position += 2;
+                    TokenType::IRANGE
+                } else {
+                    self.either(b'.', TokenType::RANGE, TokenType::DOT)
                 }
-                token.t = TokenType::COLON;
-            }
-            ';' => {
-                token.t = TokenType::SEMICOLON;
             }
-            '>' => {
-                if self.scan_compound_token(&mut token, ">", TokenType::RSHIFT) {
-                    return token;
-                } else if self.scan_compound_token(&mut token, "=", TokenType::GTE) {
-                    return token;
+            b'"' => self.scan_string(start),
+            // comments were consumed as trivia
+            b'/' => TokenType::FORWARDSLASH,
+            b'%' => TokenType::PERCENT,
+            b'|' => self.either(b'|', TokenType::OR, TokenType::PIPE),
+            b'?' => TokenType::QUESTION,
+            b'~' => TokenType::TILDE,
+            b'\'' => TokenType::SQUOTE,
+            b'(' => TokenType::LPAREN,
+            b')' => TokenType::RPAREN,
+            b'{' => TokenType::LBRACE,
+            b'}' => TokenType::RBRACE,
+            b'[' => TokenType::LBRACK,
+            b']' => TokenType::RBRACK,
+            b',' => TokenType::COMMA,
+            b':' => self.either(b':', TokenType::SCOPE, TokenType::COLON),
+            b';' => TokenType::SEMICOLON,
+            b'>' => {
+                if self.eat(b'>') {
+                    TokenType::RSHIFT
+                } else {
+                    self.either(b'=', TokenType::GTE, TokenType::GT)
                 }
-
-                token.t = TokenType::GT;
             }
-            '<' => {
-                if self.scan_compound_token(&mut token, "<", TokenType::LSHIFT) {
-                    return token;
-                } else if self.scan_compound_token(&mut token, "=", TokenType::LTE) {
-                    return token;
+            b'<' => {
+                if self.eat(b'<') {
+                    TokenType::LSHIFT
+                } else {
+                    self.either(b'=', TokenType::LTE, TokenType::LT)
                 }
-                token.t = TokenType::LT;
             }
             // Alphanumeric and Identifier tokens
-            'a'..='z' | 'A'..='Z' | '_' => {
-                let identifier = self.scan_identifier();
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                self.eat_identifier();
 
-                if let Some(keyword) = KeywordTokenType::from_str(&identifier) {
-                    token.t = TokenType::KEYWORD(keyword);
-                } else {
-                    token.t = TokenType::IDENT;
+                match KeywordTokenType::from_str(&self.file.src[start..self.position]) {
+                    Some(keyword) => TokenType::KEYWORD(keyword),
+                    None => TokenType::IDENT,
                 }
-
-                token.literal = identifier;
-
-                return token;
             }
             // Number tokens
-            '0'..='9' => {
-                token.t = TokenType::INT;
-                token.literal = self.scan_number();
-
-                return token;
-            }
-            // End-of-file token
-            '\0' => {
-                token.t = TokenType::EOF;
-            }
+            b'0'..=b'9' => self.scan_number(start),
             // Default case for any other character
-            _ => token.t = TokenType::ILLEGAL,
-        }
+            _ => {
+                // step over the whole character, not only its first byte
+                self.position = start + self.file.src[start..].chars().next().unwrap().len_utf8();
 
-        self.advance();
+                let token = self.token(TokenType::ILLEGAL, start);
+                self.error(LexerErrorCode::InvalidCharacter { token });
 
-        token
+                TokenType::ILLEGAL
+            }
+        }
     }
 
-    pub fn advance(&mut self) {
-        if self.next_position >= self.input.len() {
-            self.c = '\0';
-        } else {
-            self.c = self.input[self.next_position];
+    fn scan_string(&mut self, start: usize) -> TokenType {
+        match self.bytes()[self.position..]
+            .iter()
+            .position(|&b| b == b'"')
+        {
+            Some(closing) => self.position += closing + 1,
+            None => {
+                self.position = self.bytes().len();
+
+                let opening_quote = Token::new(
+                    TokenType::STRING,
+                    Span::new(Rc::clone(&self.file), start, start + 1),
+                );
+                self.error(LexerErrorCode::UnterminatedString {
+                    token: opening_quote,
+                });
+            }
         }
 
-        self.position = self.next_position;
-        self.next_position += 1;
+        TokenType::STRING
     }
 
-    fn scan_identifier(&mut self) -> String {
-        let position = self.position;
+    fn scan_number(&mut self, start: usize) -> TokenType {
+        while matches!(self.peek(), Some(b'0'..=b'9' | b'_')) {
+            self.position += 1;
+        }
 
-        while self.c.is_alphanumeric() || self.c == '_' {
-            self.advance();
+        let digits_end = self.position;
+        if self.peek_char().is_some_and(char::is_alphanumeric) {
+            self.eat_identifier();
         }
 
-        self.input[position..self.position]
-            .into_iter()
-            .collect::<String>()
-    }
+        let fits_in_i64 = self.bytes()[start..digits_end]
+            .iter()
+            .filter(|&&b| b != b'_')
+            .try_fold(0i64, |value, &b| {
+                value.checked_mul(10)?.checked_add((b - b'0') as i64)
+            })
+            .is_some();
 
-    fn scan_number(&mut self) -> String {
-        let position = self.position;
+        if digits_end != self.position || !fits_in_i64 {
+            let token = self.token(TokenType::ILLEGAL, start);
+            self.error(LexerErrorCode::MalformedNumber { token });
 
-        while self.c.is_numeric() || self.c == '_' {
-            self.advance();
+            return TokenType::ILLEGAL;
         }
 
-        self.input[position..self.position]
-            .into_iter()
-            .collect::<String>()
-            .replace("_", "")
+        TokenType::INT
     }
 
-    fn scan_delimiter(&mut self, expected_closing: char) -> String {
-        let position = self.next_position;
+    fn token(&self, t: TokenType, start: usize) -> Token {
+        Token::new(t, Span::new(Rc::clone(&self.file), start, self.position))
+    }
 
-        loop {
-            self.advance();
+    fn error(&mut self, code: LexerErrorCode) {
+        self.errors.push(LexerError::new(code));
+    }
 
-            if self.c == '\0' || self.c == expected_closing {
-                break;
-            }
-        }
+    fn bytes(&self) -> &[u8] {
+        self.file.src.as_bytes()
+    }
 
-        self.input[position..self.position]
-            .into_iter()
-            .collect::<String>()
+    fn peek(&self) -> Option<u8> {
+        self.bytes().get(self.position).copied()
     }
 
-    fn scan_compound_token(
-        &mut self,
-        token: &mut Token,
-        expected_chars: &str,
-        expected_token: TokenType,
-    ) -> bool {
-        let position = self.position;
-        let next_position = self.next_position;
-        let mut literal: Vec<char> = Vec::new();
-        for c in expected_chars.chars() {
-            if self.peek() == c {
-                literal.push(self.c);
-                self.advance();
-            } else {
-                self.position = position;
-                self.next_position = next_position;
-                return false;
-            }
+    /// Like `peek` but decodes the whole character, only needed past the ASCII range.
+    fn peek_char(&self) -> Option<char> {
+        match self.peek()? {
+            b if b.is_ascii() => Some(b as char),
+            _ => self.file.src[self.position..].chars().next(),
         }
+    }
 
-        literal.push(self.c);
+    /// Consumes the next byte if it is `expected`.
+    fn eat(&mut self, expected: u8) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
 
-        token.t = expected_token;
-        token.literal = literal.iter().collect::<String>();
-        self.advance();
-        true
+    /// Scans a two bytes token if the next byte is `expected`, a single byte one otherwise.
+    fn either(&mut self, expected: u8, compound: TokenType, single: TokenType) -> TokenType {
+        if self.eat(expected) {
+            compound
+        } else {
+            single
+        }
     }
 
-    fn eat_whitespace(&mut self) {
-        loop {
-            match self.c {
-                ' ' | '\t' => {
-                    self.advance();
-                }
-                '\n' | '\r' => {
-                    self.line += 1;
-                    self.last_new_line = self.position;
-                    self.advance();
-                }
-                _ => break,
+    fn eat_identifier(&mut self) {
+        while let Some(c) = self.peek_char() {
+            if !(c.is_alphanumeric() || c == '_') {
+                break;
             }
+
+            self.position += c.len_utf8();
         }
     }
 
-    fn eat_comment(&mut self) {
-        loop {
-            match self.c {
-                '\n' | '\r' | '\0' => {
-                    break;
-                }
-                _ => self.advance(),
+    fn eat_whitespace(&mut self, multiline: bool) {
+        while let Some(b) = self.peek() {
+            if !(matches!(b, b' ' | b'\t') || multiline && matches!(b, b'\n' | b'\r')) {
+                break;
             }
+
+            self.position += 1;
         }
     }
 
-    fn eat_comment_block(&mut self) {
-        loop {
-            match self.c {
-                '\0' => break,
-                '*' => {
-                    if self.peek() == '/' {
-                        self.advance();
-                        break;
-                    }
-
-                    self.advance()
-                }
-                _ => self.advance(),
+    fn eat_comment(&mut self) {
+        self.position = self.bytes()[self.position..]
+            .iter()
+            .position(|&b| b == b'\n' || b == b'\r')
+            .map_or(self.bytes().len(), |end| self.position + end);
+    }
+
+    fn eat_comment_block(&mut self, start: usize) {
+        match self.file.src[self.position..].find("*/") {
+            Some(end) => self.position += end + 2,
+            None => {
+                self.position = self.bytes().len();
+
+                let token = Token::new(
+                    TokenType::COMMENTBLOCK,
+                    Span::new(Rc::clone(&self.file), start, start + 2),
+                );
+                self.error(LexerErrorCode::UnterminatedBlockComment { token });
             }
         }
     }
+}
 
-    fn peek(&self) -> char {
-        if self.next_position >= self.input.len() {
-            '\0'
-        } else {
-            self.input[self.next_position]
+impl Iterator for Lexer {
+    type Item = Token;
+
+    /// Yields every token of the source, `EOF` included, then `None`.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
         }
+
+        let token = self.next_token();
+        self.exhausted = token.t == TokenType::EOF;
+
+        Some(token)
     }
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::redundant_static_lifetimes,
+    clippy::useless_asref,
+    clippy::vec_init_then_push
+)]
 mod test;
@@ -2,9 +2,13 @@ mod tests {
 
     use crate::{
         lexer::Lexer,
-        token::token_type::{
-            KeywordTokenType::{self},
-            TokenType,
+        token::{
+            token_type::{
+                KeywordTokenType::{self},
+                TokenType,
+            },
+            trivia::{Trivia, TriviaKind},
+            Token,
         },
     };
 
@@ -33,17 +37,17 @@ mod tests {
         let mut lexer = Lexer::new(CODE.as_ref());
 
         for (t, literal) in expected_tokens {
-            let token = lexer.scan();
+            let token = lexer.next_token();
 
             assert_eq!(
                 token.t, t,
                 "Expected token {} got {} at {}:{}",
-                t, token.t, token.line, token.column
+                t, token.t, token.line(), token.column()
             );
             assert_eq!(
                 token.t, t,
                 "Expected literal {} got {} at {}:{}",
-                literal, token.literal, token.line, token.column
+                literal, token.literal(), token.line(), token.column()
             );
         }
     }
@@ -105,17 +109,17 @@ mod tests {
         let mut lexer = Lexer::new(CODE.as_ref());
 
         for (t, literal) in expected_tokens {
-            let token = lexer.scan();
+            let token = lexer.next_token();
 
             assert_eq!(
                 token.t, t,
                 "Expected token {} got {} at {}:{}",
-                t, token.t, token.line, token.column
+                t, token.t, token.line(), token.column()
             );
             assert_eq!(
                 token.t, t,
                 "Expected literal {} got {} at {}:{}",
-                literal, token.literal, token.line, token.column
+                literal, token.literal(), token.line(), token.column()
             );
         }
     }
@@ -440,20 +444,205 @@ mod tests {
         let mut lexer = Lexer::new(CODE.as_ref());
 
         for (t, literal) in expected_tokens {
-            let token = lexer.scan();
+            let token = lexer.next_token();
 
             // println!("{:?}", token);
 
             assert_eq!(
                 token.t, t,
                 "Expected token {} got {} at {}:{}",
-                t, token.t, token.line, token.column
+                t, token.t, token.line(), token.column()
             );
             assert_eq!(
-                token.literal, literal,
+                token.literal(), literal,
                 "Expected literal {} got {} at {}:{}",
-                literal, token.literal, token.line, token.column
+                literal, token.literal(), token.line(), token.column()
             );
         }
     }
+
+    #[test]
+    fn test_scan_with_lexical_errors() {
+        const CODE: &str = r#"
+            let price = 12abc;
+            let currency = `euro`;
+            let name = "unterminated
+        "#;
+
+        let expected_tokens: Vec<(TokenType, &str)> = vec![
+            (TokenType::KEYWORD(KeywordTokenType::LET), "let"),
+            (TokenType::IDENT, "price"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::ILLEGAL, "12abc"),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::KEYWORD(KeywordTokenType::LET), "let"),
+            (TokenType::IDENT, "currency"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::ILLEGAL, "`"),
+            (TokenType::IDENT, "euro"),
+            (TokenType::ILLEGAL, "`"),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::KEYWORD(KeywordTokenType::LET), "let"),
+            (TokenType::IDENT, "name"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::STRING, "unterminated\n        "),
+            (TokenType::EOF, ""),
+        ];
+
+        let mut lexer = Lexer::new(CODE);
+
+        for (t, literal) in expected_tokens {
+            let token = lexer.next_token();
+
+            assert_eq!(
+                token.t, t,
+                "Expected token {} got {} at {}:{}",
+                t, token.t, token.line(), token.column()
+            );
+            assert_eq!(
+                token.literal(), literal,
+                "Expected literal {} got {} at {}:{}",
+                literal, token.literal(), token.line(), token.column()
+            );
+        }
+
+        let error_codes: Vec<usize> = vec![103, 102, 102, 100];
+        assert_eq!(lexer.errors.len(), error_codes.len(), "{:#?}", lexer.errors);
+        for (index, code) in error_codes.iter().enumerate() {
+            assert_eq!(lexer.errors[index].code.id(), *code);
+        }
+    }
+
+    #[test]
+    fn test_scan_unterminated_block_comment() {
+        let mut lexer = Lexer::new("let x = 1; /* never closed");
+
+        loop {
+            if lexer.next_token().t == TokenType::EOF {
+                break;
+            }
+        }
+
+        assert_eq!(lexer.errors.len(), 1);
+        assert_eq!(lexer.errors[0].code.id(), 101);
+    }
+
+    #[test]
+    fn test_scan_comments() {
+        const CODE: &str = "let x = 1; // trailing\n/* block */ x";
+
+        let mut lexer = Lexer::new(CODE);
+        let tokens: Vec<TokenType> = lexer.by_ref().map(|token| token.t).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                TokenType::KEYWORD(KeywordTokenType::LET),
+                TokenType::IDENT,
+                TokenType::ASSIGN,
+                TokenType::INT,
+                TokenType::SEMICOLON,
+                TokenType::IDENT,
+                TokenType::EOF,
+            ]
+        );
+
+        // comments are kept aside for the formatter
+        let comments: Vec<(TokenType, &str)> = lexer
+            .comments
+            .iter()
+            .map(|comment| (comment.t.clone(), comment.span.source_text()))
+            .collect();
+        assert_eq!(
+            comments,
+            vec![
+                (TokenType::COMMENT, "// trailing"),
+                (TokenType::COMMENTBLOCK, "/* block */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_trivia() {
+        const CODE: &str = "let x = 1; // one\n\n/* two */ x\n";
+
+        let mut lexer = Lexer::new(CODE);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_syntax_token();
+            let done = token.token.t == TokenType::EOF;
+
+            let trivia = |trivia: &Vec<Trivia>| -> Vec<(TriviaKind, String)> {
+                trivia
+                    .iter()
+                    .map(|t| (t.kind, t.span.source_text().to_string()))
+                    .collect()
+            };
+            tokens.push((
+                trivia(&token.leading),
+                token.token.literal().to_string(),
+                trivia(&token.trailing),
+            ));
+
+            if done {
+                break;
+            }
+        }
+
+        let whitespace = |text: &str| (TriviaKind::Whitespace, text.to_string());
+        assert_eq!(tokens[3], (vec![], "1".to_string(), vec![]));
+        assert_eq!(
+            tokens[4],
+            (
+                vec![],
+                ";".to_string(),
+                vec![whitespace(" "), (TriviaKind::Comment, "// one".to_string())]
+            )
+        );
+        // the line break ending a line leads the next token
+        assert_eq!(
+            tokens[5],
+            (
+                vec![
+                    whitespace("\n\n"),
+                    (TriviaKind::CommentBlock, "/* two */".to_string()),
+                    whitespace(" ")
+                ],
+                "x".to_string(),
+                vec![]
+            )
+        );
+        assert_eq!(tokens[6], (vec![whitespace("\n")], "".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_lexer_iterator() {
+        const CODE: &str = r#"
+            let café = "crème" + 1_000; // trailing comment
+            fn(x) { x ** 2 }
+        "#;
+
+        let tokens: Vec<Token> = Lexer::new(CODE).collect();
+
+        let mut lexer = Lexer::new(CODE);
+        for token in &tokens {
+            let expected = lexer.next_token();
+            assert_eq!(token.t, expected.t);
+            assert_eq!(token.span, expected.span);
+        }
+
+        // the iterator stops right after the end of file
+        assert_eq!(tokens.len(), 17);
+        assert_eq!(tokens.last().unwrap().t, TokenType::EOF);
+
+        let literals: Vec<&str> = tokens.iter().take(6).map(Token::literal).collect();
+        assert_eq!(literals, vec!["let", "café", "=", "crème", "+", "1_000"]);
+
+        // literals are borrowed from the source instead of being copied
+        let source = tokens[0].span.file.src.as_str();
+        let literal = tokens[3].literal();
+        assert!(source.as_ptr() <= literal.as_ptr());
+        assert!(
+            literal.as_ptr() as usize + literal.len() <= source.as_ptr() as usize + source.len()
+        );
+    }
 }
@@ -0,0 +1,29 @@
+#![allow(unused)]
+
+pub mod allocator;
+pub mod ast;
+pub mod cli;
+pub mod config;
+pub mod cst;
+pub mod evaluator;
+pub mod fix;
+#[cfg(test)]
+mod fixtures;
+pub mod formatter;
+pub mod gc;
+pub mod json;
+pub mod lexer;
+pub mod lint;
+pub mod log;
+pub mod lsp;
+pub mod objects;
+pub mod optimizer;
+pub mod parser;
+pub mod program;
+pub mod repl;
+pub mod resolver;
+pub mod source;
+pub mod token;
+pub mod traits;
+pub mod type_checker;
+pub mod types;
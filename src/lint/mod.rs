@@ -0,0 +1,365 @@
+use std::fmt::Display;
+
+use crate::{
+    log::{
+        diagnostic::{Diagnostic, Severity},
+        warning::ParserWarning,
+    },
+    source::Span,
+};
+
+pub mod semantic;
+
+/// The name of the group every lint belongs to, e.g: `-D warnings` turns every warning into an
+/// error.
+pub const WARNINGS: &str = "warnings";
+
+/// Every lint, a lint is the name a warning is known by when setting its level. Names are
+/// stable just like codes.
+pub const LINTS: &[Lint] = &[
+    Lint::new(4000, "unnecessary_parens", Level::Warn),
+    Lint::new(4001, "empty_if", Level::Warn),
+    Lint::new(4002, "empty_if_consequence", Level::Warn),
+    Lint::new(4003, "empty_if_alternative", Level::Warn),
+    Lint::new(4004, "redundant_if_branch", Level::Warn),
+    Lint::new(4005, "predictable_if_branch", Level::Warn),
+    Lint::new(4006, "empty_function", Level::Warn),
+    Lint::new(4008, "unknown_lints", Level::Warn),
+    Lint::new(4009, "unused_variables", Level::Warn),
+    Lint::new(4010, "shadowing", Level::Warn),
+    Lint::new(4011, "unreachable_code", Level::Warn),
+    Lint::new(4012, "unnecessary_var", Level::Warn),
+    Lint::new(4013, "self_assignment", Level::Warn),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// The warning is not reported.
+    Allow,
+    Warn,
+    /// The warning is reported as an error, the file is not run.
+    Deny,
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+
+    /// The flag setting a lint to this level, e.g: `-D` for `-D warnings`.
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Level::Allow => "-A",
+            Level::Warn => "-W",
+            Level::Deny => "-D",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Allow => write!(f, "allow"),
+            Level::Warn => write!(f, "warn"),
+            Level::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lint {
+    /// The code of the warning reported by this lint.
+    pub code: usize,
+    pub name: &'static str,
+    pub default_level: Level,
+}
+
+impl Lint {
+    const fn new(code: usize, name: &'static str, default_level: Level) -> Self {
+        Self {
+            code,
+            name,
+            default_level,
+        }
+    }
+}
+
+pub fn find(name: &str) -> Option<&'static Lint> {
+    LINTS.iter().find(|lint| lint.name == name)
+}
+
+/// The lint reporting `warning`, unknown warnings have none and are always reported.
+pub fn of(warning: &ParserWarning) -> Option<&'static Lint> {
+    LINTS.iter().find(|lint| lint.code == warning.id())
+}
+
+/// Whether `name` can be given a level: a lint or the `warnings` group.
+pub fn is_known(name: &str) -> bool {
+    name == WARNINGS || find(name).is_some()
+}
+
+/// Where the level of a lint was set, told to the user when it differs from the default one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelSource {
+    Default,
+    /// The path of the config file.
+    Config(String),
+    CommandLine,
+    /// The span of the attribute.
+    Attribute(Span),
+}
+
+/// A level set on some lints by an attribute, for everything inside of `scope`.
+#[derive(Debug, Clone)]
+pub struct LintAttribute {
+    pub level: Level,
+    pub lints: Vec<String>,
+    pub span: Span,
+    pub scope: Span,
+}
+
+/// The levels set by the config file and the command line, attributes are taken into account
+/// when the warnings are reported.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintLevels {
+    /// In order of precedence, the last level set on a lint wins.
+    levels: Vec<(String, Level, LevelSource)>,
+}
+
+impl LintLevels {
+    pub fn new() -> Self {
+        Self { levels: Vec::new() }
+    }
+
+    pub fn set(&mut self, name: &str, level: Level, source: LevelSource) {
+        self.levels.push((name.to_string(), level, source));
+    }
+
+    /// The level of `lint` for a warning reported at `span`. The `warnings` group only applies
+    /// to lints that would otherwise warn, allowed lints stay allowed under `-D warnings`.
+    pub fn level(
+        &self,
+        lint: &Lint,
+        span: Option<&Span>,
+        attributes: &[LintAttribute],
+    ) -> (Level, LevelSource) {
+        let mut levels: Vec<(&str, Level, LevelSource)> = self
+            .levels
+            .iter()
+            .map(|(name, level, source)| (name.as_str(), *level, source.clone()))
+            .collect();
+
+        if let Some(span) = span {
+            // the innermost attribute comes last, nested scopes are always smaller
+            let mut applying: Vec<&LintAttribute> = attributes
+                .iter()
+                .filter(|attribute| {
+                    attribute.scope.file.id == span.file.id
+                        && attribute.scope.start <= span.start
+                        && span.end <= attribute.scope.end
+                })
+                .collect();
+            applying.sort_by_key(|attribute| std::cmp::Reverse(attribute.scope.len()));
+
+            for attribute in applying {
+                for name in &attribute.lints {
+                    levels.push((
+                        name,
+                        attribute.level,
+                        LevelSource::Attribute(attribute.span.clone()),
+                    ));
+                }
+            }
+        }
+
+        let last = |name: &str| {
+            levels
+                .iter()
+                .rev()
+                .find(|(other, _, _)| *other == name)
+                .map(|(_, level, source)| (*level, source.clone()))
+        };
+
+        let (level, source) = last(lint.name).unwrap_or((lint.default_level, LevelSource::Default));
+        if level == Level::Warn {
+            if let Some(group) = last(WARNINGS) {
+                return group;
+            }
+        }
+
+        (level, source)
+    }
+
+    /// The diagnostics of the warnings that are not allowed, denied ones are errors.
+    pub fn report(
+        &self,
+        warnings: &[ParserWarning],
+        attributes: &[LintAttribute],
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for warning in warnings {
+            let mut diagnostic = warning.to_diagnostic();
+            let Some(lint) = of(warning) else {
+                diagnostics.push(diagnostic);
+                continue;
+            };
+
+            let span = warning.token().map(|token| &token.span);
+            let (level, source) = self.level(lint, span, attributes);
+
+            match level {
+                Level::Allow => continue,
+                Level::Warn => {}
+                Level::Deny => diagnostic.severity = Severity::Error,
+            }
+
+            let note = match &source {
+                LevelSource::Default => None,
+                LevelSource::Config(path) => Some(format!("the lint level is set in `{}`", path)),
+                LevelSource::CommandLine => Some(format!(
+                    "`{} {}` was passed on the command line",
+                    level.flag(),
+                    self.flag_name(lint, level)
+                )),
+                LevelSource::Attribute(span) => Some(format!(
+                    "the lint level is set by `{}` at {}",
+                    span.source_text(),
+                    span.get_location()
+                )),
+            };
+
+            diagnostics.push(match note {
+                Some(note) => diagnostic.with_note(note),
+                None => diagnostic,
+            });
+        }
+
+        diagnostics
+    }
+
+    /// The name the command line flag setting `lint` to `level` was given, the lint itself or
+    /// the `warnings` group.
+    fn flag_name(&self, lint: &Lint, level: Level) -> &'static str {
+        let set_directly = self
+            .levels
+            .iter()
+            .rev()
+            .find(|(name, _, _)| name == lint.name)
+            .is_some_and(|(_, other, _)| *other == level);
+
+        if set_directly {
+            lint.name
+        } else {
+            WARNINGS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, log::diagnostic::Severity, parser::Parser};
+
+    use super::{find, Level, LevelSource, LintLevels};
+
+    /// The severity and code of the diagnostics reported for `code`.
+    fn report(code: &str, levels: &LintLevels) -> Vec<(Severity, usize)> {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+        parser.warnings.extend(super::semantic::check(&program));
+
+        levels
+            .report(&parser.warnings, &parser.lint_attributes)
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.code.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_attributes() {
+        const CODE: &str = r#"
+            @allow(unnecessary_parens)
+            let f = fn() {
+                if (true) { 1 }
+
+                @warn(unnecessary_parens)
+                if (true) { 1 }
+            };
+
+            @deny(empty_if_alternative)
+            if (true) { 1 } else {}
+
+            @allow(warnings) @deny(unnecessary_parens)
+            if (true) {} else { 1 }
+        "#;
+
+        assert_eq!(
+            report(CODE, &LintLevels::new()),
+            vec![
+                (Severity::Warning, 4000),
+                (Severity::Warning, 4000),
+                (Severity::Error, 4003),
+                (Severity::Error, 4000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_levels_precedence() {
+        const CODE: &str = r#"
+            if (true) { 1 } else {}
+
+            @warn(unnecessary_parens)
+            if (true) { 1 }
+        "#;
+
+        let mut levels = LintLevels::new();
+        levels.set(
+            "empty_if_alternative",
+            Level::Allow,
+            LevelSource::Config("eheh.toml".into()),
+        );
+        levels.set(
+            "unnecessary_parens",
+            Level::Deny,
+            LevelSource::Config("eheh.toml".into()),
+        );
+        levels.set("unnecessary_parens", Level::Allow, LevelSource::CommandLine);
+        assert_eq!(report(CODE, &levels), vec![(Severity::Warning, 4000)]);
+
+        // allowed lints stay allowed under `-D warnings`
+        let mut levels = LintLevels::new();
+        levels.set(
+            "empty_if_alternative",
+            Level::Allow,
+            LevelSource::CommandLine,
+        );
+        levels.set("warnings", Level::Deny, LevelSource::CommandLine);
+        assert_eq!(
+            report(CODE, &levels),
+            vec![(Severity::Error, 4000), (Severity::Error, 4000)]
+        );
+    }
+
+    #[test]
+    fn test_unknown_lints() {
+        const CODE: &str = r#"
+            @allow(unnecesary_parens)
+            if (true) { 1 }
+        "#;
+
+        assert_eq!(
+            report(CODE, &LintLevels::new()),
+            vec![(Severity::Warning, 4008), (Severity::Warning, 4000)]
+        );
+        assert!(find("unnecessary_parens").is_some());
+        assert!(find("warnings").is_none());
+    }
+}
@@ -0,0 +1,611 @@
+use std::collections::HashSet;
+
+use crate::{
+    ast::{
+        expressions::{
+            call_expression::CallExpression, function_literal::FunctionLiteral,
+            identifier::Identifier, if_expression::IfExpression, infix_expression::InfixExpression,
+            named_argument::NamedArgument, prefix_expression::PrefixExpression,
+        },
+        kind::{ExpressionKind, StatementKind},
+        statements::{block_statement::BlockStatement, declare_statement::DeclareStatement},
+        visit::{self, Visitor},
+    },
+    log::{diagnostic::TextEdit, warning::ParserWarning},
+    parser::negate_condition,
+    program::Program,
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+    traits::{expression::Expression, node::Node},
+    types::ASTStatement,
+};
+
+/// Lints needing more than a single node to be decided: unused and shadowed bindings, `var`
+/// bindings never reassigned, unreachable code, self-assignments and redundant if branches.
+/// The program must parse without errors.
+pub fn check(program: &Program) -> Vec<ParserWarning> {
+    let mut checker = Checker {
+        scopes: vec![Scope::new(true)],
+        warnings: Vec::new(),
+        operand: false,
+        named_arguments: HashSet::new(),
+    };
+
+    checker.visit_program(program);
+    checker.close_scope();
+
+    for warning in &mut checker.warnings {
+        if let ParserWarning::UnusedVariable {
+            token,
+            is_parameter: true,
+            passed_by_name,
+            ..
+        } = warning
+        {
+            *passed_by_name = checker.named_arguments.contains(token.literal());
+        }
+    }
+
+    // function bodies are checked after their scope, report in source order
+    checker
+        .warnings
+        .sort_by_key(|warning| warning.token().map(|token| token.span.start));
+    checker.warnings
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BindingKind {
+    Let,
+    Var,
+    Const,
+    Parameter,
+}
+
+#[derive(Debug)]
+struct Binding {
+    name: Token,
+    kind: BindingKind,
+    /// The `let`, `var` or `const` keyword, the name of parameters.
+    keyword: Token,
+    used: bool,
+    reassigned: bool,
+}
+
+/// A function, or the program for the outermost one. Blocks do not introduce scopes, a binding
+/// lives as long as the function it is declared in just like at runtime.
+#[derive(Debug)]
+struct Scope<'a> {
+    bindings: Vec<Binding>,
+    /// Functions defined in this scope, their bodies are checked once every binding of the
+    /// scope is known since they may use bindings declared after them.
+    functions: Vec<&'a FunctionLiteral>,
+    /// Bindings of the program outlive it, they are used by the REPL and the next files.
+    top_level: bool,
+}
+
+impl<'a> Scope<'a> {
+    fn new(top_level: bool) -> Self {
+        Self {
+            bindings: Vec::new(),
+            functions: Vec::new(),
+            top_level,
+        }
+    }
+}
+
+struct Checker<'a> {
+    scopes: Vec<Scope<'a>>,
+    warnings: Vec<ParserWarning>,
+    /// Whether the next expression visited is the operand of another one, the other expressions
+    /// never need to be wrapped in parentheses.
+    operand: bool,
+    /// The names of the named arguments of every call, known once the program is checked.
+    named_arguments: HashSet<&'a str>,
+}
+
+impl<'a> Visitor<'a> for Checker<'a> {
+    fn visit_statements(&mut self, statements: &'a [ASTStatement]) {
+        let returned = statements
+            .iter()
+            .enumerate()
+            .find_map(|(index, statement)| match statement.kind() {
+                StatementKind::Return(return_statement) => Some((index, return_statement)),
+                _ => None,
+            });
+
+        if let Some((index, return_statement)) =
+            returned.filter(|(index, _)| index + 1 < statements.len())
+        {
+            let first = &statements[index + 1];
+            let last = &statements[statements.len() - 1];
+
+            self.warnings.push(ParserWarning::UnreachableCode {
+                token: return_statement.token.clone(),
+                unreachable: first.span().to(&last.span()),
+            });
+        }
+
+        visit::walk_statements(self, statements);
+    }
+
+    fn visit_declare_statement(&mut self, declare: &'a DeclareStatement) {
+        let kind = match declare.token.t {
+            TokenType::KEYWORD(KeywordTokenType::VAR) => BindingKind::Var,
+            TokenType::KEYWORD(KeywordTokenType::CONST) => BindingKind::Const,
+            _ => BindingKind::Let,
+        };
+
+        match &declare.value {
+            // declared first so that the function can call itself
+            Some(value) if matches!(value.kind(), ExpressionKind::Function(_)) => {
+                self.declare(&declare.name.token, kind, &declare.token);
+                self.visit_expression(value.as_ref());
+            }
+            Some(value) => {
+                self.visit_expression(value.as_ref());
+                self.declare(&declare.name.token, kind, &declare.token);
+            }
+            None => self.declare(&declare.name.token, kind, &declare.token),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &'a dyn Expression) {
+        let standalone = !std::mem::take(&mut self.operand);
+
+        if let ExpressionKind::If(if_expression) = expression.kind() {
+            self.redundant_if_branch(if_expression, standalone);
+        }
+
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_identifier(&mut self, identifier: &'a Identifier) {
+        if let Some(binding) = self.lookup(&identifier.token) {
+            binding.used = true;
+        }
+    }
+
+    fn visit_named_argument(&mut self, argument: &'a NamedArgument) {
+        self.named_arguments.insert(&argument.name.value);
+        visit::walk_named_argument(self, argument);
+    }
+
+    fn visit_function_literal(&mut self, function: &'a FunctionLiteral) {
+        self.scope().functions.push(function);
+    }
+
+    fn visit_call_expression(&mut self, call: &'a CallExpression) {
+        self.operand(call.function.as_ref());
+        for arg in &call.args {
+            self.visit_expression(arg.as_ref());
+        }
+        for arg in &call.named_args {
+            self.visit_named_argument(arg);
+        }
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &'a IfExpression) {
+        self.operand(if_expression.condition.as_ref());
+        self.visit_block_statement(&if_expression.consequence);
+        if let Some(alternative) = &if_expression.alternative {
+            self.visit_block_statement(alternative);
+        }
+    }
+
+    fn visit_infix_expression(&mut self, infix: &'a InfixExpression) {
+        if infix.operator == "=" {
+            self.assignment(infix);
+        } else {
+            self.operand(infix.lhs.as_ref());
+        }
+        self.operand(infix.rhs.as_ref());
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &'a PrefixExpression) {
+        self.operand(prefix.rhs.as_ref());
+    }
+}
+
+impl<'a> Checker<'a> {
+    fn scope(&mut self) -> &mut Scope<'a> {
+        self.scopes.last_mut().unwrap()
+    }
+
+    fn operand(&mut self, expression: &'a dyn Expression) {
+        self.operand = true;
+        self.visit_expression(expression);
+    }
+
+    /// Assigning a binding does not use it.
+    fn assignment(&mut self, assignment: &'a InfixExpression) {
+        let ExpressionKind::Identifier(target) = assignment.lhs.kind() else {
+            self.operand(assignment.lhs.as_ref());
+            return;
+        };
+
+        if let Some(binding) = self.lookup(&target.token) {
+            binding.reassigned = true;
+        }
+
+        let is_self_assignment = matches!(
+            assignment.rhs.kind(),
+            ExpressionKind::Identifier(value) if value.value == target.value
+        );
+        if is_self_assignment {
+            self.warnings.push(ParserWarning::SelfAssignment {
+                token: target.token.clone(),
+                assignment: assignment.span(),
+            });
+        }
+    }
+
+    /// `if c { true } else { false }` is `c`, and `!c` the other way around.
+    fn redundant_if_branch(&mut self, if_expression: &IfExpression, standalone: bool) {
+        let consequence = boolean_value(&if_expression.consequence);
+        let alternative = if_expression.alternative.as_ref().and_then(boolean_value);
+        let (Some(consequence), Some(alternative)) = (consequence, alternative) else {
+            return;
+        };
+        if consequence == alternative {
+            return;
+        }
+
+        let condition = &if_expression.condition;
+        let span = condition.span();
+        let text = span.source_text();
+
+        let mut replacement = if consequence {
+            text.to_string()
+        } else {
+            match negate_condition(condition) {
+                Some(edit) => format!(
+                    "{}{}{}",
+                    &span.file.src[span.start..edit.span.start],
+                    edit.replacement,
+                    &span.file.src[edit.span.end..span.end]
+                ),
+                None => format!("!({})", text),
+            }
+        };
+
+        if !standalone && matches!(condition.kind(), ExpressionKind::Infix(_)) {
+            replacement = format!("({})", replacement);
+        }
+
+        self.warnings.push(ParserWarning::RedundantIfBranch {
+            token: if_expression.token.clone(),
+            branch: consequence,
+            replacement: TextEdit::new(if_expression.span(), replacement),
+        });
+    }
+
+    fn declare(&mut self, name: &Token, kind: BindingKind, keyword: &Token) {
+        let literal = name.literal();
+
+        if !literal.starts_with('_') {
+            // bindings declared after the function are not shadowed, they do not exist yet
+            let outer = self.scopes[..self.scopes.len() - 1]
+                .iter()
+                .rev()
+                .find_map(|scope| {
+                    scope.bindings.iter().rev().find(|binding| {
+                        binding.name.literal() == literal
+                            && binding.name.span.start < name.span.start
+                    })
+                });
+
+            if let Some(previous) = outer {
+                self.warnings.push(ParserWarning::ShadowedBinding {
+                    token: name.clone(),
+                    previous: previous.name.span.clone(),
+                });
+            }
+        }
+
+        self.scope().bindings.push(Binding {
+            name: name.clone(),
+            kind,
+            keyword: keyword.clone(),
+            used: false,
+            reassigned: false,
+        });
+    }
+
+    /// The binding `name` refers to: the last one declared before it, a function body checked
+    /// after its scope sees the bindings declared after it too but only uses them when none is
+    /// declared before, e.g: `let f = fn() { g() }; let g = fn() {};`.
+    fn lookup(&mut self, name: &Token) -> Option<&mut Binding> {
+        let literal = name.literal();
+
+        self.scopes.iter_mut().rev().find_map(|scope| {
+            let declared = scope
+                .bindings
+                .iter()
+                .enumerate()
+                .filter(|(_, binding)| binding.name.literal() == literal);
+            let index = declared
+                .clone()
+                .rfind(|(_, binding)| binding.name.span.start < name.span.start)
+                .or_else(|| declared.clone().next())
+                .map(|(index, _)| index)?;
+
+            Some(&mut scope.bindings[index])
+        })
+    }
+
+    fn function(&mut self, function: &'a FunctionLiteral) {
+        self.scopes.push(Scope::new(false));
+
+        for parameter in &function.parameters {
+            if let Some(default) = &parameter.default {
+                self.visit_expression(default.as_ref());
+            }
+
+            let name = &parameter.identifier.token;
+            self.declare(name, BindingKind::Parameter, name);
+        }
+
+        self.visit_block_statement(&function.body);
+        self.close_scope();
+    }
+
+    /// Checks the functions defined in the scope then reports its bindings, the bindings of the
+    /// program are never reported.
+    fn close_scope(&mut self) {
+        for function in std::mem::take(&mut self.scope().functions) {
+            self.function(function);
+        }
+
+        let scope = self.scopes.pop().unwrap();
+        if scope.top_level {
+            return;
+        }
+
+        for binding in &scope.bindings {
+            if binding.name.literal().starts_with('_') {
+                continue;
+            }
+
+            if !binding.used {
+                let literal = binding.name.literal();
+                let redeclared = self
+                    .scopes
+                    .iter()
+                    .chain([&scope])
+                    .flat_map(|scope| &scope.bindings)
+                    .any(|other| other.name.literal() == literal && !std::ptr::eq(other, binding));
+
+                self.warnings.push(ParserWarning::UnusedVariable {
+                    token: binding.name.clone(),
+                    is_parameter: binding.kind == BindingKind::Parameter,
+                    passed_by_name: false,
+                    redeclared,
+                });
+            }
+
+            if binding.kind == BindingKind::Var && !binding.reassigned {
+                self.warnings.push(ParserWarning::UnnecessaryVar {
+                    token: binding.keyword.clone(),
+                    name: binding.name.literal().to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// The value of a block made of a single boolean, e.g: `{ true }`.
+fn boolean_value(block: &BlockStatement) -> Option<bool> {
+    let [statement] = block.statements.as_slice() else {
+        return None;
+    };
+
+    let StatementKind::Expression(statement) = statement.kind() else {
+        return None;
+    };
+
+    match statement.expression.kind() {
+        ExpressionKind::Boolean(boolean) => Some(boolean.value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fix::{apply_suggestions, fix_source},
+        lexer::Lexer,
+        lint::LintLevels,
+        parser::Parser,
+    };
+
+    use super::check;
+
+    /// The code of the warnings raised on `code` and the text of the token they point at.
+    fn warnings(code: &str) -> Vec<(usize, String)> {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+
+        check(&program)
+            .iter()
+            .map(|warning| (warning.id(), warning.token().unwrap().literal().to_string()))
+            .collect()
+    }
+
+    /// `code` once the suggestions of the warnings raised on it are applied.
+    fn fixed(code: &str) -> String {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        let diagnostics: Vec<_> = check(&program)
+            .iter()
+            .map(|warning| warning.to_diagnostic())
+            .collect();
+        let suggestions: Vec<_> = diagnostics
+            .iter()
+            .flat_map(|diagnostic| &diagnostic.suggestions)
+            .collect();
+
+        apply_suggestions(code, &suggestions).0
+    }
+
+    #[test]
+    fn test_unused_variables() {
+        const CODE: &str = r#"
+            let unused_at_top_level = 1;
+            let f = fn(a, b, _c) {
+                let d = 1;
+                let _e = 2;
+                let g = fn() { h() };
+                let h = fn() { a };
+                let fib = fn(n) { fib(n) };
+                g(fib)
+            };
+        "#;
+
+        assert_eq!(
+            warnings(CODE),
+            vec![(4009, "b".to_string()), (4009, "d".to_string())]
+        );
+        assert_eq!(
+            fixed("let f = fn(a) { 1 };"),
+            "let f = fn(_a) { 1 };".to_string()
+        );
+    }
+
+    #[test]
+    fn test_unused_parameter_passed_by_name() {
+        const CODE: &str = "let f = fn(x, y) { y }; f(x = 1, y = 2);";
+
+        assert_eq!(warnings(CODE), vec![(4009, "x".to_string())]);
+        // `f(_x = 1, ...)` would not bind the parameter anymore
+        let fixed = fix_source("main.eheh", CODE, &LintLevels::new()).unwrap();
+        assert_eq!(fixed.src, CODE);
+    }
+
+    #[test]
+    fn test_shadowing() {
+        const CODE: &str = r#"
+            let a = 1;
+            let f = fn(a) {
+                let b = a;
+                let g = fn() {
+                    let b = 2;
+                    let _a = b;
+                };
+                let later = 3;
+                g(b, later)
+            };
+            let later = 4;
+        "#;
+
+        assert_eq!(
+            warnings(CODE),
+            vec![(4010, "a".to_string()), (4010, "b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_redeclared_bindings() {
+        const CODE: &str = r#"
+            let x = 1;
+            let f = fn() {
+                let x = 2;
+                let y = 1;
+                let g = fn() { x + y };
+                let a = g();
+                let y = 2;
+                let z = 3;
+                let h = fn() { z };
+                let z = 4;
+                a + y + h()
+            };
+        "#;
+
+        // `h()` reads the second `z` at runtime, the lint cannot tell which one it uses
+        assert_eq!(
+            warnings(CODE),
+            vec![(4010, "x".to_string()), (4009, "z".to_string())]
+        );
+        let fixed = fix_source("main.eheh", CODE, &LintLevels::new()).unwrap();
+        assert_eq!(fixed.src, CODE);
+    }
+
+    #[test]
+    fn test_unreachable_code() {
+        const CODE: &str = r#"
+            let f = fn(a) {
+                if a {
+                    return 1;
+                    2;
+                    3
+                }
+                return a;
+            };
+        "#;
+
+        assert_eq!(warnings(CODE), vec![(4011, "return".to_string())]);
+    }
+
+    #[test]
+    fn test_unnecessary_var() {
+        const CODE: &str = r#"
+            var top_level = 1;
+            let f = fn() {
+                var a = 1;
+                var b = 2;
+                b = a;
+                b
+            };
+        "#;
+
+        assert_eq!(warnings(CODE), vec![(4012, "var".to_string())]);
+        assert_eq!(
+            fixed("let f = fn() { var a = 1; a };"),
+            "let f = fn() { let a = 1; a };".to_string()
+        );
+    }
+
+    #[test]
+    fn test_self_assignment() {
+        const CODE: &str = r#"
+            var a = 1;
+            a = a;
+            a = 2;
+        "#;
+
+        assert_eq!(warnings(CODE), vec![(4013, "a".to_string())]);
+    }
+
+    #[test]
+    fn test_redundant_if_branch() {
+        const CODE: &str = r#"
+            let a = 1;
+            let b = if a > 0 { true } else { false };
+            let c = if a { true } else { true };
+        "#;
+
+        assert_eq!(warnings(CODE), vec![(4004, "if".to_string())]);
+
+        assert_eq!(
+            fixed("let b = if a > 0 { true } else { false };"),
+            "let b = a > 0;".to_string()
+        );
+        assert_eq!(
+            fixed("let b = 1 + if a > 0 { false } else { true };"),
+            "let b = 1 + (a <= 0);".to_string()
+        );
+        assert_eq!(
+            fixed("let b = if f(a) { false } else { true };"),
+            "let b = !f(a);".to_string()
+        );
+        assert_eq!(
+            fixed("let b = if a + 1 { false } else { true };"),
+            "let b = !(a + 1);".to_string()
+        );
+    }
+}
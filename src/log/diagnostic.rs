@@ -0,0 +1,852 @@
+use std::{collections::BTreeSet, fmt::Display, rc::Rc};
+
+use colored::{Color, Colorize};
+
+use crate::{
+    json::Json,
+    source::{SourceFile, Span},
+};
+
+/// Source lines are only elided when a multi-line label spans more than that many lines.
+const MAX_MULTILINE_LINES: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn color(&self) -> Color {
+        match self {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A span of the source annotated in a diagnostic, the primary label points at the cause of
+/// the diagnostic and is underlined with `^`, secondary ones give context and use `-`.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: Option<String>,
+    pub primary: bool,
+}
+
+impl Label {
+    pub fn primary(span: Span) -> Self {
+        Self {
+            span,
+            message: None,
+            primary: true,
+        }
+    }
+
+    pub fn secondary(span: Span) -> Self {
+        Self {
+            span,
+            message: None,
+            primary: false,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// 1-based first and last lines covered by the label.
+    fn lines(&self) -> (usize, usize) {
+        let file = &self.span.file;
+        let last = if self.span.end > self.span.start {
+            self.span.end - 1
+        } else {
+            self.span.start
+        };
+
+        (file.line_number(self.span.start), file.line_number(last))
+    }
+
+    /// 0-based display columns of the first character and one past the last character.
+    fn columns(&self) -> (usize, usize) {
+        let start = display_column(&self.span.file, self.span.start);
+        let end = if self.span.end > self.span.start {
+            let src = &self.span.file.src;
+            let last = src[..floor_char_boundary(src, self.span.end)]
+                .char_indices()
+                .next_back()
+                .map_or(self.span.start, |(offset, _)| offset);
+            display_column(&self.span.file, last) + 1
+        } else {
+            start + 1
+        };
+
+        if self.is_multiline() {
+            (start, end)
+        } else {
+            (start, end.max(start + 1))
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object()
+            .with("primary", self.primary)
+            .with("message", self.message.clone())
+            .with("file", self.span.file.name.as_str())
+            .with("span", span_to_json(&self.span))
+    }
+
+    fn is_multiline(&self) -> bool {
+        let (start, end) = self.lines();
+        start != end
+    }
+}
+
+/// How confident a `Suggestion` is, only machine applicable suggestions are applied by
+/// `eheh fix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The edits are known to fix the problem without changing what the code does.
+    MachineApplicable,
+    /// The edits are likely what the user meant, but they should review them.
+    MaybeIncorrect,
+}
+
+impl Display for Applicability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Applicability::MachineApplicable => write!(f, "machine-applicable"),
+            Applicability::MaybeIncorrect => write!(f, "maybe-incorrect"),
+        }
+    }
+}
+
+/// Replaces the source covered by `span` with `replacement`, an empty span inserts text and an
+/// empty replacement deletes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    pub fn delete(span: Span) -> Self {
+        Self::new(span, "")
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object()
+            .with("file", self.span.file.name.as_str())
+            .with("span", span_to_json(&self.span))
+            .with("replacement", self.replacement.as_str())
+    }
+}
+
+/// A fix for a diagnostic, its edits never overlap and must be applied all at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    pub edits: Vec<TextEdit>,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(
+        message: impl Into<String>,
+        edits: Vec<TextEdit>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            edits,
+            applicability,
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object()
+            .with("message", self.message.as_str())
+            .with("applicability", self.applicability.to_string())
+            .with(
+                "edits",
+                self.edits
+                    .iter()
+                    .map(TextEdit::to_json)
+                    .collect::<Vec<Json>>(),
+            )
+    }
+}
+
+/// Everything the user gets told about a problem in their code: the errors and warnings of
+/// every stage of the interpreter are rendered through this type.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<usize>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub helps: Vec<String>,
+    /// Rendered as helps for humans, tools can apply their edits.
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            helps: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: usize) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.helps.push(help.into());
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// The code as shown to the user, e.g: `E01000`.
+    pub fn code_name(&self) -> Option<String> {
+        self.code.map(|code| format!("E{:0>5}", code))
+    }
+
+    pub fn to_json(&self) -> Json {
+        let primary_span = self.primary_span();
+
+        Json::object()
+            .with("code", self.code_name())
+            .with("severity", self.severity.to_string())
+            .with("message", self.message.as_str())
+            .with("file", primary_span.map(|span| span.file.name.as_str()))
+            .with("span", primary_span.map(span_to_json))
+            .with(
+                "labels",
+                self.labels
+                    .iter()
+                    .map(Label::to_json)
+                    .collect::<Vec<Json>>(),
+            )
+            .with("notes", self.notes.clone())
+            .with("helps", self.helps.clone())
+            .with(
+                "suggestions",
+                self.suggestions
+                    .iter()
+                    .map(Suggestion::to_json)
+                    .collect::<Vec<Json>>(),
+            )
+    }
+
+    pub fn primary_span(&self) -> Option<&Span> {
+        self.labels
+            .iter()
+            .find(|label| label.primary)
+            .or(self.labels.first())
+            .map(|label| &label.span)
+    }
+
+    /// The labels grouped by source file, the file of the primary label comes first.
+    fn labels_by_file(&self) -> Vec<(Rc<SourceFile>, Vec<&Label>)> {
+        let mut groups: Vec<(Rc<SourceFile>, Vec<&Label>)> = Vec::new();
+
+        let mut labels: Vec<&Label> = self.labels.iter().collect();
+        labels.sort_by_key(|label| !label.primary);
+
+        for label in labels {
+            match groups
+                .iter_mut()
+                .find(|(file, _)| Rc::ptr_eq(file, &label.span.file))
+            {
+                Some((_, group)) => group.push(label),
+                None => groups.push((Rc::clone(&label.span.file), vec![label])),
+            }
+        }
+
+        groups
+    }
+
+    fn render_snippet(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        file: &SourceFile,
+        labels: &[&Label],
+        gutter: usize,
+    ) -> std::fmt::Result {
+        let multiline: Vec<&Label> = labels
+            .iter()
+            .copied()
+            .filter(|label| label.is_multiline())
+            .collect();
+        let lanes_width = if multiline.is_empty() {
+            0
+        } else {
+            multiline.len() + 1
+        };
+
+        let mut previous: Option<usize> = None;
+        for line in snippet_lines(labels) {
+            if let Some(previous) = previous {
+                if line > previous + 1 {
+                    writeln!(f, "{}", "...".blue())?;
+                }
+            }
+            previous = Some(line);
+
+            let text = expand_tabs(file.line(line).unwrap_or(""));
+
+            let mut lanes = Row::new();
+            for (lane, label) in multiline.iter().enumerate() {
+                let (start, end) = label.lines();
+                if start < line && line <= end {
+                    lanes.put(lane, '|', self.paint(label));
+                }
+            }
+            lanes.pad(lanes_width);
+
+            let number = format!("{:>gutter$}", line).blue();
+            if text.is_empty() && lanes_width == 0 {
+                writeln!(f, "{} {}", number, "|".blue())?;
+            } else {
+                writeln!(f, "{} {} {}{}", number, "|".blue(), lanes, text)?;
+            }
+
+            for row in self.marker_rows(line, labels, &multiline, lanes_width) {
+                writeln!(f, "{} {} {}", " ".repeat(gutter), "|".blue(), row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The rows drawn under the source `line`: underlines, multi-line label boundaries and
+    /// label messages.
+    fn marker_rows(
+        &self,
+        line: usize,
+        labels: &[&Label],
+        multiline: &[&Label],
+        lanes_width: usize,
+    ) -> Vec<Row> {
+        let mut rows = Vec::new();
+
+        let lanes = |except: Option<usize>| {
+            let mut row = Row::new();
+            for (lane, label) in multiline.iter().enumerate() {
+                let (start, end) = label.lines();
+                if Some(lane) != except && start <= line && line < end {
+                    row.put(lane, '|', self.paint(label));
+                }
+            }
+            row
+        };
+
+        // boundaries of the multi-line labels starting or ending on this line
+        for (lane, label) in multiline.iter().enumerate() {
+            let (start_line, end_line) = label.lines();
+            let (start, end) = label.columns();
+            let paint = self.paint(label);
+
+            if start_line == line {
+                let mut row = lanes(Some(lane));
+                row.fill(lane + 1, lanes_width + start, '_', paint);
+                row.put(lanes_width + start, self.mark(label), paint);
+                rows.push(row);
+            } else if end_line == line {
+                let mut row = lanes(Some(lane));
+                row.put(lane, '|', paint);
+                row.fill(lane + 1, lanes_width + end - 1, '_', paint);
+                row.put(lanes_width + end - 1, self.mark(label), paint);
+                if let Some(message) = &label.message {
+                    row.text(lanes_width + end + 1, message, paint);
+                }
+                rows.push(row);
+            }
+        }
+
+        let mut single: Vec<&Label> = labels
+            .iter()
+            .copied()
+            .filter(|label| !label.is_multiline() && label.lines().0 == line)
+            .collect();
+
+        if single.is_empty() {
+            return rows;
+        }
+
+        single.sort_by_key(|label| label.columns().0);
+
+        let mut underline = lanes(None);
+        for label in single.iter().filter(|label| !label.primary) {
+            let (start, end) = label.columns();
+            underline.fill(
+                lanes_width + start,
+                lanes_width + end,
+                '-',
+                self.paint(label),
+            );
+        }
+        for label in single.iter().filter(|label| label.primary) {
+            let (start, end) = label.columns();
+            underline.fill(
+                lanes_width + start,
+                lanes_width + end,
+                '^',
+                self.paint(label),
+            );
+        }
+
+        let mut with_message: Vec<&Label> = single
+            .iter()
+            .copied()
+            .filter(|label| label.message.is_some())
+            .collect();
+
+        // the rightmost message fits on the underline itself
+        if let Some(last) = with_message.pop() {
+            let (_, end) = last.columns();
+            let message = last.message.as_ref().unwrap();
+            underline.text(lanes_width + end + 1, message, self.paint(last));
+        }
+        rows.push(underline);
+
+        // the other ones hang below their label, from right to left
+        while let Some(label) = with_message.pop() {
+            let mut connector = lanes(None);
+            let mut message_row = lanes(None);
+
+            for other in &with_message {
+                let column = lanes_width + other.columns().0;
+                connector.put(column, '|', self.paint(other));
+                message_row.put(column, '|', self.paint(other));
+            }
+
+            let column = lanes_width + label.columns().0;
+            connector.put(column, '|', self.paint(label));
+            message_row.text(column, label.message.as_ref().unwrap(), self.paint(label));
+
+            rows.push(connector);
+            rows.push(message_row);
+        }
+
+        rows
+    }
+
+    fn paint(&self, label: &Label) -> Color {
+        if label.primary {
+            self.severity.color()
+        } else {
+            Color::Blue
+        }
+    }
+
+    fn mark(&self, label: &Label) -> char {
+        if label.primary {
+            '^'
+        } else {
+            '-'
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = match self.code_name() {
+            Some(code) => format!("{}[{}]", self.severity, code),
+            None => self.severity.to_string(),
+        };
+        writeln!(
+            f,
+            "{}: {}",
+            header.color(self.severity.color()).bold(),
+            self.message.bold()
+        )?;
+
+        let groups = self.labels_by_file();
+        let gutter = groups
+            .iter()
+            .flat_map(|(_, labels)| snippet_lines(labels))
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+
+        for (index, (file, labels)) in groups.iter().enumerate() {
+            let arrow = if index == 0 { "-->" } else { ":::" };
+            writeln!(
+                f,
+                "{}{} {}",
+                " ".repeat(gutter),
+                arrow.blue(),
+                labels[0].span.get_location()
+            )?;
+            writeln!(f, "{} {}", " ".repeat(gutter), "|".blue())?;
+
+            self.render_snippet(f, file, labels, gutter)?;
+        }
+
+        let has_sub_messages =
+            !self.notes.is_empty() || !self.helps.is_empty() || !self.suggestions.is_empty();
+        if !groups.is_empty() && has_sub_messages {
+            writeln!(f, "{} {}", " ".repeat(gutter), "|".blue())?;
+        }
+
+        for note in &self.notes {
+            writeln!(
+                f,
+                "{} {} {}: {}",
+                " ".repeat(gutter),
+                "=".blue(),
+                "note".bold(),
+                indent_continuation(note, gutter + "= note: ".len() + 1)
+            )?;
+        }
+
+        let suggestions = self.suggestions.iter().map(|s| &s.message);
+        for help in self.helps.iter().chain(suggestions) {
+            writeln!(
+                f,
+                "{} {} {}: {}",
+                " ".repeat(gutter),
+                "=".blue(),
+                "help".bold(),
+                indent_continuation(help, gutter + "= help: ".len() + 1)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The sorted source lines displayed for `labels`, long multi-line labels only show their
+/// first and last lines.
+fn snippet_lines(labels: &[&Label]) -> Vec<usize> {
+    let mut lines = BTreeSet::new();
+
+    for label in labels {
+        let (start, end) = label.lines();
+
+        if end - start < MAX_MULTILINE_LINES {
+            lines.extend(start..=end);
+        } else {
+            lines.extend([start, start + 1, end - 1, end]);
+        }
+    }
+
+    // a single hidden line takes as much room as the `...` replacing it
+    let mut filled: Vec<usize> = Vec::new();
+    for line in lines {
+        if let Some(&previous) = filled.last() {
+            if line == previous + 2 {
+                filled.push(previous + 1);
+            }
+        }
+        filled.push(line);
+    }
+
+    filled
+}
+
+/// Aligns the lines following the first one of a multi-line note or help with its text.
+fn indent_continuation(text: &str, width: usize) -> String {
+    text.replace('\n', &format!("\n{}", " ".repeat(width)))
+}
+
+/// Lines and columns start at 1, the end column is exclusive.
+fn span_to_json(span: &Span) -> Json {
+    let (line_start, column_start) = span.file.line_column(span.start);
+    let (line_end, column_end) = span.file.line_column(span.end);
+
+    Json::object()
+        .with("byte_start", span.start)
+        .with("byte_end", span.end)
+        .with("line_start", line_start)
+        .with("column_start", column_start)
+        .with("line_end", line_end)
+        .with("column_end", column_end)
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
+
+/// 0-based column of the byte `offset` once the tabs of its line are expanded, an offset inside
+/// of a character is the column of that character.
+fn display_column(file: &SourceFile, offset: usize) -> usize {
+    let offset = floor_char_boundary(&file.src, offset);
+    let line_start = file.line_starts()[file.line_number(offset) - 1];
+
+    file.src[line_start..offset]
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// The start of the character `offset` falls in, `offset` itself past the end of `src`.
+fn floor_char_boundary(src: &str, offset: usize) -> usize {
+    let mut offset = offset.min(src.len());
+    while !src.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// A line of markers drawn under a source line, every cell has its own color.
+struct Row {
+    cells: Vec<(char, Option<Color>)>,
+}
+
+impl Row {
+    fn new() -> Self {
+        Self { cells: Vec::new() }
+    }
+
+    fn pad(&mut self, width: usize) {
+        while self.cells.len() < width {
+            self.cells.push((' ', None));
+        }
+    }
+
+    fn put(&mut self, column: usize, c: char, color: Color) {
+        self.pad(column + 1);
+        self.cells[column] = (c, Some(color));
+    }
+
+    /// Fills `[from, to)` with `c`, keeping the lanes of other multi-line labels visible.
+    fn fill(&mut self, from: usize, to: usize, c: char, color: Color) {
+        for column in from..to {
+            if c == '_' && self.cells.get(column).is_some_and(|cell| cell.0 == '|') {
+                continue;
+            }
+            self.put(column, c, color);
+        }
+    }
+
+    fn text(&mut self, column: usize, text: &str, color: Color) {
+        for (offset, c) in text.chars().enumerate() {
+            self.put(column + offset, c, color);
+        }
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut start = 0;
+
+        while start < self.cells.len() {
+            let color = self.cells[start].1;
+            let end = self.cells[start..]
+                .iter()
+                .position(|cell| cell.1 != color)
+                .map_or(self.cells.len(), |length| start + length);
+
+            let run: String = self.cells[start..end].iter().map(|cell| cell.0).collect();
+            match color {
+                Some(color) => write!(f, "{}", run.color(color).bold())?,
+                None => write!(f, "{}", run)?,
+            }
+
+            start = end;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::source::{SourceFile, Span};
+
+    use super::{Diagnostic, Label};
+
+    fn render(diagnostic: &Diagnostic) -> String {
+        colored::control::set_override(false);
+        diagnostic.to_string()
+    }
+
+    fn span(file: &Rc<SourceFile>, text: &str) -> Span {
+        let start = file.src.find(text).unwrap();
+        Span::new(Rc::clone(file), start, start + text.len())
+    }
+
+    #[test]
+    fn test_render_single_line_labels() {
+        let file = Rc::new(SourceFile::new(
+            0,
+            "main.eheh",
+            "let a = 1;\nlet b = a + true;\n",
+        ));
+
+        let diagnostic = Diagnostic::error("mismatched types")
+            .with_code(42)
+            .with_label(Label::primary(span(&file, "true")).with_message("expected `int`"))
+            .with_label(Label::secondary(span(&file, "a +")).with_message("this is an `int`"))
+            .with_note("booleans cannot be added to integers")
+            .with_help("remove the `true`");
+
+        assert_eq!(
+            render(&diagnostic),
+            [
+                "error[E00042]: mismatched types",
+                " --> main.eheh:2:13",
+                "  |",
+                "2 | let b = a + true;",
+                "  |         --- ^^^^ expected `int`",
+                "  |         |",
+                "  |         this is an `int`",
+                "  |",
+                "  = note: booleans cannot be added to integers",
+                "  = help: remove the `true`",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_render_multiline_label() {
+        let file = Rc::new(SourceFile::new(
+            0,
+            "main.eheh",
+            "let f = fn() {\n    1;\n};\nf()\n",
+        ));
+
+        let start = file.src.find("fn").unwrap();
+        let end = file.src.find("};").unwrap() + 1;
+
+        let diagnostic = Diagnostic::warning("unused function")
+            .with_label(
+                Label::primary(Span::new(Rc::clone(&file), start, end))
+                    .with_message("never called"),
+            )
+            .with_label(Label::secondary(span(&file, "f()")));
+
+        assert_eq!(
+            render(&diagnostic),
+            [
+                "warning: unused function",
+                " --> main.eheh:1:9",
+                "  |",
+                "1 |   let f = fn() {",
+                "  |  _________^",
+                "2 | |     1;",
+                "3 | | };",
+                "  | |_^ never called",
+                "4 |   f()",
+                "  |   ---",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_render_multibyte_characters() {
+        let file = Rc::new(SourceFile::new(
+            0,
+            "main.eheh",
+            "let x = café + 1;\nlet y = 1 § 2;\n",
+        ));
+
+        let diagnostic = Diagnostic::error("identifier not found")
+            .with_label(Label::primary(span(&file, "café")).with_message("not found"))
+            .with_label(Label::secondary(span(&file, "§")).with_message("invalid"));
+
+        assert_eq!(
+            render(&diagnostic),
+            [
+                "error: identifier not found",
+                " --> main.eheh:1:9",
+                "  |",
+                "1 | let x = café + 1;",
+                "  |         ^^^^ not found",
+                "2 | let y = 1 § 2;",
+                "  |           - invalid",
+                "",
+            ]
+            .join("\n")
+        );
+
+        // a span ending inside of a character still covers all of it
+        let start = file.src.find('§').unwrap();
+        let half = Span::new(Rc::clone(&file), start, start + 1);
+        let diagnostic = Diagnostic::error("invalid character").with_label(Label::primary(half));
+
+        assert!(render(&diagnostic).contains("  |           ^\n"));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let file = Rc::new(SourceFile::new(
+            0,
+            "main.eheh",
+            "let a = 1;\nlet b = café;\n",
+        ));
+
+        let diagnostic = Diagnostic::warning("unused variable")
+            .with_code(2000)
+            .with_label(Label::primary(span(&file, "café;")).with_message("never read"));
+
+        assert_eq!(
+            diagnostic.to_json().to_string(),
+            concat!(
+                r#"{"code":"E02000","severity":"warning","message":"unused variable","#,
+                r#""file":"main.eheh","span":{"byte_start":19,"byte_end":25,"line_start":2,"#,
+                r#""column_start":9,"line_end":2,"column_end":14},"labels":[{"primary":true,"#,
+                r#""message":"never read","file":"main.eheh","span":{"byte_start":19,"#,
+                r#""byte_end":25,"line_start":2,"column_start":9,"line_end":2,"column_end":14}}],"#,
+                r#""notes":[],"helps":[],"suggestions":[]}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::log::{
+    diagnostic::{Diagnostic, Severity},
+    registry::{self, CodeInfo},
+    sarif::to_sarif,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    /// Rendered snippets meant for a terminal, written to stderr.
+    #[default]
+    Human,
+    /// One JSON object per diagnostic and per line, written to stdout.
+    Json,
+    /// A single SARIF 2.1.0 log written to stdout once every diagnostic is known.
+    Sarif,
+}
+
+impl MessageFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(MessageFormat::Human),
+            "json" => Some(MessageFormat::Json),
+            "sarif" => Some(MessageFormat::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// Writes diagnostics out in the requested `MessageFormat`.
+#[derive(Debug, Clone, Default)]
+pub struct Emitter {
+    pub format: MessageFormat,
+    /// Diagnostics held back until `finish`, only used by formats that need all of them.
+    pending: Vec<Diagnostic>,
+    /// Errors emitted so far that `eheh explain` knows about, in order of appearance.
+    explained_errors: Vec<&'static CodeInfo>,
+}
+
+impl Emitter {
+    pub fn new(format: MessageFormat) -> Self {
+        Self {
+            format,
+            pending: Vec::new(),
+            explained_errors: Vec::new(),
+        }
+    }
+
+    pub fn emit(&mut self, diagnostic: Diagnostic) {
+        let explained = diagnostic
+            .code
+            .and_then(registry::lookup)
+            .filter(|info| info.explanation.is_some());
+        if let Some(info) = explained {
+            if diagnostic.severity == Severity::Error && !self.explained_errors.contains(&info) {
+                self.explained_errors.push(info);
+            }
+        }
+
+        match self.format {
+            MessageFormat::Human => eprintln!("{}", diagnostic),
+            MessageFormat::Json => println!("{}", diagnostic.to_json()),
+            MessageFormat::Sarif => self.pending.push(diagnostic),
+        }
+    }
+
+    /// Flushes the diagnostics that were held back, must be called once before exiting.
+    pub fn finish(&mut self) {
+        match self.format {
+            MessageFormat::Human => match self.explained_errors.as_slice() {
+                [] => {}
+                [info] => eprintln!(
+                    "For more information about this error, try `eheh explain {}`.",
+                    info.name()
+                ),
+                infos => {
+                    let names: Vec<String> = infos.iter().map(|info| info.name()).collect();
+
+                    eprintln!(
+                        "Some errors have detailed explanations: {}.",
+                        names.join(", ")
+                    );
+                    eprintln!(
+                        "For more information about an error, try `eheh explain {}`.",
+                        names[0]
+                    );
+                }
+            },
+            MessageFormat::Json => {}
+            MessageFormat::Sarif => {
+                println!("{}", to_sarif(&self.pending));
+                self.pending.clear();
+            }
+        }
+
+        self.explained_errors.clear();
+    }
+}
@@ -0,0 +1,154 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    log::diagnostic::{Diagnostic, Label},
+    token::Token,
+};
+
+#[derive(Debug, Clone)]
+pub enum LexerErrorCode {
+    UnterminatedString { token: Token },
+    UnterminatedBlockComment { token: Token },
+    InvalidCharacter { token: Token },
+    MalformedNumber { token: Token },
+}
+
+impl LexerErrorCode {
+    /// The code of the error, see `log::registry`. Codes are stable: never renumber or reuse one.
+    pub fn id(&self) -> usize {
+        match self {
+            LexerErrorCode::UnterminatedString { .. } => 100,
+            LexerErrorCode::UnterminatedBlockComment { .. } => 101,
+            LexerErrorCode::InvalidCharacter { .. } => 102,
+            LexerErrorCode::MalformedNumber { .. } => 103,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        match self {
+            LexerErrorCode::UnterminatedString { token }
+            | LexerErrorCode::UnterminatedBlockComment { token }
+            | LexerErrorCode::InvalidCharacter { token }
+            | LexerErrorCode::MalformedNumber { token } => token,
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let token = self.token();
+        let label = Label::primary(token.span.clone());
+
+        let diagnostic = match self {
+            LexerErrorCode::UnterminatedString { .. } => {
+                Diagnostic::error("The string literal is missing its closing `\"`.")
+                    .with_label(label.with_message("the string starts here"))
+                    .with_help("add a `\"` where the string should end")
+            }
+            LexerErrorCode::UnterminatedBlockComment { .. } => {
+                Diagnostic::error("The block comment is missing its closing `*/`.")
+                    .with_label(label.with_message("the comment starts here"))
+                    .with_help("add a `*/` where the comment should end")
+            }
+            LexerErrorCode::InvalidCharacter { .. } => Diagnostic::error(format!(
+                "Invalid character `{}` in the source code.",
+                token.literal()
+            ))
+            .with_label(label),
+            LexerErrorCode::MalformedNumber { .. } => {
+                let diagnostic =
+                    Diagnostic::error(format!("Malformed number literal `{}`.", token.literal()))
+                        .with_label(label);
+
+                if token.literal().chars().any(char::is_alphabetic) {
+                    diagnostic.with_help("identifiers cannot start with a digit")
+                } else {
+                    diagnostic.with_note(format!(
+                        "integers must be between {} and {}",
+                        i64::MIN,
+                        i64::MAX
+                    ))
+                }
+            }
+        };
+
+        diagnostic.with_code(self.id())
+    }
+}
+
+impl Display for LexerErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LexerError {
+    pub source: Option<Box<LexerError>>,
+    pub code: LexerErrorCode,
+}
+
+impl LexerError {
+    pub fn new(code: LexerErrorCode) -> Self {
+        Self { source: None, code }
+    }
+
+    pub fn set_source(&mut self, err: LexerError) {
+        self.source = Some(Box::new(err));
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        self.code.to_diagnostic()
+    }
+}
+
+impl Display for LexerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Error for LexerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        if let Some(sauce) = &self.source {
+            Some(sauce)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, log::error::parser::ParserErrorCode, parser::Parser};
+
+    #[test]
+    fn test_lexical_errors_are_reported_by_the_parser() {
+        const CODE: &str = r#"
+            let a = 1;
+            let b = 2; §
+            let c = 3;
+            let d = "never closed;
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        let lexical_errors: Vec<usize> = parser
+            .errors
+            .iter()
+            .filter_map(|err| match &err.code {
+                ParserErrorCode::LexicalError(err) => Some(err.code.id()),
+                _ => None,
+            })
+            .collect();
+
+        // invalid character `§`, unterminated string
+        assert_eq!(lexical_errors, vec![102, 100]);
+
+        // the statements following the invalid character are still parsed
+        assert!(program
+            .statements
+            .iter()
+            .any(|stmt| stmt.to_string() == "let c = 3;"));
+    }
+}
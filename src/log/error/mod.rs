@@ -1,2 +1,4 @@
+pub mod lexer;
 pub mod parser;
 pub mod runtime;
+pub mod type_checker;
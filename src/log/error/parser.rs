@@ -1,249 +1,213 @@
 use std::{error::Error, fmt::Display};
 
-use colored::Colorize;
+use crate::{
+    log::diagnostic::{Applicability, Diagnostic, Label, Suggestion, TextEdit},
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+};
 
-use crate::token::{token_type::TokenType, Token};
+use super::lexer::LexerError;
 
 #[derive(Debug, Clone)]
-#[repr(usize)]
 pub enum ParserErrorCode {
     UnexpectedToken {
         token: Token,
         expected_token_types: Vec<TokenType>,
-        context: Option<String>,
-    } = 1_000,
+    },
     UnknownPrefixToken {
         token: Token,
-        context: Option<String>,
     },
     MissingIfCondition {
         token: Token,
-        context: Option<String>,
     },
     DelimiterMismatch {
         token: Token,
         expected_delimiter: TokenType,
         current_delimiter: Option<TokenType>,
-        context: Option<Vec<String>>,
     },
     MissingFnReturnType {
         token: Token,
-        context: Option<String>,
+    },
+    LexicalError(LexerError),
+    TooManyErrors {
+        token: Token,
+        limit: usize,
+    },
+    MisspelledKeyword {
+        token: Token,
+        keyword: KeywordTokenType,
+    },
+    /// `token` is the name of the attribute.
+    UnknownAttribute {
+        token: Token,
+    },
+    /// `token` starts the positional argument.
+    PositionalAfterNamedArgument {
+        token: Token,
     },
     Unknown,
 }
 
 impl ParserErrorCode {
+    /// The code of the error, see `log::registry`. Codes are stable: never renumber or reuse one.
     pub fn id(&self) -> usize {
-        unsafe { *(self as *const Self as *const usize) }
+        match self {
+            ParserErrorCode::UnexpectedToken { .. } => 1000,
+            ParserErrorCode::UnknownPrefixToken { .. } => 1001,
+            ParserErrorCode::MissingIfCondition { .. } => 1002,
+            ParserErrorCode::DelimiterMismatch { .. } => 1003,
+            ParserErrorCode::MissingFnReturnType { .. } => 1004,
+            ParserErrorCode::LexicalError(err) => err.code.id(),
+            ParserErrorCode::TooManyErrors { .. } => 1006,
+            ParserErrorCode::Unknown => 1007,
+            ParserErrorCode::MisspelledKeyword { .. } => 1008,
+            ParserErrorCode::UnknownAttribute { .. } => 1009,
+            ParserErrorCode::PositionalAfterNamedArgument { .. } => 1010,
+        }
     }
-}
 
-impl Display for ParserErrorCode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    pub fn token(&self) -> Option<&Token> {
         match self {
+            ParserErrorCode::UnexpectedToken { token, .. }
+            | ParserErrorCode::UnknownPrefixToken { token }
+            | ParserErrorCode::MissingIfCondition { token }
+            | ParserErrorCode::DelimiterMismatch { token, .. }
+            | ParserErrorCode::MissingFnReturnType { token }
+            | ParserErrorCode::TooManyErrors { token, .. }
+            | ParserErrorCode::MisspelledKeyword { token, .. }
+            | ParserErrorCode::UnknownAttribute { token }
+            | ParserErrorCode::PositionalAfterNamedArgument { token } => Some(token),
+            ParserErrorCode::LexicalError(err) => Some(err.code.token()),
+            ParserErrorCode::Unknown => None,
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
             ParserErrorCode::UnexpectedToken {
                 token,
                 expected_token_types,
-                context,
             } => {
-                write!(f, "{}", format!("error[E{:0>5}]", self.id()).red())?;
-
-                match expected_token_types.len() {
-                    0 => (),
-                    1 => {
-                        write!(f, ": expected `{}`", expected_token_types[0].to_literal())?;
-                    }
-                    _ => {
-                        write!(
-                            f,
-                            ": expected one of {} or `{}`",
-                            expected_token_types[0..expected_token_types.len() - 1]
-                                .iter()
-                                .map(|t| { format!("`{}`", t.to_literal()) })
-                                .collect::<Vec<String>>()
-                                .join(", "),
-                            expected_token_types.last().unwrap().to_literal()
-                        )?;
-                    }
-                }
-
-                writeln!(f, ", found `{}`", token.literal)?;
-                writeln!(f, "  {} {}", "-->".blue(), token.get_location())?;
-
-                if let Some(ctx) = context {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        ctx
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).red()
-                    )?;
-                    match expected_token_types.len() {
-                        1 => {
-                            writeln!(f, ": expected `{}`", expected_token_types[0].to_literal())?;
-                        }
-                        _ => {
-                            write!(
-                                f,
-                                ": expected one of {}",
-                                expected_token_types[0..expected_token_types.len() - 1]
-                                    .iter()
-                                    .map(|t| { format!("`{}`", t.to_literal()) })
-                                    .collect::<Vec<String>>()
-                                    .join(", ")
-                            )?;
-
-                            writeln!(
-                                f,
-                                " or `{}`",
-                                expected_token_types.last().unwrap().to_literal()
-                            )?;
-                        }
-                    };
-                }
-            }
-            ParserErrorCode::MissingIfCondition { token, context } => {
-                writeln!(
-                    f,
-                    "{}: The `if` expression is missing a condition.",
-                    format!("error[E{:0>5}]", self.id()).red()
-                )?;
-
-                writeln!(f, "  {} {}", "-->".blue(), token.get_location())?;
-
-                if let Some(ctx) = context {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        ctx
-                    )?;
-                    writeln!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).red()
-                    )?;
+                let expected = match expected_token_types.len() {
+                    0 => String::from("something else"),
+                    1 => format!("`{}`", expected_token_types[0].to_literal()),
+                    _ => format!(
+                        "one of {} or `{}`",
+                        expected_token_types[0..expected_token_types.len() - 1]
+                            .iter()
+                            .map(|t| format!("`{}`", t.to_literal()))
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                        expected_token_types.last().unwrap().to_literal()
+                    ),
                 };
-            }
-            ParserErrorCode::MissingFnReturnType { token, context } => {
-                writeln!(
-                    f,
-                    "{}: The function is missing a return type.",
-                    format!("error[E{:0>5}]", self.id()).red()
-                )?;
 
-                writeln!(f, "  {} {}", "-->".blue(), token.get_location())?;
-
-                if let Some(ctx) = context {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        ctx
-                    )?;
-                    writeln!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).red()
-                    )?;
-                }
+                Diagnostic::error(format!("expected {}, found {}", expected, describe(token)))
+                    .with_label(
+                        Label::primary(token.span.clone())
+                            .with_message(format!("expected {}", expected)),
+                    )
             }
-            ParserErrorCode::Unknown => {
-                writeln!(
-                    f,
-                    "{}: An unknown error occured while parsing the code.",
-                    format!("error[E{:0>5}]", self.id()).red(),
-                );
+            ParserErrorCode::UnknownPrefixToken { token } => {
+                Diagnostic::error(format!("Unknown prefix token: {}.", describe(token))).with_label(
+                    Label::primary(token.span.clone()).with_message("expected an expression"),
+                )
             }
-            ParserErrorCode::UnknownPrefixToken { token, context } => {
-                writeln!(
-                    f,
-                    "{}: Unknown prefix token: `{}`.",
-                    format!("error[E{:0>5}]", self.id()).red(),
-                    token.literal
-                )?;
-                writeln!(f, "  {} {}", "-->".blue(), token.get_location())?;
-
-                if let Some(ctx) = context {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        ctx
-                    )?;
-                    writeln!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).red()
-                    )?;
-                }
+            ParserErrorCode::MissingIfCondition { token } => {
+                Diagnostic::error("The `if` expression is missing a condition.")
+                    .with_label(
+                        Label::primary(token.span.clone()).with_message("expected a condition"),
+                    )
+                    .with_help("add a condition before the block, e.g: `if x > 0 { ... }`")
             }
             ParserErrorCode::DelimiterMismatch {
                 token,
                 expected_delimiter,
                 current_delimiter,
-                context,
             } => {
-                write!(
-                    f,
-                    "{}: The `{}` delimiter is missing its pair, expected to match with a `{}`",
-                    format!("error[E{:0>5}]", self.id()).red(),
+                let got = match current_delimiter {
+                    Some(delimiter) => delimiter.to_literal(),
+                    None => token.t.to_literal(),
+                };
+
+                Diagnostic::error(format!(
+                    "The `{}` delimiter is missing its pair, expected to match with a `{}`, got {}.",
                     TokenType::matching_delimiter(expected_delimiter.to_owned())
                         .unwrap()
                         .to_literal(),
-                    expected_delimiter.to_literal()
-                )?;
-
-                if let Some(delimiter) = current_delimiter {
-                    writeln!(f, ", got {}.", delimiter.to_literal())?;
-                } else {
-                    writeln!(f, ", got {}.", token.t.to_literal())?;
-                }
-
-                writeln!(f, "  {} {}", "-->".blue(), token.get_location())?;
-
-                if let Some(ctx) = context {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        ctx.join("\n")
-                    )?;
-                    writeln!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).red()
-                    )?;
-                }
+                    expected_delimiter.to_literal(),
+                    got
+                ))
+                .with_label(
+                    Label::primary(token.span.clone())
+                        .with_message(format!("expected `{}`", expected_delimiter.to_literal())),
+                )
+            }
+            ParserErrorCode::MissingFnReturnType { token } => {
+                Diagnostic::error("The function is missing a return type.")
+                    .with_label(
+                        Label::primary(token.span.clone())
+                            .with_message("expected a type after `->`"),
+                    )
+                    .with_help("remove the `->` if the function does not return anything")
+            }
+            ParserErrorCode::LexicalError(err) => return err.code.to_diagnostic(),
+            ParserErrorCode::TooManyErrors { token, limit } => {
+                Diagnostic::error(format!("Aborting after {} errors.", limit))
+                    .with_label(
+                        Label::primary(token.span.clone()).with_message("parsing stopped here"),
+                    )
+                    .with_note(
+                        "the following errors would most likely be caused by the previous ones",
+                    )
+            }
+            ParserErrorCode::MisspelledKeyword { token, keyword } => {
+                Diagnostic::error(format!("Unknown keyword {}.", describe(token)))
+                    .with_label(Label::primary(token.span.clone()).with_message("not a keyword"))
+                    .with_suggestion(Suggestion::new(
+                        format!(
+                            "there is a keyword `{}` with a similar name",
+                            keyword.to_literal()
+                        ),
+                        vec![TextEdit::new(token.span.clone(), keyword.to_literal())],
+                        Applicability::MaybeIncorrect,
+                    ))
+            }
+            ParserErrorCode::UnknownAttribute { token } => {
+                Diagnostic::error(format!("Unknown attribute {}.", describe(token)))
+                    .with_label(Label::primary(token.span.clone()).with_message("not an attribute"))
+                    .with_help("the known attributes are `allow`, `warn` and `deny`")
+            }
+            ParserErrorCode::PositionalAfterNamedArgument { token } => {
+                Diagnostic::error("Positional argument after named arguments.")
+                    .with_label(
+                        Label::primary(token.span.clone())
+                            .with_message("expected a named argument"),
+                    )
+                    .with_help("pass the positional arguments first, e.g: `f(1, y = 2)`")
+            }
+            ParserErrorCode::Unknown => {
+                Diagnostic::error("An unknown error occured while parsing the code.")
             }
         };
 
-        Ok(())
+        diagnostic.with_code(self.id())
+    }
+}
+
+/// How a token is called in error messages.
+fn describe(token: &Token) -> String {
+    match token.t {
+        TokenType::EOF => String::from("end of file"),
+        _ => format!("`{}`", token.span.source_text()),
+    }
+}
+
+impl Display for ParserErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
     }
 }
 
@@ -257,6 +221,19 @@ impl ParserError {
     pub fn set_source(&mut self, err: ParserError) {
         self.source = Some(Box::new(err));
     }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        self.code.to_diagnostic()
+    }
+}
+
+impl From<LexerError> for ParserError {
+    fn from(err: LexerError) -> Self {
+        Self {
+            source: None,
+            code: ParserErrorCode::LexicalError(err),
+        }
+    }
 }
 
 impl Display for ParserError {
@@ -276,6 +253,7 @@ impl Error for ParserError {
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_static_lifetimes)]
 mod tests {
     use crate::{lexer::Lexer, parser::Parser};
 
@@ -298,13 +276,41 @@ mod tests {
         let mut parser = Parser::new(&mut lexer);
         let _program = parser.parse().unwrap();
 
-        // unexpected token: `x` -> `(`
-        // Unknown prefix token
+        // unexpected token: `:` -> `(`
+        // missing return type
         // Delimiter mismatch (true -> missing `)`
-        // Unknown prefix token
-        let error_codes: Vec<usize> = vec![1000, 1001, 1004, 1000, 1001];
+        let error_codes: Vec<usize> = vec![1000, 1004, 1003];
+        assert_eq!(
+            parser.errors.len(),
+            error_codes.len(),
+            "{:#?}",
+            parser.errors
+        );
         for (index, code) in error_codes.iter().enumerate() {
             assert_eq!(parser.errors[index].code.id(), *code);
         }
     }
+
+    #[test]
+    fn test_error_rendering() {
+        const CODE: &str = "let a = 1;\nlet b = (a + 2;\n";
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let _program = parser.parse().unwrap();
+
+        colored::control::set_override(false);
+        assert_eq!(
+            parser.errors[0].to_string(),
+            [
+                "error[E01000]: expected `)`, found `;`",
+                " --> <anonymous>:2:15",
+                "  |",
+                "2 | let b = (a + 2;",
+                "  |               ^ expected `)`",
+                "",
+            ]
+            .join("\n")
+        );
+    }
 }
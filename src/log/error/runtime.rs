@@ -1,15 +1,17 @@
 use std::{error::Error, fmt::Display};
 
-use colored::Colorize;
-
-use crate::token::{token_type::TokenType, Token};
+use crate::{
+    log::diagnostic::{Diagnostic, Label},
+    token::{token_type::KeywordTokenType, Token},
+};
 
 #[derive(Debug, Clone)]
-#[repr(usize)]
 pub enum RuntimeErrorCode {
     IdentifierNotFound {
         identifier: String,
         context: Option<String>,
+        /// A visible name or a keyword close to `identifier`.
+        suggestion: Option<String>,
     },
     DivisionByZero {
         location: Token,
@@ -43,6 +45,8 @@ pub enum RuntimeErrorCode {
     UnknownInfixOperator {
         operator: String,
         context: Option<String>,
+        /// A supported operator close to `operator`.
+        suggestion: Option<String>,
     },
     // structs, fields and modules
     PrivateAccessError,
@@ -62,130 +66,252 @@ pub enum RuntimeErrorCode {
     UnresolvedName,
     UnexpectedToken,
     Custom(String), // For more specific or custom errors
+    /// A call passing more arguments than `function` has parameters or leaving some out.
+    ArityMismatch {
+        function: String,
+        /// e.g: `2 arguments`, `1 to 2 arguments` or `at least 1 argument`.
+        expected: String,
+        found: usize,
+        /// The parameters without a default value nothing was passed to.
+        missing: Vec<String>,
+    },
+    UnknownArgument {
+        function: String,
+        argument: String,
+        /// A parameter of `function` with a name close to `argument`.
+        suggestion: Option<String>,
+    },
+    DuplicateArgument {
+        function: String,
+        argument: String,
+    },
+    /// A call to a value that is not a function, e.g: `5(1)`.
+    NotCallable {
+        callee: String,
+        found: String,
+    },
+    /// A call nested deeper than the `--max-call-depth` limit, usually endless recursion.
+    CallDepthExceeded {
+        limit: usize,
+    },
+    /// An evaluation nested deeper than the stack of its thread fits, after `depth` calls.
+    StackExhausted {
+        depth: usize,
+    },
 }
 
 impl RuntimeErrorCode {
+    /// The code of the error, see `log::registry`. Codes are stable: never renumber or reuse one.
     pub fn id(&self) -> usize {
-        unsafe { *(self as *const Self as *const usize) }
+        match self {
+            RuntimeErrorCode::IdentifierNotFound { .. } => 3000,
+            RuntimeErrorCode::DivisionByZero { .. } => 3001,
+            RuntimeErrorCode::InvalidOperation { .. } => 3002,
+            RuntimeErrorCode::TypeMismatch { .. } => 3003,
+            RuntimeErrorCode::TypeNotFound { .. } => 3004,
+            RuntimeErrorCode::FunctionNotFound { .. } => 3005,
+            RuntimeErrorCode::IndexOutOfRange { .. } => 3006,
+            RuntimeErrorCode::InvalidArraySize { .. } => 3007,
+            RuntimeErrorCode::UnknownInfixOperator { .. } => 3008,
+            RuntimeErrorCode::PrivateAccessError => 3009,
+            RuntimeErrorCode::InaccessibleModule => 3010,
+            RuntimeErrorCode::InaccessibleFunction => 3011,
+            RuntimeErrorCode::InaccessibleField => 3012,
+            RuntimeErrorCode::InaccessibleEnum => 3013,
+            RuntimeErrorCode::ConstEvaluationError => 3014,
+            RuntimeErrorCode::InvalidConstValue => 3015,
+            RuntimeErrorCode::UninitializedVariable => 3016,
+            RuntimeErrorCode::UninitializedStatic => 3017,
+            RuntimeErrorCode::OverflowError => 3018,
+            RuntimeErrorCode::UnderflowError => 3019,
+            RuntimeErrorCode::UnreachableCode => 3020,
+            RuntimeErrorCode::UnresolvedName => 3021,
+            RuntimeErrorCode::UnexpectedToken => 3022,
+            RuntimeErrorCode::Custom(_) => 3023,
+            RuntimeErrorCode::ArityMismatch { .. } => 3024,
+            RuntimeErrorCode::UnknownArgument { .. } => 3025,
+            RuntimeErrorCode::DuplicateArgument { .. } => 3026,
+            RuntimeErrorCode::NotCallable { .. } => 3027,
+            RuntimeErrorCode::CallDepthExceeded { .. }
+            | RuntimeErrorCode::StackExhausted { .. } => 3028,
+        }
     }
 }
 
-impl Display for RuntimeErrorCode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
+impl RuntimeErrorCode {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
             RuntimeErrorCode::IdentifierNotFound {
                 identifier,
                 context,
+                suggestion,
             } => {
-                write!(f, "{}", format!("runtime_error[E{:0>5}]", self.id()).red())?;
-                writeln!(f, ": Identifier '{}' not found", identifier)?;
-
-                if let Some(ctx) = context {
-                    let identifier_pos = ctx.find(identifier).unwrap();
-
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(f, "{:3} {}\t{}", "10".to_string().blue(), "|".blue(), ctx)?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(identifier_pos),
-                        "^".repeat(identifier.len()).red()
-                    )?;
-                }
-            }
-            RuntimeErrorCode::DivisionByZero { location } => {
-                writeln!(f, "Division by zero at {}", location.get_location())?;
-            }
-            RuntimeErrorCode::InvalidOperation { operation, context } => {
-                write!(f, "{}", format!("runtime_error[E{:0>5}]", self.id()).red())?;
-                writeln!(f, ": Invalid operation: {}", operation)?;
-
-                if let Some(ctx) = context {
-                    let operation_pos = ctx.find(operation).unwrap();
-
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(f, "{:3} {}\t{}", "10".to_string().blue(), "|".blue(), ctx)?;
-                    writeln!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(operation_pos),
-                        "^".repeat(operation.len()).red()
-                    )?;
+                let diagnostic = with_context(
+                    Diagnostic::error(format!("Identifier `{}` not found.", identifier)),
+                    context,
+                    identifier,
+                );
+
+                match suggestion {
+                    Some(keyword) if KeywordTokenType::from_str(keyword).is_some() => diagnostic
+                        .with_help(format!(
+                            "there is a keyword `{}` with a similar name",
+                            keyword
+                        )),
+                    Some(name) => diagnostic
+                        .with_help(format!("a variable with a similar name exists: `{}`", name)),
+                    None => diagnostic,
                 }
             }
+            RuntimeErrorCode::DivisionByZero { location } => Diagnostic::error("Division by zero.")
+                .with_label(
+                    Label::primary(location.span.clone()).with_message("the divisor is zero"),
+                ),
+            RuntimeErrorCode::InvalidOperation { operation, context } => with_context(
+                Diagnostic::error(format!("Invalid operation: `{}`.", operation)),
+                context,
+                operation,
+            ),
             RuntimeErrorCode::TypeMismatch {
                 expected_type,
                 actual_type,
                 location,
-            } => {
-                writeln!(
-                    f,
-                    "Type mismatch: Expected {} but got {} at {}",
-                    expected_type,
-                    actual_type,
-                    location.get_location()
-                )?;
-            }
+            } => Diagnostic::error(format!(
+                "Type mismatch: expected `{}` but got `{}`.",
+                expected_type, actual_type
+            ))
+            .with_label(
+                Label::primary(location.span.clone())
+                    .with_message(format!("expected `{}`", expected_type)),
+            ),
+            RuntimeErrorCode::TypeNotFound {
+                expected_type,
+                location,
+            } => Diagnostic::error(format!("Type `{}` not found.", expected_type))
+                .with_label(Label::primary(location.span.clone())),
             RuntimeErrorCode::FunctionNotFound {
                 function_name,
                 context,
-            } => {
-                writeln!(f, "Function '{}' not found", function_name)?;
-                if let Some(ctx) = context {
-                    writeln!(f, "\n{}", ctx)?;
-                }
-            }
+            } => with_context(
+                Diagnostic::error(format!("Function `{}` not found.", function_name)),
+                context,
+                function_name,
+            ),
             RuntimeErrorCode::IndexOutOfRange {
                 index,
                 collection,
                 location,
+            } => Diagnostic::error(format!(
+                "Index {} is out of range for `{}`.",
+                index, collection
+            ))
+            .with_label(Label::primary(location.span.clone())),
+            RuntimeErrorCode::InvalidArraySize { size, location } => {
+                Diagnostic::error(format!("Invalid array size {}.", size))
+                    .with_label(Label::primary(location.span.clone()))
+            }
+            RuntimeErrorCode::UnknownInfixOperator {
+                operator,
+                context,
+                suggestion,
             } => {
-                writeln!(
-                    f,
-                    "Index {} is out of range for {} at {}",
-                    index,
-                    collection,
-                    location.get_location()
-                )?;
+                let diagnostic = with_context(
+                    Diagnostic::error(format!("Unknown operator: `{}`.", operator)),
+                    context,
+                    operator,
+                );
+
+                match suggestion {
+                    Some(operator) => diagnostic.with_help(format!("did you mean `{}`?", operator)),
+                    None => diagnostic,
+                }
             }
-            RuntimeErrorCode::InvalidArraySize { size, location } => {
-                writeln!(
-                    f,
-                    "Invalid array size {} at {}",
-                    size,
-                    location.get_location()
-                )?;
+            RuntimeErrorCode::OverflowError => Diagnostic::error("Integer overflow.").with_note(
+                format!("integers must be between {} and {}", i64::MIN, i64::MAX),
+            ),
+            RuntimeErrorCode::UnderflowError => Diagnostic::error("Integer underflow.").with_note(
+                format!("integers must be between {} and {}", i64::MIN, i64::MAX),
+            ),
+            RuntimeErrorCode::Custom(message) => Diagnostic::error(message.as_str()),
+            RuntimeErrorCode::ArityMismatch {
+                function,
+                expected,
+                found,
+                missing,
+            } => {
+                let diagnostic = Diagnostic::error(format!(
+                    "`{}` takes {} but {} {} given.",
+                    function,
+                    expected,
+                    found,
+                    if *found == 1 { "was" } else { "were" }
+                ));
+
+                let missing: Vec<String> = missing
+                    .iter()
+                    .map(|parameter| format!("`{}`", parameter))
+                    .collect();
+                if missing.is_empty() {
+                    diagnostic
+                } else {
+                    diagnostic.with_note(format!("missing {}", missing.join(", ")))
+                }
             }
-            RuntimeErrorCode::UnknownInfixOperator { operator, context } => {
-                write!(f, "{}", format!("runtime_error[E{:0>5}]", self.id()).red())?;
-                writeln!(f, ": Unknown infix operator: {}", operator)?;
-
-                if let Some(ctx) = context {
-                    let operator_pos = ctx.find(operator).unwrap();
-
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(f, "{:3} {}\t{}", "10".to_string().blue(), "|".blue(), ctx)?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(operator_pos),
-                        "^".repeat(operator.len()).red()
-                    )?;
+            RuntimeErrorCode::UnknownArgument {
+                function,
+                argument,
+                suggestion,
+            } => {
+                let diagnostic = Diagnostic::error(format!(
+                    "`{}` has no parameter named `{}`.",
+                    function, argument
+                ));
+
+                match suggestion {
+                    Some(parameter) => {
+                        diagnostic.with_help(format!("did you mean `{}`?", parameter))
+                    }
+                    None => diagnostic,
                 }
             }
-            RuntimeErrorCode::Custom(message) => {
-                write!(f, "{}", format!("runtime_error[E{:0>5}]", self.id()).red())?;
-                writeln!(f, " {}", message)?;
+            RuntimeErrorCode::DuplicateArgument { function, argument } => Diagnostic::error(
+                format!("`{}` is passed `{}` more than once.", function, argument),
+            ),
+            RuntimeErrorCode::NotCallable { callee, found } => Diagnostic::error(format!(
+                "`{}` is a value of type `{}`, it cannot be called.",
+                callee, found
+            )),
+            RuntimeErrorCode::CallDepthExceeded { limit } => {
+                Diagnostic::error(format!("The call depth limit of {} was exceeded.", limit))
+                    .with_help(
+                        "make sure the recursion ends, or raise the limit with `--max-call-depth`",
+                    )
             }
-            err => {
-                write!(f, "{}", format!("runtime_error[E{:0>5}]", self.id()).red())?;
-                writeln!(f, ": unimplemented error({:?})", self)?;
+            RuntimeErrorCode::StackExhausted { depth } => {
+                Diagnostic::error(format!("The stack ran out after {} nested calls.", depth))
+                    .with_help(
+                        "make sure the recursion ends, or nest fewer expressions in its body",
+                    )
             }
-        }
-        Ok(())
+            err => Diagnostic::error(format!("unimplemented error({:?})", err)),
+        };
+
+        diagnostic.with_code(self.id())
+    }
+}
+
+/// Runtime errors do not know their location yet, the expression they come from is shown in
+/// a note instead when it tells more than the message.
+fn with_context(diagnostic: Diagnostic, context: &Option<String>, subject: &str) -> Diagnostic {
+    match context {
+        Some(ctx) if ctx != subject => diagnostic.with_note(format!("in `{}`", ctx)),
+        _ => diagnostic,
+    }
+}
+
+impl Display for RuntimeErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
     }
 }
 
@@ -199,6 +325,10 @@ impl RuntimeError {
     pub fn set_source(&mut self, err: RuntimeError) {
         self.source = Some(Box::new(err));
     }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        self.code.to_diagnostic()
+    }
 }
 
 impl Display for RuntimeError {
@@ -219,8 +349,70 @@ impl Error for RuntimeError {
 
 #[cfg(test)]
 mod tests {
-    use crate::{lexer::Lexer, parser::Parser};
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        evaluator::Evaluator, lexer::Lexer, objects::environment::Environment, parser::Parser,
+    };
 
     #[test]
-    fn test_various_errors() {}
+    fn test_various_errors() {
+        const CODE: &str = r#"
+            let a = 1;
+            missing
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+        let evaluated = Evaluator::eval(&program, environment).unwrap();
+
+        colored::control::set_override(false);
+        assert_eq!(
+            evaluated.to_string(),
+            [
+                "error[E03000]: Identifier `missing` not found.",
+                " --> <anonymous>:3:13",
+                "  |",
+                "3 |             missing",
+                "  |             ^^^^^^^",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_stack_trace_rendering() {
+        const CODE: &str = r#"let half = fn(x) { x / two };
+let quarter = fn(x) { half(half(x)) };
+quarter(8)"#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        let environment = Rc::new(RefCell::new(Environment::new(None)));
+        let evaluated = Evaluator::eval(&program, environment).unwrap();
+
+        colored::control::set_override(false);
+        assert_eq!(
+            evaluated.to_string(),
+            [
+                "error[E03000]: Identifier `two` not found.",
+                " --> <anonymous>:1:24",
+                "  |",
+                "1 | let half = fn(x) { x / two };",
+                "  |                        ^^^",
+                "  |",
+                "  = note: stack trace, most recent call first:",
+                "             0: `half`, called at <anonymous>:2:28",
+                "             1: `quarter`, called at <anonymous>:3:1",
+                "",
+            ]
+            .join("\n")
+        );
+    }
 }
@@ -0,0 +1,111 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    log::diagnostic::{Diagnostic, Label, Severity},
+    source::Span,
+};
+
+#[derive(Debug, Clone)]
+pub enum TypeErrorCode {
+    /// `span` is the expression of the wrong type, `reason` the annotation it was checked
+    /// against when there is one. `expected` is written as is, e.g: "`i64` or `bool`".
+    Mismatch {
+        span: Span,
+        expected: String,
+        found: String,
+        reason: Option<Span>,
+    },
+    /// `span` is the expression being called.
+    NotCallable { span: Span, found: String },
+}
+
+impl TypeErrorCode {
+    /// The code of the error, see `log::registry`. Codes are stable: never renumber or reuse one.
+    pub fn id(&self) -> usize {
+        match self {
+            TypeErrorCode::Mismatch { .. } => 2000,
+            TypeErrorCode::NotCallable { .. } => 2001,
+        }
+    }
+
+    pub fn span(&self) -> &Span {
+        match self {
+            TypeErrorCode::Mismatch { span, .. } | TypeErrorCode::NotCallable { span, .. } => span,
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
+            TypeErrorCode::Mismatch {
+                span,
+                expected,
+                found,
+                reason,
+            } => {
+                let diagnostic = Diagnostic::error("Mismatched types.").with_label(
+                    Label::primary(span.clone())
+                        .with_message(format!("expected {}, found `{}`", expected, found)),
+                );
+
+                match reason {
+                    Some(reason) => diagnostic.with_label(
+                        Label::secondary(reason.clone()).with_message("expected because of this"),
+                    ),
+                    None => diagnostic,
+                }
+            }
+            TypeErrorCode::NotCallable { span, found } => {
+                Diagnostic::error(format!("A value of type `{}` cannot be called.", found))
+                    .with_label(Label::primary(span.clone()).with_message("not a function"))
+            }
+        };
+
+        diagnostic.with_code(self.id())
+    }
+}
+
+impl Display for TypeErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeError {
+    pub source: Option<Box<TypeError>>,
+    pub code: TypeErrorCode,
+}
+
+impl TypeError {
+    pub fn new(code: TypeErrorCode) -> Self {
+        Self { source: None, code }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        self.code.to_diagnostic()
+    }
+
+    /// The diagnostic as a warning, for the code that runs anyway: the checker rejects some
+    /// valid programs, e.g: a function calling a global redeclared after it.
+    pub fn to_warning(&self) -> Diagnostic {
+        let mut diagnostic = self.to_diagnostic();
+        diagnostic.severity = Severity::Warning;
+        diagnostic
+    }
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Error for TypeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        if let Some(sauce) = &self.source {
+            Some(sauce)
+        } else {
+            None
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod diagnostic;
+pub mod emitter;
 pub mod error;
+pub mod registry;
+pub mod sarif;
+pub mod suggestion;
 pub mod warning;
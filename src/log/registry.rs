@@ -0,0 +1,498 @@
+use std::ops::Range;
+
+/// Every code ever given to a diagnostic, each stage of the interpreter owns its own range so
+/// that a code always means the same thing. Codes are stable: once released, a code is never
+/// renumbered nor reused for another diagnostic.
+pub const CODES: &[CodeInfo] = &[
+    // lexer
+    explained(
+        100,
+        "unterminated string literal",
+        include_str!("explanations/E00100.md"),
+    ),
+    explained(
+        101,
+        "unterminated block comment",
+        include_str!("explanations/E00101.md"),
+    ),
+    explained(
+        102,
+        "invalid character",
+        include_str!("explanations/E00102.md"),
+    ),
+    explained(
+        103,
+        "malformed number literal",
+        include_str!("explanations/E00103.md"),
+    ),
+    // parser
+    explained(
+        1000,
+        "unexpected token",
+        include_str!("explanations/E01000.md"),
+    ),
+    explained(
+        1001,
+        "expected an expression",
+        include_str!("explanations/E01001.md"),
+    ),
+    explained(
+        1002,
+        "missing if condition",
+        include_str!("explanations/E01002.md"),
+    ),
+    explained(
+        1003,
+        "mismatched delimiter",
+        include_str!("explanations/E01003.md"),
+    ),
+    explained(
+        1004,
+        "missing function return type",
+        include_str!("explanations/E01004.md"),
+    ),
+    explained(
+        1006,
+        "too many errors",
+        include_str!("explanations/E01006.md"),
+    ),
+    unexplained(1007, "unknown parser error"),
+    explained(
+        1008,
+        "misspelled keyword",
+        include_str!("explanations/E01008.md"),
+    ),
+    explained(
+        1009,
+        "unknown attribute",
+        include_str!("explanations/E01009.md"),
+    ),
+    explained(
+        1010,
+        "positional argument after named arguments",
+        include_str!("explanations/E01010.md"),
+    ),
+    // type checker
+    explained(
+        2000,
+        "mismatched types",
+        include_str!("explanations/E02000.md"),
+    ),
+    explained(2001, "not callable", include_str!("explanations/E02001.md")),
+    // runtime
+    explained(
+        3000,
+        "identifier not found",
+        include_str!("explanations/E03000.md"),
+    ),
+    explained(
+        3001,
+        "division by zero",
+        include_str!("explanations/E03001.md"),
+    ),
+    explained(
+        3002,
+        "invalid operation",
+        include_str!("explanations/E03002.md"),
+    ),
+    explained(
+        3003,
+        "type mismatch",
+        include_str!("explanations/E03003.md"),
+    ),
+    explained(
+        3004,
+        "type not found",
+        include_str!("explanations/E03004.md"),
+    ),
+    explained(
+        3005,
+        "function not found",
+        include_str!("explanations/E03005.md"),
+    ),
+    explained(
+        3006,
+        "index out of range",
+        include_str!("explanations/E03006.md"),
+    ),
+    explained(
+        3007,
+        "invalid array size",
+        include_str!("explanations/E03007.md"),
+    ),
+    explained(
+        3008,
+        "unknown infix operator",
+        include_str!("explanations/E03008.md"),
+    ),
+    unexplained(3009, "private access"),
+    unexplained(3010, "inaccessible module"),
+    unexplained(3011, "inaccessible function"),
+    unexplained(3012, "inaccessible field"),
+    unexplained(3013, "inaccessible enum"),
+    unexplained(3014, "constant evaluation error"),
+    unexplained(3015, "invalid constant value"),
+    unexplained(3016, "uninitialized variable"),
+    unexplained(3017, "uninitialized static"),
+    explained(
+        3018,
+        "integer overflow",
+        include_str!("explanations/E03018.md"),
+    ),
+    explained(
+        3019,
+        "integer underflow",
+        include_str!("explanations/E03019.md"),
+    ),
+    unexplained(3020, "unreachable code"),
+    unexplained(3021, "unresolved name"),
+    unexplained(3022, "unexpected token at runtime"),
+    unexplained(3023, "custom runtime error"),
+    explained(
+        3024,
+        "wrong number of arguments",
+        include_str!("explanations/E03024.md"),
+    ),
+    explained(
+        3025,
+        "unknown named argument",
+        include_str!("explanations/E03025.md"),
+    ),
+    explained(
+        3026,
+        "argument passed twice",
+        include_str!("explanations/E03026.md"),
+    ),
+    explained(
+        3027,
+        "value is not callable",
+        include_str!("explanations/E03027.md"),
+    ),
+    explained(
+        3028,
+        "call depth limit exceeded",
+        include_str!("explanations/E03028.md"),
+    ),
+    // warnings
+    explained(
+        4000,
+        "unnecessary parentheses",
+        include_str!("explanations/E04000.md"),
+    ),
+    explained(
+        4001,
+        "empty if expression",
+        include_str!("explanations/E04001.md"),
+    ),
+    explained(
+        4002,
+        "empty if consequence",
+        include_str!("explanations/E04002.md"),
+    ),
+    explained(
+        4003,
+        "empty if alternative",
+        include_str!("explanations/E04003.md"),
+    ),
+    explained(
+        4004,
+        "redundant if branch",
+        include_str!("explanations/E04004.md"),
+    ),
+    explained(
+        4005,
+        "predictable if branch",
+        include_str!("explanations/E04005.md"),
+    ),
+    explained(
+        4006,
+        "empty function",
+        include_str!("explanations/E04006.md"),
+    ),
+    unexplained(4007, "unknown warning"),
+    explained(4008, "unknown lint", include_str!("explanations/E04008.md")),
+    explained(
+        4009,
+        "unused variable",
+        include_str!("explanations/E04009.md"),
+    ),
+    explained(
+        4010,
+        "shadowed binding",
+        include_str!("explanations/E04010.md"),
+    ),
+    explained(
+        4011,
+        "unreachable code",
+        include_str!("explanations/E04011.md"),
+    ),
+    explained(
+        4012,
+        "unnecessary var",
+        include_str!("explanations/E04012.md"),
+    ),
+    explained(
+        4013,
+        "self-assignment",
+        include_str!("explanations/E04013.md"),
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Lexer,
+    Parser,
+    TypeChecker,
+    Runtime,
+    Warning,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 5] = [
+        Stage::Lexer,
+        Stage::Parser,
+        Stage::TypeChecker,
+        Stage::Runtime,
+        Stage::Warning,
+    ];
+
+    pub fn codes(&self) -> Range<usize> {
+        match self {
+            Stage::Lexer => 0..1000,
+            Stage::Parser => 1000..2000,
+            Stage::TypeChecker => 2000..3000,
+            Stage::Runtime => 3000..4000,
+            Stage::Warning => 4000..5000,
+        }
+    }
+
+    pub fn of(code: usize) -> Option<Stage> {
+        Stage::ALL
+            .into_iter()
+            .find(|stage| stage.codes().contains(&code))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeInfo {
+    pub code: usize,
+    pub title: &'static str,
+    /// Long-form explanation in markdown, printed by `eheh explain`.
+    pub explanation: Option<&'static str>,
+}
+
+impl CodeInfo {
+    /// The code as shown to the user, e.g: `E01000`.
+    pub fn name(&self) -> String {
+        format!("E{:0>5}", self.code)
+    }
+
+    pub fn stage(&self) -> Stage {
+        Stage::of(self.code).unwrap()
+    }
+}
+
+const fn explained(code: usize, title: &'static str, explanation: &'static str) -> CodeInfo {
+    CodeInfo {
+        code,
+        title,
+        explanation: Some(explanation),
+    }
+}
+
+const fn unexplained(code: usize, title: &'static str) -> CodeInfo {
+    CodeInfo {
+        code,
+        title,
+        explanation: None,
+    }
+}
+
+pub fn lookup(code: usize) -> Option<&'static CodeInfo> {
+    CODES.iter().find(|info| info.code == code)
+}
+
+/// Parses a code the way users write it: `E01000`, `e1000` or `1000`.
+pub fn parse_code(code: &str) -> Option<usize> {
+    let digits = code
+        .strip_prefix('E')
+        .or(code.strip_prefix('e'))
+        .unwrap_or(code);
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+/// The text printed by `eheh explain`.
+pub fn explain(code: &str) -> Result<String, String> {
+    let info = parse_code(code)
+        .and_then(lookup)
+        .ok_or(format!("`{}` is not a valid error code", code))?;
+
+    match info.explanation {
+        Some(explanation) => Ok(format!(
+            "{}: {}\n\n{}",
+            info.name(),
+            info.title,
+            explanation
+        )),
+        None => Ok(format!(
+            "{}: {}\n\nThis error code has no extended explanation yet.\n",
+            info.name(),
+            info.title
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        evaluator::Evaluator,
+        lexer::Lexer,
+        lint::{self, LintLevels},
+        objects::{environment::Environment, error::Error},
+        parser::Parser,
+        traits::object::ObjectType,
+        type_checker,
+    };
+
+    use super::{explain, parse_code, Stage, CODES};
+
+    /// Codes whose examples use syntax that is not parsed yet or that no stage raises yet, only
+    /// the layout of their explanation is checked.
+    const NOT_RAISED_YET: &[usize] = &[3003, 3004, 3005, 3006, 3007, 4005];
+
+    /// The `eheh` code blocks of an explanation: the erroneous example comes first and the
+    /// fixed one last.
+    fn examples(explanation: &str) -> Vec<String> {
+        explanation
+            .split("```")
+            .skip(1)
+            .step_by(2)
+            .filter_map(|block| block.strip_prefix("eheh\n"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// The codes of every diagnostic raised while running `code`.
+    fn diagnostic_codes(code: &str) -> Vec<usize> {
+        // the examples of E03028 recurse until the call depth limit
+        Evaluator::on_large_stack(|| {
+            let mut lexer = Lexer::new(code);
+            let mut parser = Parser::new(&mut lexer);
+            let program = parser.parse().unwrap();
+            let mut codes: Vec<usize> = parser.errors.iter().map(|err| err.code.id()).collect();
+            if parser.errors.is_empty() {
+                parser.warnings.extend(lint::semantic::check(&program));
+                codes.extend(
+                    type_checker::check(&program)
+                        .errors
+                        .iter()
+                        .map(|err| err.code.id()),
+                );
+            }
+
+            codes.extend(
+                LintLevels::new()
+                    .report(&parser.warnings, &parser.lint_attributes)
+                    .iter()
+                    .filter_map(|diagnostic| diagnostic.code),
+            );
+
+            if !codes.is_empty() {
+                return codes;
+            }
+
+            let environment = Rc::new(RefCell::new(Environment::new(None)));
+            if let Ok(evaluated) = Evaluator::eval(&program, environment) {
+                if evaluated.t() == ObjectType::Error {
+                    let error = evaluated.as_any_ref().downcast_ref::<Error>().unwrap();
+                    codes.extend(error.to_diagnostic().code);
+                }
+            }
+
+            codes
+        })
+    }
+
+    #[test]
+    fn test_codes_are_unique_and_in_range() {
+        for (index, info) in CODES.iter().enumerate() {
+            assert!(
+                Stage::of(info.code).is_some(),
+                "{} has no stage",
+                info.name()
+            );
+
+            if index > 0 {
+                assert!(
+                    CODES[index - 1].code < info.code,
+                    "{} is out of order",
+                    info.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_code() {
+        assert_eq!(parse_code("E01000"), Some(1000));
+        assert_eq!(parse_code("e1000"), Some(1000));
+        assert_eq!(parse_code("100"), Some(100));
+        assert_eq!(parse_code("E"), None);
+        assert_eq!(parse_code("E-1"), None);
+        assert_eq!(parse_code("W01000"), None);
+    }
+
+    #[test]
+    fn test_explain() {
+        assert!(explain("E01000")
+            .unwrap()
+            .starts_with("E01000: unexpected token\n\n"));
+        assert!(explain("E01007")
+            .unwrap()
+            .contains("no extended explanation"));
+        assert!(explain("E09999").is_err());
+    }
+
+    #[test]
+    fn test_explanation_examples() {
+        for info in CODES {
+            let Some(explanation) = info.explanation else {
+                continue;
+            };
+
+            let examples = examples(explanation);
+            assert!(
+                examples.len() >= 2,
+                "{} needs an erroneous and a fixed example",
+                info.name()
+            );
+
+            if NOT_RAISED_YET.contains(&info.code) {
+                continue;
+            }
+
+            let erroneous = diagnostic_codes(&examples[0]);
+            assert!(
+                erroneous.contains(&info.code),
+                "the erroneous example of {} raised {:?}",
+                info.name(),
+                erroneous
+            );
+
+            let fixed = diagnostic_codes(examples.last().unwrap());
+            assert!(
+                fixed.is_empty(),
+                "the fixed example of {} raised {:?}",
+                info.name(),
+                fixed
+            );
+        }
+    }
+}
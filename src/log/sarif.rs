@@ -0,0 +1,211 @@
+use crate::{
+    json::Json,
+    log::diagnostic::{Diagnostic, Label, Severity, Suggestion},
+    source::Span,
+};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/adia-dev/eheh-lang";
+
+/// Exports diagnostics as a SARIF 2.1.0 log with a single run, the format understood by most
+/// code scanning tools.
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> Json {
+    let mut rules: Vec<String> = diagnostics
+        .iter()
+        .filter_map(Diagnostic::code_name)
+        .collect();
+    rules.sort();
+    rules.dedup();
+
+    let driver = Json::object()
+        .with("name", "eheh")
+        .with("version", env!("CARGO_PKG_VERSION"))
+        .with("informationUri", INFORMATION_URI)
+        .with(
+            "rules",
+            rules
+                .into_iter()
+                .map(|id| Json::object().with("id", id))
+                .collect::<Vec<Json>>(),
+        );
+
+    let run = Json::object()
+        .with("tool", Json::object().with("driver", driver))
+        .with("columnKind", "unicodeCodePoints")
+        .with(
+            "results",
+            diagnostics.iter().map(result).collect::<Vec<Json>>(),
+        );
+
+    Json::object()
+        .with("$schema", SCHEMA)
+        .with("version", "2.1.0")
+        .with("runs", vec![run])
+}
+
+fn result(diagnostic: &Diagnostic) -> Json {
+    let level = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    // SARIF has no notion of notes and helps, they are appended to the message instead.
+    let mut text = diagnostic.message.clone();
+    for note in &diagnostic.notes {
+        text.push_str(&format!("\nnote: {}", note));
+    }
+    let suggestions = diagnostic.suggestions.iter().map(|s| &s.message);
+    for help in diagnostic.helps.iter().chain(suggestions) {
+        text.push_str(&format!("\nhelp: {}", help));
+    }
+
+    let primary = diagnostic
+        .labels
+        .iter()
+        .position(|label| label.primary)
+        .or((!diagnostic.labels.is_empty()).then_some(0));
+
+    let locations: Vec<Json> = primary
+        .map(|index| location(&diagnostic.labels[index]))
+        .into_iter()
+        .collect();
+    let related_locations: Vec<Json> = diagnostic
+        .labels
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| Some(*index) != primary)
+        .map(|(_, label)| location(label))
+        .collect();
+
+    Json::object()
+        .with("ruleId", diagnostic.code_name())
+        .with("level", level)
+        .with("message", Json::object().with("text", text))
+        .with("locations", locations)
+        .with("relatedLocations", related_locations)
+        .with(
+            "fixes",
+            diagnostic
+                .suggestions
+                .iter()
+                .map(fix)
+                .collect::<Vec<Json>>(),
+        )
+}
+
+/// The edits of a suggestion, grouped by the file they change.
+fn fix(suggestion: &Suggestion) -> Json {
+    let mut files: Vec<&str> = Vec::new();
+    for edit in &suggestion.edits {
+        if !files.contains(&edit.span.file.name.as_str()) {
+            files.push(&edit.span.file.name);
+        }
+    }
+
+    let changes: Vec<Json> = files
+        .into_iter()
+        .map(|file| {
+            let replacements: Vec<Json> = suggestion
+                .edits
+                .iter()
+                .filter(|edit| edit.span.file.name == file)
+                .map(|edit| {
+                    Json::object()
+                        .with("deletedRegion", region(&edit.span))
+                        .with(
+                            "insertedContent",
+                            Json::object().with("text", edit.replacement.as_str()),
+                        )
+                })
+                .collect();
+
+            Json::object()
+                .with("artifactLocation", Json::object().with("uri", file))
+                .with("replacements", replacements)
+        })
+        .collect();
+
+    Json::object()
+        .with(
+            "description",
+            Json::object().with("text", suggestion.message.as_str()),
+        )
+        .with("artifactChanges", changes)
+}
+
+fn location(label: &Label) -> Json {
+    let location = Json::object().with(
+        "physicalLocation",
+        Json::object()
+            .with(
+                "artifactLocation",
+                Json::object().with("uri", label.span.file.name.as_str()),
+            )
+            .with("region", region(&label.span)),
+    );
+
+    match &label.message {
+        Some(message) => location.with("message", Json::object().with("text", message.as_str())),
+        None => location,
+    }
+}
+
+/// Lines and columns start at 1, the end column is exclusive.
+fn region(span: &Span) -> Json {
+    let (start_line, start_column) = span.file.line_column(span.start);
+    let (end_line, end_column) = span.file.line_column(span.end);
+
+    Json::object()
+        .with("startLine", start_line)
+        .with("startColumn", start_column)
+        .with("endLine", end_line)
+        .with("endColumn", end_column)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::Parser};
+
+    use super::to_sarif;
+
+    #[test]
+    fn test_sarif_export() {
+        const CODE: &str = r#"let b = (a + 2;"#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        parser.parse().unwrap();
+
+        let diagnostics: Vec<_> = parser.errors.iter().map(|e| e.to_diagnostic()).collect();
+        let sarif = to_sarif(&diagnostics);
+
+        assert_eq!(sarif.get("version").unwrap().as_str(), Some("2.1.0"));
+
+        let run = &sarif.get("runs").unwrap().as_array().unwrap()[0];
+        let results = run.get("results").unwrap().as_array().unwrap();
+        assert_eq!(results.len(), diagnostics.len());
+
+        let result = &results[0];
+        assert_eq!(result.get("ruleId").unwrap().as_str(), Some("E01000"));
+        assert_eq!(result.get("level").unwrap().as_str(), Some("error"));
+
+        let location = &result.get("locations").unwrap().as_array().unwrap()[0];
+        let physical_location = location.get("physicalLocation").unwrap();
+        assert_eq!(
+            physical_location
+                .get("artifactLocation")
+                .unwrap()
+                .get("uri")
+                .unwrap()
+                .as_str(),
+            Some("<anonymous>")
+        );
+
+        let rules = run
+            .get("tool")
+            .and_then(|tool| tool.get("driver"))
+            .and_then(|driver| driver.get("rules"))
+            .unwrap();
+        assert_eq!(rules.as_array().unwrap().len(), 1);
+    }
+}
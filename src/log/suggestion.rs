@@ -0,0 +1,76 @@
+/// Number of edits (insertions, deletions, substitutions and transpositions of two adjacent
+/// characters) needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the distance between the first i characters of `a` and the first j
+    // characters of `b`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in distances[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            distances[i][j] = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + substitution);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distances[i][j] = distances[i][j].min(distances[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// The candidate closest to `name`, if one is close enough to be a likely typo. Ties go to the
+/// candidate that comes first.
+pub fn find_best_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = name.chars().count().max(3) / 3;
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, find_best_match};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("return", "return"), 0);
+        assert_eq!(edit_distance("retrun", "return"), 1);
+        assert_eq!(edit_distance("fun", "fn"), 1);
+        assert_eq!(edit_distance("lett", "let"), 1);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_find_best_match() {
+        let candidates = ["total", "count", "counter"];
+
+        assert_eq!(find_best_match("totl", candidates), Some("total"));
+        assert_eq!(find_best_match("conut", candidates), Some("count"));
+        assert_eq!(find_best_match("countr", candidates), Some("count"));
+        assert_eq!(find_best_match("x", candidates), None);
+        assert_eq!(find_best_match("count", candidates), None);
+    }
+}
@@ -1,235 +1,313 @@
-use std::{collections::VecDeque, error::Error, fmt::Display};
+use std::{fmt::Display, rc::Rc};
 
-use colored::Colorize;
+use crate::{
+    log::diagnostic::{Applicability, Diagnostic, Label, Suggestion, TextEdit},
+    source::Span,
+    token::Token,
+};
 
-use crate::token::{token_type::TokenType, Token};
-
-#[derive(Debug)]
-#[repr(usize)]
+#[derive(Debug, Clone)]
 pub enum ParserWarning {
+    /// `token` is the opening parenthesis and `closing` the matching one.
     UnnecessaryParentheses {
         token: Token,
-        context: Vec<String>,
-    } = 1_000,
+        closing: Token,
+    },
     EmptyIfExpression {
         token: Token,
-        context: Vec<String>,
     },
+    /// `swap` negates the condition and replaces the branches with the alternative, it is empty
+    /// when there is no alternative or when the condition cannot be negated in place.
     EmptyIfConsequenceBranch {
         token: Token,
-        context: Vec<String>,
         has_alternative: bool,
+        swap: Vec<TextEdit>,
     },
+    /// `else_branch` covers the `else` keyword and the empty block following it, it is not set
+    /// when the block holds comments.
     EmptyIfAlternativeBranch {
         token: Token,
-        context: Vec<String>,
+        else_branch: Option<Span>,
     },
+    /// `if c { true } else { false }`, `branch` is the value of the consequence and `replacement`
+    /// replaces the whole if expression with the condition, negated when `branch` is false.
     RedundantIfBranch {
         token: Token,
-        context: Vec<String>,
         branch: bool,
+        replacement: TextEdit,
     },
     PredictableIfBranch {
         token: Token,
-        context: Vec<String>,
         is_true_branch: bool,
     },
     EmptyFunction {
         token: Token,
-        context: Vec<String>,
+    },
+    /// `suggestion` is a lint with a similar name.
+    UnknownLint {
+        token: Token,
+        suggestion: Option<String>,
+    },
+    /// `token` is the name of the binding, `passed_by_name` whether a call of the program has
+    /// a named argument called like the parameter: prefixing it could break the call.
+    /// `redeclared` whether another binding of the function or of an outer one has the same
+    /// name: a function called after both are declared reads the last one, so the binding
+    /// might be used after all.
+    UnusedVariable {
+        token: Token,
+        is_parameter: bool,
+        passed_by_name: bool,
+        redeclared: bool,
+    },
+    /// `previous` is the name of the binding of the outer scope.
+    ShadowedBinding {
+        token: Token,
+        previous: Span,
+    },
+    /// `token` is the `return` and `unreachable` covers every statement following it up to the
+    /// end of the block.
+    UnreachableCode {
+        token: Token,
+        unreachable: Span,
+    },
+    /// `token` is the `var` keyword.
+    UnnecessaryVar {
+        token: Token,
+        name: String,
+    },
+    /// `token` is the assigned identifier and `assignment` covers the whole assignment.
+    SelfAssignment {
+        token: Token,
+        assignment: Span,
     },
     Unknown,
 }
 
 impl ParserWarning {
-    fn id(&self) -> usize {
-        unsafe { *(self as *const Self as *const usize) }
+    /// The code of the warning, see `log::registry`. Codes are stable: never renumber or reuse
+    /// one.
+    pub fn id(&self) -> usize {
+        match self {
+            ParserWarning::UnnecessaryParentheses { .. } => 4000,
+            ParserWarning::EmptyIfExpression { .. } => 4001,
+            ParserWarning::EmptyIfConsequenceBranch { .. } => 4002,
+            ParserWarning::EmptyIfAlternativeBranch { .. } => 4003,
+            ParserWarning::RedundantIfBranch { .. } => 4004,
+            ParserWarning::PredictableIfBranch { .. } => 4005,
+            ParserWarning::EmptyFunction { .. } => 4006,
+            ParserWarning::Unknown => 4007,
+            ParserWarning::UnknownLint { .. } => 4008,
+            ParserWarning::UnusedVariable { .. } => 4009,
+            ParserWarning::ShadowedBinding { .. } => 4010,
+            ParserWarning::UnreachableCode { .. } => 4011,
+            ParserWarning::UnnecessaryVar { .. } => 4012,
+            ParserWarning::SelfAssignment { .. } => 4013,
+        }
     }
-}
 
-impl Display for ParserWarning {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    pub fn token(&self) -> Option<&Token> {
         match self {
-            ParserWarning::UnnecessaryParentheses { token, context } => {
-                writeln!(
-                    f,
-                    "{}: Unnecessary parentheses around {}",
-                    format!("warning[E{:0>5}]", self.id()).yellow(),
-                    token.literal
-                )?;
+            ParserWarning::UnnecessaryParentheses { token, .. }
+            | ParserWarning::EmptyIfExpression { token }
+            | ParserWarning::EmptyIfConsequenceBranch { token, .. }
+            | ParserWarning::EmptyIfAlternativeBranch { token, .. }
+            | ParserWarning::RedundantIfBranch { token, .. }
+            | ParserWarning::PredictableIfBranch { token, .. }
+            | ParserWarning::EmptyFunction { token }
+            | ParserWarning::UnknownLint { token, .. }
+            | ParserWarning::UnusedVariable { token, .. }
+            | ParserWarning::ShadowedBinding { token, .. }
+            | ParserWarning::UnreachableCode { token, .. }
+            | ParserWarning::UnnecessaryVar { token, .. }
+            | ParserWarning::SelfAssignment { token, .. } => Some(token),
+            ParserWarning::Unknown => None,
+        }
+    }
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
-                }
-                writeln!(f, "")
-            }
-            ParserWarning::Unknown => todo!(),
-            ParserWarning::EmptyIfExpression { token, context } => {
-                writeln!(
-                    f,
-                    "{}: The if expression has no meaningful consequence or alternative. Consider removing it.",
-                    format!("warning[E{:0>5}]", self.id()).yellow()
-                )?;
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let label = self.token().map(|token| Label::primary(token.span.clone()));
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
-                }
-                writeln!(f, "")
-            }
+        let diagnostic = match self {
+            ParserWarning::UnnecessaryParentheses { token, closing } => Diagnostic::warning(
+                "Unnecessary parentheses around the condition of the if expression.",
+            )
+            .with_label(Label::secondary(closing.span.clone()))
+            .with_suggestion(Suggestion::new(
+                "remove the parentheses around the condition",
+                vec![remove_delimiter(&token.span), remove_delimiter(&closing.span)],
+                Applicability::MachineApplicable,
+            )),
+            ParserWarning::EmptyIfExpression { .. } => Diagnostic::warning(
+                "The if expression has no meaningful consequence or alternative. Consider removing it.",
+            ),
             ParserWarning::EmptyIfConsequenceBranch {
-                token,
-                context,
                 has_alternative,
+                swap,
+                ..
             } => {
-                if *has_alternative {
-                    writeln!(
-                        f,
-                        "{}: The consequence of the if expression is empty. Consider swapping the alternative and consequence branches of the if expression.",
-                        format!("warning[E{:0>5}]", self.id()).yellow()
-                        )?;
-                } else {
-                    writeln!(
-                        f,
-                        "{}: The if expression has no meaningful consequence. Consider removing it.",
-                        format!("warning[E{:0>5}]", self.id()).yellow()
-                        )?;
-                }
+                let help = "swap the alternative and consequence branches and negate the condition";
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
+                if !swap.is_empty() {
+                    Diagnostic::warning("The consequence of the if expression is empty.")
+                        .with_suggestion(Suggestion::new(
+                            help,
+                            swap.clone(),
+                            Applicability::MachineApplicable,
+                        ))
+                } else if *has_alternative {
+                    Diagnostic::warning("The consequence of the if expression is empty.")
+                        .with_help(help)
+                } else {
+                    Diagnostic::warning(
+                        "The if expression has no meaningful consequence. Consider removing it.",
+                    )
                 }
-                writeln!(f, "")
             }
-            ParserWarning::EmptyIfAlternativeBranch { token, context } => {
-                writeln!(
-                    f,
-                    "{}: The alternative of the if expression is empty, consider removing the alternative branch.",
-                    format!("warning[E{:0>5}]", self.id()).yellow()
-                )?;
+            ParserWarning::EmptyIfAlternativeBranch { else_branch, .. } => {
+                let diagnostic =
+                    Diagnostic::warning("The alternative of the if expression is empty.");
+                let help = "remove the `else` branch";
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
+                match else_branch {
+                    Some(span) => diagnostic.with_suggestion(Suggestion::new(
+                        help,
+                        vec![TextEdit::delete(span.clone())],
+                        Applicability::MachineApplicable,
+                    )),
+                    None => diagnostic.with_help(help),
                 }
-                writeln!(f, "")
             }
-            ParserWarning::EmptyFunction { token, context } => {
-                writeln!(
-                    f,
-                    "{}: The function has an empty body. Consider removing it if unnecessary.",
-                    format!("warning[E{:0>5}]", self.id()).yellow()
-                )?;
+            ParserWarning::RedundantIfBranch {
+                branch,
+                replacement,
+                ..
+            } => Diagnostic::warning(if *branch {
+                "The if expression evaluates to its condition."
+            } else {
+                "The if expression evaluates to the negation of its condition."
+            })
+            .with_suggestion(Suggestion::new(
+                if *branch {
+                    "replace the if expression with its condition"
+                } else {
+                    "replace the if expression with its negated condition"
+                },
+                vec![replacement.clone()],
+                Applicability::MachineApplicable,
+            )),
+            ParserWarning::PredictableIfBranch { is_true_branch, .. } => Diagnostic::warning(format!(
+                "The if expression always selects the {} branch.",
+                if *is_true_branch { "true" } else { "false" }
+            ))
+            .with_help("remove the other branch and inline the if expression"),
+            ParserWarning::EmptyFunction { .. } => {
+                Diagnostic::warning("The function has an empty body. Consider removing it if unnecessary.")
+            }
+            ParserWarning::UnknownLint { token, suggestion } => {
+                let diagnostic =
+                    Diagnostic::warning(format!("Unknown lint `{}`.", token.literal()));
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
+                match suggestion {
+                    Some(suggestion) => diagnostic.with_suggestion(Suggestion::new(
+                        format!("a lint with a similar name exists: `{}`", suggestion),
+                        vec![TextEdit::new(token.span.clone(), suggestion)],
+                        Applicability::MaybeIncorrect,
+                    )),
+                    None => diagnostic,
                 }
-                writeln!(f, "")
             }
-            ParserWarning::RedundantIfBranch {
+            ParserWarning::UnusedVariable {
                 token,
-                context,
-                branch,
-            } => todo!(),
-            ParserWarning::PredictableIfBranch {
-                token,
-                context,
-                is_true_branch,
-            } => {
-                writeln!(
-                    f,
-                    "{}: The if expression always selects the {} branch, consider removing the other branch and inlining the if expression.",
-                    format!("warning[E{:0>5}]", self.id()).yellow(),
-                    if *is_true_branch {"true"} else {"false"}
-                )?;
+                is_parameter,
+                passed_by_name,
+                redeclared,
+            } => Diagnostic::warning(format!(
+                "Unused {} `{}`.",
+                if *is_parameter { "parameter" } else { "variable" },
+                token.literal()
+            ))
+            .with_suggestion(Suggestion::new(
+                "if this is intentional, prefix it with an underscore",
+                vec![TextEdit::new(
+                    Span::new(Rc::clone(&token.span.file), token.span.start, token.span.start),
+                    "_",
+                )],
+                if *passed_by_name || *redeclared {
+                    Applicability::MaybeIncorrect
+                } else {
+                    Applicability::MachineApplicable
+                },
+            )),
+            ParserWarning::ShadowedBinding { token, previous } => {
+                Diagnostic::warning(format!(
+                    "`{}` shadows a binding of an outer scope.",
+                    token.literal()
+                ))
+                .with_label(
+                    Label::secondary(previous.clone())
+                        .with_message(format!("`{}` is first bound here", token.literal())),
+                )
+                .with_help("consider renaming one of the bindings")
+            }
+            ParserWarning::UnreachableCode { token, unreachable } => {
+                Diagnostic::warning("Unreachable code.")
+                .with_label(Label::primary(unreachable.clone()).with_message("unreachable code"))
+                .with_label(
+                    Label::secondary(token.span.clone())
+                        .with_message("any code following this `return` is unreachable"),
+                )
+            }
+            ParserWarning::UnnecessaryVar { token, name } => Diagnostic::warning(format!(
+                "`{}` is declared with `var` but never reassigned.",
+                name
+            ))
+            .with_suggestion(Suggestion::new(
+                "declare it with `let`",
+                vec![TextEdit::new(token.span.clone(), "let")],
+                Applicability::MachineApplicable,
+            )),
+            ParserWarning::SelfAssignment { token, assignment } => {
+                Diagnostic::warning(format!("`{}` is assigned to itself.", token.literal()))
+                    .with_label(Label::primary(assignment.clone()))
+                    .with_help("remove the assignment")
+            }
+            ParserWarning::Unknown => Diagnostic::warning("An unknown warning was raised."),
+        };
 
-                if !context.is_empty() {
-                    writeln!(f, "    {}", "|".blue())?;
-                    writeln!(
-                        f,
-                        "{:3} {}\t{}",
-                        token.line.to_string().blue(),
-                        "|".blue(),
-                        context.join(format!("\n{:3} {}", " ", "|".blue()).as_str())
-                    )?;
-                    write!(
-                        f,
-                        "    {}\t{}{} ",
-                        "|".blue(),
-                        " ".repeat(token.column - 1),
-                        "^".repeat(token.literal.len()).yellow()
-                    )?;
-                }
-                writeln!(f, "")
+        // variants that label their own primary span are not labeled again
+        let diagnostic = match label {
+            Some(label) if !diagnostic.labels.iter().any(|label| label.primary) => {
+                diagnostic.with_label(label)
             }
-        }
+            _ => diagnostic,
+        };
+
+        diagnostic.with_code(self.id())
+    }
+}
+
+/// Deletes a parenthesis, a space is left in its place when it is glued to the surrounding
+/// code so that `if(a){` becomes `if a {`.
+fn remove_delimiter(span: &Span) -> TextEdit {
+    let src = &span.file.src;
+    let glued_before = src[..span.start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_whitespace());
+    let glued_after = src[span.end..]
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_whitespace());
+
+    if glued_before && glued_after {
+        TextEdit::new(span.clone(), " ")
+    } else {
+        TextEdit::delete(span.clone())
+    }
+}
+
+impl Display for ParserWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
     }
 }
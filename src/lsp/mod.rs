@@ -0,0 +1,577 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    rc::Rc,
+};
+
+use crate::{
+    ast::{
+        expressions::function_literal::FunctionLiteral,
+        statements::{
+            attributed_statement::AttributedStatement, declare_statement::DeclareStatement,
+            expression_statement::ExpressionStatement,
+        },
+    },
+    json::Json,
+    lexer::Lexer,
+    lint::{self, LintLevels},
+    log::diagnostic::{Diagnostic, Severity},
+    parser::Parser,
+    program::Program,
+    resolver::{self, BindingKind},
+    source::{SourceFile, Span},
+    token::token_type::{KeywordTokenType, TokenType},
+    traits::node::Node,
+    type_checker::{self, Binding, Type},
+};
+
+// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+const REQUEST_FAILED: i64 = -32803;
+
+const SYMBOL_FUNCTION: i64 = 12;
+const SYMBOL_VARIABLE: i64 = 13;
+const SYMBOL_CONSTANT: i64 = 14;
+
+/// Serves the requests of an editor read from `input` until it asks the server to exit, the
+/// responses and notifications are written to `output`. Returns whether the editor asked the
+/// server to shut down before exiting, as the protocol requires.
+pub fn serve(
+    mut input: impl BufRead,
+    output: impl Write,
+    lint_levels: LintLevels,
+) -> io::Result<bool> {
+    let mut server = Server {
+        output,
+        lint_levels,
+        documents: HashMap::new(),
+        initialized: false,
+        shutdown: false,
+    };
+
+    while let Some(message) = read_message(&mut input)? {
+        let message = match Json::parse(&message) {
+            Ok(message) => message,
+            Err(err) => {
+                server.respond(&Json::Null, Err((PARSE_ERROR, err)))?;
+                continue;
+            }
+        };
+
+        if !server.handle(&message)? {
+            break;
+        }
+    }
+
+    Ok(server.shutdown)
+}
+
+struct Server<W: Write> {
+    output: W,
+    lint_levels: LintLevels,
+    /// The open documents by uri.
+    documents: HashMap<String, Rc<SourceFile>>,
+    initialized: bool,
+    shutdown: bool,
+}
+
+impl<W: Write> Server<W> {
+    /// Returns `false` once the editor asked the server to exit.
+    fn handle(&mut self, message: &Json) -> io::Result<bool> {
+        let Some(method) = message.get("method").and_then(Json::as_str) else {
+            // a response to a request of the server, none are sent
+            return Ok(true);
+        };
+        let params = message.get("params").unwrap_or(&Json::Null);
+
+        let Some(id) = message.get("id") else {
+            match method {
+                "exit" => return Ok(false),
+                _ if !self.initialized => {}
+                "textDocument/didOpen" => {
+                    let document = params.get("textDocument").unwrap_or(&Json::Null);
+                    if let (Some(uri), Some(text)) = (
+                        document.get("uri").and_then(Json::as_str),
+                        document.get("text").and_then(Json::as_str),
+                    ) {
+                        self.open(uri, text)?;
+                    }
+                }
+                "textDocument/didChange" => {
+                    // the whole text is sent on every change, see the `initialize` response
+                    let text = params
+                        .get("contentChanges")
+                        .and_then(Json::as_array)
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change.get("text"))
+                        .and_then(Json::as_str);
+                    if let (Some(uri), Some(text)) = (uri(params), text) {
+                        self.open(uri, text)?;
+                    }
+                }
+                "textDocument/didClose" => {
+                    if let Some(uri) = uri(params) {
+                        self.documents.remove(uri);
+                        self.publish_diagnostics(uri, Vec::new())?;
+                    }
+                }
+                _ => {}
+            }
+
+            return Ok(true);
+        };
+
+        let result = match method {
+            "initialize" => {
+                self.initialized = true;
+                Ok(Json::object()
+                    .with(
+                        "capabilities",
+                        Json::object()
+                            .with(
+                                "textDocumentSync",
+                                Json::object().with("openClose", true).with("change", 1i64),
+                            )
+                            .with("hoverProvider", true)
+                            .with("documentSymbolProvider", true)
+                            .with("definitionProvider", true)
+                            .with("referencesProvider", true)
+                            .with("renameProvider", true),
+                    )
+                    .with(
+                        "serverInfo",
+                        Json::object()
+                            .with("name", "eheh")
+                            .with("version", env!("CARGO_PKG_VERSION")),
+                    ))
+            }
+            _ if !self.initialized => Err((
+                SERVER_NOT_INITIALIZED,
+                "the server is not initialized".to_string(),
+            )),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Json::Null)
+            }
+            "textDocument/hover" => Ok(self.hover(params)),
+            "textDocument/documentSymbol" => Ok(self.document_symbols(params)),
+            "textDocument/definition" => Ok(self.definition(params)),
+            "textDocument/references" => Ok(self.references(params)),
+            "textDocument/rename" => self.rename(params),
+            method => Err((METHOD_NOT_FOUND, format!("unknown method `{}`", method))),
+        };
+
+        self.respond(id, result)?;
+        Ok(true)
+    }
+
+    fn open(&mut self, uri: &str, text: &str) -> io::Result<()> {
+        let file = Rc::new(SourceFile::new(self.documents.len(), uri, text));
+        self.documents.insert(uri.to_string(), Rc::clone(&file));
+
+        let diagnostics = self
+            .diagnostics(&file)
+            .iter()
+            .map(|diagnostic| diagnostic_to_json(uri, diagnostic))
+            .collect();
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    /// The diagnostics `eheh check` would report for the file.
+    fn diagnostics(&self, file: &Rc<SourceFile>) -> Vec<Diagnostic> {
+        let mut lexer = Lexer::from_source(Rc::clone(file));
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        let mut diagnostics: Vec<Diagnostic> = parser
+            .errors
+            .iter()
+            .map(|error| error.to_diagnostic())
+            .collect();
+        if diagnostics.is_empty() {
+            parser.warnings.extend(lint::semantic::check(&program));
+            diagnostics.extend(
+                type_checker::check(&program)
+                    .errors
+                    .iter()
+                    .map(|error| error.to_diagnostic()),
+            );
+        }
+
+        diagnostics.extend(
+            self.lint_levels
+                .report(&parser.warnings, &parser.lint_attributes),
+        );
+        diagnostics
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Json>) -> io::Result<()> {
+        let notification = Json::object()
+            .with("jsonrpc", "2.0")
+            .with("method", "textDocument/publishDiagnostics")
+            .with(
+                "params",
+                Json::object()
+                    .with("uri", uri)
+                    .with("diagnostics", diagnostics),
+            );
+
+        write_message(&mut self.output, &notification)
+    }
+
+    /// The binding of the identifier under the cursor, e.g: `let x: i32 = 5`.
+    fn hover(&self, params: &Json) -> Json {
+        let Some((_, program, offset)) = self.cursor(params) else {
+            return Json::Null;
+        };
+
+        let resolution = resolver::resolve(&program);
+        let check = type_checker::check(&program);
+        let Some((reference, binding)) = resolution.reference_at(offset).and_then(|reference| {
+            let definition = &resolution.definitions[reference.definition?];
+            Some((reference, check.binding(&definition.name.span)?))
+        }) else {
+            return Json::Null;
+        };
+
+        let mut signature = match binding.kind {
+            BindingKind::Let => "let",
+            BindingKind::Const => "const",
+            BindingKind::Var => "var",
+            BindingKind::Parameter => "(parameter)",
+        }
+        .to_string();
+        signature.push(' ');
+        signature.push_str(binding.name.literal());
+        if binding.annotation.is_some() || binding.t != Type::Unknown {
+            signature.push_str(&format!(": {}", binding.type_name()));
+        }
+        if let Some(value) = &binding.value {
+            signature.push_str(&format!(" = {}", value));
+        }
+
+        Json::object()
+            .with(
+                "contents",
+                Json::object()
+                    .with("kind", "markdown")
+                    .with("value", format!("```eheh\n{}\n```", signature)),
+            )
+            .with("range", range(&reference.span))
+    }
+
+    /// The declaration of the binding under the cursor.
+    fn definition(&self, params: &Json) -> Json {
+        let Some((file, program, offset)) = self.cursor(params) else {
+            return Json::Null;
+        };
+
+        match resolver::resolve(&program).definition_at(offset) {
+            Some(definition) => location(&file.name, &definition.name.span),
+            None => Json::Null,
+        }
+    }
+
+    /// The uses of the binding under the cursor, along with its declaration when the editor asks
+    /// for it.
+    fn references(&self, params: &Json) -> Json {
+        let Some((file, program, offset)) = self.cursor(params) else {
+            return Json::Null;
+        };
+        let include_declaration = params
+            .get("context")
+            .and_then(|context| context.get("includeDeclaration"))
+            .and_then(Json::as_bool)
+            .unwrap_or(true);
+
+        let resolution = resolver::resolve(&program);
+        let declaration = resolution
+            .definition_at(offset)
+            .map(|definition| &definition.name.span);
+
+        let locations: Vec<Json> = resolution
+            .references_at(offset)
+            .into_iter()
+            .filter(|&span| include_declaration || Some(span) != declaration)
+            .map(|span| location(&file.name, span))
+            .collect();
+        Json::Array(locations)
+    }
+
+    fn rename(&self, params: &Json) -> Result<Json, (i64, String)> {
+        let Some((file, _, offset)) = self.cursor(params) else {
+            return Ok(Json::Null);
+        };
+        let new_name = params
+            .get("newName")
+            .and_then(Json::as_str)
+            .unwrap_or_default();
+
+        let edits: Vec<Json> = resolver::rename(&file, offset, new_name)
+            .map_err(|err| (REQUEST_FAILED, err))?
+            .iter()
+            .map(|edit| {
+                Json::object()
+                    .with("range", range(&edit.span))
+                    .with("newText", edit.replacement.as_str())
+            })
+            .collect();
+
+        Ok(Json::object().with("changes", Json::object().with(&file.name, edits)))
+    }
+
+    /// The top-level `let`, `const` and `var` declarations and named functions.
+    fn document_symbols(&self, params: &Json) -> Json {
+        let Some((file, program)) = self.document(params) else {
+            return Json::Null;
+        };
+        let check = type_checker::check(&program);
+
+        let mut symbols = Vec::new();
+        for statement in &program.statements {
+            let span = statement.span();
+            let statement = match statement.as_any().downcast_ref::<AttributedStatement>() {
+                Some(attributed) => &attributed.statement,
+                None => statement,
+            };
+
+            if let Some(declare) = statement.as_any().downcast_ref::<DeclareStatement>() {
+                let name = &declare.name.token.span;
+                let is_function = declare
+                    .value
+                    .as_ref()
+                    .is_some_and(|value| value.as_any().is::<FunctionLiteral>());
+                let kind = match declare.token.t {
+                    _ if is_function => SYMBOL_FUNCTION,
+                    TokenType::KEYWORD(KeywordTokenType::CONST) => SYMBOL_CONSTANT,
+                    _ => SYMBOL_VARIABLE,
+                };
+                let detail = check.binding(name).map(Binding::type_name);
+
+                symbols.push(symbol(&declare.name.value, detail, kind, &span, name));
+            } else if let Some(function) = statement
+                .as_any()
+                .downcast_ref::<ExpressionStatement>()
+                .and_then(|statement| {
+                    statement
+                        .expression
+                        .as_any()
+                        .downcast_ref::<FunctionLiteral>()
+                })
+            {
+                let Some(name) = &function.name else {
+                    continue;
+                };
+                let signature = &file.src[function.token.span.start..function.body.span().start];
+
+                symbols.push(symbol(
+                    &name.value,
+                    Some(signature.trim_end().to_string()),
+                    SYMBOL_FUNCTION,
+                    &span,
+                    &name.token.span,
+                ));
+            }
+        }
+
+        Json::Array(symbols)
+    }
+
+    /// The open document named in the parameters of a request, and its program.
+    fn document(&self, params: &Json) -> Option<(Rc<SourceFile>, Program)> {
+        let file = params
+            .get("textDocument")
+            .and_then(uri)
+            .and_then(|uri| self.documents.get(uri))?;
+
+        let mut lexer = Lexer::from_source(Rc::clone(file));
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        Some((Rc::clone(file), program))
+    }
+
+    /// The open document named in the parameters of a request, its program and the offset of
+    /// the position of the request.
+    fn cursor(&self, params: &Json) -> Option<(Rc<SourceFile>, Program, usize)> {
+        let (file, program) = self.document(params)?;
+        let offset = offset(&file, params.get("position")?);
+
+        Some((file, program, offset))
+    }
+
+    fn respond(&mut self, id: &Json, result: Result<Json, (i64, String)>) -> io::Result<()> {
+        let response = Json::object().with("jsonrpc", "2.0").with("id", id.clone());
+        let response = match result {
+            Ok(result) => response.with("result", result),
+            Err((code, message)) => response.with(
+                "error",
+                Json::object().with("code", code).with("message", message),
+            ),
+        };
+
+        write_message(&mut self.output, &response)
+    }
+}
+
+fn uri(params: &Json) -> Option<&str> {
+    params
+        .get("textDocument")
+        .unwrap_or(params)
+        .get("uri")
+        .and_then(Json::as_str)
+}
+
+fn location(uri: &str, span: &Span) -> Json {
+    Json::object().with("uri", uri).with("range", range(span))
+}
+
+fn symbol(name: &str, detail: Option<String>, kind: i64, span: &Span, name_span: &Span) -> Json {
+    Json::object()
+        .with("name", name)
+        .with("detail", detail)
+        .with("kind", kind)
+        .with("range", range(span))
+        .with("selectionRange", range(name_span))
+}
+
+fn diagnostic_to_json(uri: &str, diagnostic: &Diagnostic) -> Json {
+    let mut message = diagnostic.message.clone();
+    let primary = diagnostic.labels.iter().find(|label| label.primary);
+    if let Some(label) = primary.and_then(|label| label.message.as_ref()) {
+        message.push_str(&format!("\n{}", label));
+    }
+    for note in &diagnostic.notes {
+        message.push_str(&format!("\nnote: {}", note));
+    }
+    for help in &diagnostic.helps {
+        message.push_str(&format!("\nhelp: {}", help));
+    }
+
+    let related: Vec<Json> = diagnostic
+        .labels
+        .iter()
+        .filter(|label| !label.primary)
+        .map(|label| {
+            Json::object()
+                .with("location", location(uri, &label.span))
+                .with("message", label.message.clone().unwrap_or_default())
+        })
+        .collect();
+
+    let range = match diagnostic.primary_span() {
+        Some(span) => range(span),
+        None => Json::object()
+            .with("start", position(0, 0))
+            .with("end", position(0, 0)),
+    };
+
+    Json::object()
+        .with("range", range)
+        .with(
+            "severity",
+            match diagnostic.severity {
+                Severity::Error => 1i64,
+                Severity::Warning => 2,
+            },
+        )
+        .with("code", diagnostic.code_name())
+        .with("source", "eheh")
+        .with("message", message)
+        .with("relatedInformation", related)
+}
+
+/// Positions count lines from 0 and characters in UTF-16 code units.
+fn range(span: &Span) -> Json {
+    let file = &span.file;
+    let position_of = |offset: usize| {
+        let line = file.line_number(offset) - 1;
+        let start = file.line_starts()[line];
+        let character = file.src[start..offset.min(file.src.len())]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+
+        position(line, character)
+    };
+
+    Json::object()
+        .with("start", position_of(span.start))
+        .with("end", position_of(span.end))
+}
+
+fn position(line: usize, character: usize) -> Json {
+    Json::object()
+        .with("line", line)
+        .with("character", character)
+}
+
+/// The byte offset of an editor position, positions past the end of a line are at its end.
+fn offset(file: &SourceFile, position: &Json) -> usize {
+    let line = position.get("line").and_then(Json::as_i64).unwrap_or(0) as usize;
+    let character = position
+        .get("character")
+        .and_then(Json::as_i64)
+        .unwrap_or(0) as usize;
+
+    let Some(&start) = file.line_starts().get(line) else {
+        return file.src.len();
+    };
+
+    let mut units = 0;
+    for (index, c) in file.src[start..].char_indices() {
+        if units >= character || c == '\n' {
+            return start + index;
+        }
+        units += c.len_utf16();
+    }
+
+    file.src.len()
+}
+
+/// Reads the body of the next message, `None` at the end of the input.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut length = None;
+
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing the `Content-Length` header",
+        ));
+    };
+
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_message(output: &mut impl Write, message: &Json) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,434 @@
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        json::Json,
+        lint::{Level, LevelSource, LintLevels},
+        lsp::serve,
+    };
+
+    const URI: &str = "file:///main.eheh";
+
+    /// A client sending its messages at once and reading every message of the server after it
+    /// exited.
+    struct Client {
+        input: Vec<u8>,
+        next_id: i64,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            Self {
+                input: Vec::new(),
+                next_id: 1,
+            }
+        }
+
+        fn send(&mut self, message: Json) {
+            let body = message.with("jsonrpc", "2.0").to_string();
+            self.input
+                .extend(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).bytes());
+        }
+
+        /// Returns the id of the request.
+        fn request(&mut self, method: &str, params: Json) -> i64 {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.send(
+                Json::object()
+                    .with("id", id)
+                    .with("method", method)
+                    .with("params", params),
+            );
+            id
+        }
+
+        fn notify(&mut self, method: &str, params: Json) {
+            self.send(Json::object().with("method", method).with("params", params));
+        }
+
+        fn open(&mut self, text: &str) {
+            self.notify(
+                "textDocument/didOpen",
+                Json::object().with(
+                    "textDocument",
+                    Json::object()
+                        .with("uri", URI)
+                        .with("languageId", "eheh")
+                        .with("version", 1i64)
+                        .with("text", text),
+                ),
+            );
+        }
+
+        fn change(&mut self, text: &str) {
+            self.notify(
+                "textDocument/didChange",
+                Json::object()
+                    .with(
+                        "textDocument",
+                        Json::object().with("uri", URI).with("version", 2i64),
+                    )
+                    .with("contentChanges", vec![Json::object().with("text", text)]),
+            );
+        }
+
+        fn hover(&mut self, line: i64, character: i64) -> i64 {
+            self.request(
+                "textDocument/hover",
+                Json::object()
+                    .with("textDocument", Json::object().with("uri", URI))
+                    .with(
+                        "position",
+                        Json::object()
+                            .with("line", line)
+                            .with("character", character),
+                    ),
+            )
+        }
+
+        /// Runs the server on the messages sent so far, returns whether it shut down properly
+        /// and the messages it wrote.
+        fn run(self, lint_levels: LintLevels) -> (bool, Vec<Json>) {
+            let mut output = Vec::new();
+            let shutdown = serve(Cursor::new(self.input), &mut output, lint_levels).unwrap();
+
+            let output = String::from_utf8(output).unwrap();
+            let messages = output
+                .split("Content-Length: ")
+                .skip(1)
+                .map(|message| {
+                    let (_, body) = message.split_once("\r\n\r\n").unwrap();
+                    Json::parse(body).unwrap()
+                })
+                .collect();
+
+            (shutdown, messages)
+        }
+    }
+
+    fn response(messages: &[Json], id: i64) -> &Json {
+        messages
+            .iter()
+            .find(|message| message.get("id").and_then(Json::as_i64) == Some(id))
+            .unwrap()
+    }
+
+    /// The code, severity, message and range of every diagnostic published, per notification.
+    fn published(messages: &[Json]) -> Vec<Vec<(String, i64, String, String)>> {
+        messages
+            .iter()
+            .filter(|message| {
+                message.get("method").and_then(Json::as_str)
+                    == Some("textDocument/publishDiagnostics")
+            })
+            .map(|message| {
+                let params = message.get("params").unwrap();
+                assert_eq!(params.get("uri").unwrap().as_str(), Some(URI));
+
+                params
+                    .get("diagnostics")
+                    .unwrap()
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|diagnostic| {
+                        (
+                            diagnostic.get("code").unwrap().to_string(),
+                            diagnostic.get("severity").unwrap().as_i64().unwrap(),
+                            diagnostic
+                                .get("message")
+                                .unwrap()
+                                .as_str()
+                                .unwrap()
+                                .to_string(),
+                            range(diagnostic.get("range").unwrap()),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A range as `line:character-line:character`.
+    fn range(range: &Json) -> String {
+        let position = |key| {
+            let position = range.get(key).unwrap();
+            format!(
+                "{}:{}",
+                position.get("line").unwrap(),
+                position.get("character").unwrap()
+            )
+        };
+
+        format!("{}-{}", position("start"), position("end"))
+    }
+
+    fn hover_text(messages: &[Json], id: i64) -> Option<(String, String)> {
+        let result = response(messages, id).get("result").unwrap();
+        if *result == Json::Null {
+            return None;
+        }
+
+        let contents = result.get("contents").unwrap();
+        assert_eq!(contents.get("kind").unwrap().as_str(), Some("markdown"));
+
+        let value = contents.get("value").unwrap().as_str().unwrap();
+        let value = value
+            .strip_prefix("```eheh\n")
+            .and_then(|value| value.strip_suffix("\n```"))
+            .unwrap();
+
+        Some((value.to_string(), range(result.get("range").unwrap())))
+    }
+
+    #[test]
+    fn test_session() {
+        const CODE: &str = "\
+let a = 1;
+const name: string = \"eheh\";
+/* é😀 */ let add = fn(x: i32, y) -> i32 { x + y };
+fn sub(x, y) { x - y };
+add(true, a);";
+
+        let mut client = Client::new();
+        let initialize = client.request("initialize", Json::object());
+        client.notify("initialized", Json::object());
+        client.open(CODE);
+        let hovers = [
+            client.hover(0, 4),
+            client.hover(1, 8),
+            client.hover(2, 15),
+            client.hover(2, 43),
+            client.hover(4, 11),
+            client.hover(4, 6),
+        ];
+        let symbols = client.request(
+            "textDocument/documentSymbol",
+            Json::object().with("textDocument", Json::object().with("uri", URI)),
+        );
+        client.change("let a = 1 +;");
+        client.change("let a = 1;\na;");
+        client.notify(
+            "textDocument/didClose",
+            Json::object().with("textDocument", Json::object().with("uri", URI)),
+        );
+        let shutdown = client.request("shutdown", Json::Null);
+        client.notify("exit", Json::Null);
+
+        let (exited, messages) = client.run(LintLevels::new());
+        assert!(exited);
+
+        let capabilities = response(&messages, initialize)
+            .get("result")
+            .unwrap()
+            .get("capabilities")
+            .unwrap();
+        assert_eq!(
+            capabilities.to_string(),
+            r#"{"textDocumentSync":{"openClose":true,"change":1},"hoverProvider":true,"documentSymbolProvider":true,"definitionProvider":true,"referencesProvider":true,"renameProvider":true}"#
+        );
+
+        // the type error, the syntax error, nothing once fixed and nothing once closed
+        assert_eq!(
+            published(&messages),
+            vec![
+                vec![(
+                    "\"E02000\"".to_string(),
+                    1,
+                    "Mismatched types.\nexpected `i64`, found `bool`".to_string(),
+                    "4:4-4:8".to_string()
+                ),],
+                vec![(
+                    "\"E01001\"".to_string(),
+                    1,
+                    "Unknown prefix token: `;`.\nexpected an expression".to_string(),
+                    "0:11-0:12".to_string()
+                )],
+                vec![],
+                vec![],
+            ]
+        );
+
+        let hovers: Vec<Option<(String, String)>> =
+            hovers.iter().map(|id| hover_text(&messages, *id)).collect();
+        assert_eq!(
+            hovers,
+            vec![
+                Some(("let a: i64 = 1".to_string(), "0:4-0:5".to_string())),
+                Some((
+                    "const name: string = \"eheh\"".to_string(),
+                    "1:6-1:10".to_string()
+                )),
+                // positions count UTF-16 code units, the emoji counts twice
+                Some((
+                    "let add: fn(i64, _) -> i64".to_string(),
+                    "2:14-2:17".to_string()
+                )),
+                Some(("(parameter) x: i32".to_string(), "2:43-2:44".to_string())),
+                Some(("let a: i64 = 1".to_string(), "4:10-4:11".to_string())),
+                None,
+            ]
+        );
+
+        let symbols: Vec<String> = response(&messages, symbols)
+            .get("result")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| {
+                format!(
+                    "{} {} {} {} {}",
+                    symbol.get("name").unwrap().as_str().unwrap(),
+                    symbol.get("kind").unwrap(),
+                    symbol.get("detail").unwrap(),
+                    range(symbol.get("range").unwrap()),
+                    range(symbol.get("selectionRange").unwrap())
+                )
+            })
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                "a 13 \"i64\" 0:0-0:9 0:4-0:5",
+                "name 14 \"string\" 1:0-1:27 1:6-1:10",
+                "add 12 \"fn(i64, _) -> i64\" 2:10-2:50 2:14-2:17",
+                "sub 12 \"fn sub(x, y)\" 3:0-3:22 3:3-3:6",
+            ]
+        );
+
+        assert_eq!(
+            response(&messages, shutdown).get("result"),
+            Some(&Json::Null)
+        );
+    }
+
+    #[test]
+    fn test_navigation() {
+        const CODE: &str = "let total = 1;\nlet add = fn(n) { total + n };\nadd(total);";
+
+        let position = |line: i64, character: i64| {
+            Json::object()
+                .with("textDocument", Json::object().with("uri", URI))
+                .with(
+                    "position",
+                    Json::object()
+                        .with("line", line)
+                        .with("character", character),
+                )
+        };
+
+        let mut client = Client::new();
+        client.request("initialize", Json::object());
+        client.open(CODE);
+        let definition = client.request("textDocument/definition", position(2, 6));
+        let nowhere = client.request("textDocument/definition", position(1, 10));
+        let references = client.request(
+            "textDocument/references",
+            position(1, 20).with("context", Json::object().with("includeDeclaration", false)),
+        );
+        let rename = client.request("textDocument/rename", position(0, 4).with("newName", "sum"));
+        let conflict = client.request("textDocument/rename", position(0, 4).with("newName", "n"));
+        client.request("shutdown", Json::Null);
+        client.notify("exit", Json::Null);
+
+        let (_, messages) = client.run(LintLevels::new());
+        let result = |id| response(&messages, id).get("result").unwrap();
+        let location = |location: &Json| {
+            assert_eq!(location.get("uri").unwrap().as_str(), Some(URI));
+            range(location.get("range").unwrap())
+        };
+
+        assert_eq!(location(result(definition)), "0:4-0:9");
+        assert_eq!(result(nowhere), &Json::Null);
+
+        let references: Vec<String> = result(references)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(location)
+            .collect();
+        assert_eq!(references, vec!["1:18-1:23", "2:4-2:9"]);
+
+        let edits: Vec<String> = result(rename)
+            .get("changes")
+            .unwrap()
+            .get(URI)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edit| {
+                format!(
+                    "{} {}",
+                    range(edit.get("range").unwrap()),
+                    edit.get("newText").unwrap().as_str().unwrap()
+                )
+            })
+            .collect();
+        assert_eq!(edits, vec!["0:4-0:9 sum", "1:18-1:23 sum", "2:4-2:9 sum"]);
+
+        let error = response(&messages, conflict).get("error").unwrap();
+        assert_eq!(error.get("code").unwrap().as_i64(), Some(-32803));
+        assert_eq!(
+            error.get("message").unwrap().as_str(),
+            Some("renaming `total` to `n` would change what `total` refers to at file:///main.eheh:2:19")
+        );
+    }
+
+    #[test]
+    fn test_lint_levels() {
+        const CODE: &str = "let f = fn() {};\nf();";
+
+        let mut lint_levels = LintLevels::new();
+        lint_levels.set("empty_function", Level::Deny, LevelSource::CommandLine);
+
+        let mut client = Client::new();
+        client.request("initialize", Json::object());
+        client.open(CODE);
+        client.notify("exit", Json::Null);
+
+        let (exited, messages) = client.run(lint_levels);
+        assert!(!exited, "the server exited without a shutdown request");
+
+        let diagnostics = &published(&messages)[0];
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, "\"E04006\"");
+        assert_eq!(diagnostics[0].1, 1);
+        assert!(diagnostics[0]
+            .2
+            .ends_with("note: `-D empty_function` was passed on the command line"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut client = Client::new();
+        let early = client.request("shutdown", Json::Null);
+        client.open("let a = ;");
+        let initialize = client.request("initialize", Json::object());
+        let unknown = client.request("textDocument/completion", Json::object());
+        client.input.extend(b"Content-Length: 8\r\n\r\n{\"id\": }");
+        let hover = client.hover(0, 0);
+
+        let (exited, messages) = client.run(LintLevels::new());
+        assert!(!exited);
+
+        let error = |id: &Json| {
+            messages
+                .iter()
+                .find(|message| message.get("id") == Some(id))
+                .and_then(|message| message.get("error"))
+                .and_then(|error| error.get("code"))
+                .and_then(Json::as_i64)
+        };
+
+        assert_eq!(error(&Json::Number(early)), Some(-32002));
+        assert_eq!(error(&Json::Number(initialize)), None);
+        assert_eq!(error(&Json::Number(unknown)), Some(-32601));
+        assert_eq!(error(&Json::Null), Some(-32700));
+        // documents opened before `initialize` are ignored
+        assert!(published(&messages).is_empty());
+        assert_eq!(response(&messages, hover).get("result"), Some(&Json::Null));
+    }
+}
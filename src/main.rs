@@ -1,30 +1,173 @@
 #![allow(unused)]
 
-use repl::REPL;
+use std::{cell::RefCell, env, fs, io, path::Path, process, rc::Rc};
 
-use crate::{
-    log::warning::ParserWarning,
+use eheh_lang::{
+    allocator,
+    cli::{self, Command, Options},
+    config::Config,
+    evaluator::Evaluator,
+    fix::{diff::unified_diff, fix_source},
+    formatter::format_source,
+    lexer::Lexer,
+    lint::{self, LevelSource, LintLevels},
+    log::{
+        diagnostic::{Diagnostic, Severity},
+        emitter::Emitter,
+        error::runtime::RuntimeError,
+        registry,
+        warning::ParserWarning,
+    },
+    lsp,
+    objects::{environment::Environment, error::Error},
+    optimizer::{self, OptLevel},
+    parser::Parser,
+    program::Program,
+    repl::REPL,
+    source::SourceMap,
     token::{
         token_type::{KeywordTokenType, TokenType},
         Token,
     },
+    traits::object::ObjectType,
+    type_checker,
 };
 
-mod ast;
-mod evaluator;
-mod lexer;
-mod log;
-mod objects;
-mod parser;
-mod program;
-mod repl;
-mod token;
-mod traits;
-mod types;
-
 fn main() {
-    let mut repl = REPL::new();
-    repl.start();
+    // recursion in eheh is recursion of the evaluator
+    Evaluator::on_large_stack(run);
+}
+
+fn run() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+
+    if let Some(depth) = options.max_call_depth {
+        Evaluator::set_max_call_depth(depth);
+    }
+    allocator::set_allocator(options.allocator.allocator());
+
+    colored::control::set_override(cli::should_colorize(&options));
+
+    let lint_levels = match lint_levels(&options) {
+        Ok(lint_levels) => lint_levels,
+        Err(diagnostic) => {
+            let mut emitter = Emitter::new(options.message_format);
+            emitter.emit(*diagnostic);
+            emitter.finish();
+            process::exit(1);
+        }
+    };
+
+    let paths = match options.command {
+        Command::Run { paths } => paths,
+        Command::Explain { code } => match registry::explain(&code) {
+            Ok(explanation) => {
+                print!("{}", explanation);
+                return;
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        },
+        Command::Fix { paths, dry_run } => {
+            let mut emitter = Emitter::new(options.message_format);
+            let mut success = true;
+            for path in &paths {
+                success &= fix_file(&mut emitter, Path::new(path), dry_run, &lint_levels);
+            }
+
+            emitter.finish();
+            if !success {
+                process::exit(1);
+            }
+            return;
+        }
+        Command::Fmt { paths, check } => {
+            let mut emitter = Emitter::new(options.message_format);
+            let mut success = true;
+            for path in &paths {
+                success &= format_file(&mut emitter, Path::new(path), check);
+            }
+
+            emitter.finish();
+            if !success {
+                process::exit(1);
+            }
+            return;
+        }
+        Command::Lsp => {
+            let stdin = io::stdin();
+            let stdout = io::stdout();
+            match lsp::serve(stdin.lock(), stdout.lock(), lint_levels) {
+                Ok(true) => return,
+                Ok(false) => process::exit(1),
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Check { paths } => {
+            let mut source_map = SourceMap::new();
+            let mut emitter = Emitter::new(options.message_format);
+            let mut success = true;
+            for path in &paths {
+                success &= parse_file(
+                    &mut source_map,
+                    &mut emitter,
+                    Path::new(path),
+                    &lint_levels,
+                    true,
+                )
+                .is_some();
+            }
+
+            emitter.finish();
+            if !success {
+                process::exit(1);
+            }
+            return;
+        }
+    };
+
+    if paths.is_empty() {
+        let mut repl = REPL::new();
+        repl.lint_levels = lint_levels;
+        repl.opt_level = options.opt_level;
+        repl.start();
+        return;
+    }
+
+    let mut source_map = SourceMap::new();
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let mut emitter = Emitter::new(options.message_format);
+
+    let mut success = true;
+    for path in &paths {
+        if !run_file(
+            &mut source_map,
+            &mut emitter,
+            Path::new(path),
+            &lint_levels,
+            options.opt_level,
+            Rc::clone(&environment),
+        ) {
+            success = false;
+            break;
+        }
+    }
+
+    emitter.finish();
+    if !success {
+        process::exit(1);
+    }
 
     // let token = Token::new(
     //     TokenType::IDENT,
@@ -58,3 +201,205 @@ fn main() {
 
     // println!("{}", warning);
 }
+
+/// The lint levels of the config file overridden by the ones of the command line.
+fn lint_levels(options: &Options) -> Result<LintLevels, Box<Diagnostic>> {
+    let mut lint_levels = LintLevels::new();
+
+    let dir = env::current_dir().map_err(|err| {
+        Box::new(Diagnostic::error(format!(
+            "could not read the current directory: {}",
+            err
+        )))
+    })?;
+    if let Some(config) = Config::load(&dir)? {
+        config.apply(&mut lint_levels);
+    }
+
+    for (name, level) in &options.lints {
+        lint_levels.set(name, *level, LevelSource::CommandLine);
+    }
+
+    Ok(lint_levels)
+}
+
+/// Applies the machine applicable fixes to the file at `path`, only prints them with `dry_run`.
+/// Returns `false` if the file could not be fixed.
+fn fix_file(emitter: &mut Emitter, path: &Path, dry_run: bool, lint_levels: &LintLevels) -> bool {
+    let name = path.to_string_lossy();
+    let src = match fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(err) => {
+            emitter.emit(Diagnostic::error(format!(
+                "could not read `{}`: {}",
+                name, err
+            )));
+            return false;
+        }
+    };
+
+    let fixed = match fix_source(&name, &src, lint_levels) {
+        Ok(fixed) => fixed,
+        Err(errors) => {
+            for error in errors {
+                emitter.emit(error);
+            }
+            return false;
+        }
+    };
+
+    if fixed.applied == 0 {
+        return true;
+    }
+
+    if dry_run {
+        print!("{}", unified_diff(&name, &src, &fixed.src));
+        return true;
+    }
+
+    if let Err(err) = fs::write(path, &fixed.src) {
+        emitter.emit(Diagnostic::error(format!(
+            "could not write `{}`: {}",
+            name, err
+        )));
+        return false;
+    }
+
+    let fixes = if fixed.applied == 1 { "fix" } else { "fixes" };
+    eprintln!("Fixed {} ({} {})", name, fixed.applied, fixes);
+    true
+}
+
+/// Formats the file at `path` in place, only prints the changes with `check`. Returns `false` if
+/// the file could not be formatted, or was not formatted with `check`.
+fn format_file(emitter: &mut Emitter, path: &Path, check: bool) -> bool {
+    let name = path.to_string_lossy();
+    let src = match fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(err) => {
+            emitter.emit(Diagnostic::error(format!(
+                "could not read `{}`: {}",
+                name, err
+            )));
+            return false;
+        }
+    };
+
+    let formatted = match format_source(&name, &src) {
+        Ok(formatted) => formatted,
+        Err(errors) => {
+            for error in errors {
+                emitter.emit(error);
+            }
+            return false;
+        }
+    };
+
+    if formatted == src {
+        return true;
+    }
+
+    if check {
+        print!("{}", unified_diff(&name, &src, &formatted));
+        return false;
+    }
+
+    if let Err(err) = fs::write(path, &formatted) {
+        emitter.emit(Diagnostic::error(format!(
+            "could not write `{}`: {}",
+            name, err
+        )));
+        return false;
+    }
+
+    eprintln!("Formatted {}", name);
+    true
+}
+
+/// Parses the file at `path` and reports its diagnostics, returns `None` if it has errors or
+/// denied warnings. The findings of the type checker are errors only if `deny_type_errors`.
+fn parse_file(
+    source_map: &mut SourceMap,
+    emitter: &mut Emitter,
+    path: &Path,
+    lint_levels: &LintLevels,
+    deny_type_errors: bool,
+) -> Option<Program> {
+    let file = match source_map.load_file(path) {
+        Ok(file) => file,
+        Err(err) => {
+            emitter.emit(Diagnostic::error(format!(
+                "could not read `{}`: {}",
+                path.display(),
+                err
+            )));
+            return None;
+        }
+    };
+
+    let mut lexer = Lexer::from_source(file);
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+    if parser.errors.is_empty() {
+        parser.warnings.extend(lint::semantic::check(&program));
+    }
+
+    let type_errors = match parser.errors.is_empty() {
+        true => type_checker::check(&program).errors,
+        false => Vec::new(),
+    };
+
+    let mut success = parser.errors.is_empty() && (type_errors.is_empty() || !deny_type_errors);
+    for diagnostic in lint_levels.report(&parser.warnings, &parser.lint_attributes) {
+        success &= diagnostic.severity != Severity::Error;
+        emitter.emit(diagnostic);
+    }
+
+    for error in &parser.errors {
+        emitter.emit(error.to_diagnostic());
+    }
+
+    for error in &type_errors {
+        emitter.emit(match deny_type_errors {
+            true => error.to_diagnostic(),
+            false => error.to_warning(),
+        });
+    }
+
+    success.then_some(program)
+}
+
+/// Evaluates the file at `path` in `environment`, returns `false` if it could not be run.
+fn run_file(
+    source_map: &mut SourceMap,
+    emitter: &mut Emitter,
+    path: &Path,
+    lint_levels: &LintLevels,
+    opt_level: OptLevel,
+    environment: Rc<RefCell<Environment>>,
+) -> bool {
+    let Some(mut program) = parse_file(source_map, emitter, path, lint_levels, false) else {
+        return false;
+    };
+    optimizer::optimize(&mut program, opt_level);
+
+    match Evaluator::eval(&program, environment) {
+        Ok(evaluated) if evaluated.t() == ObjectType::Error => {
+            let diagnostic = match evaluated.as_any_ref().downcast_ref::<Error>() {
+                Some(error) => error.to_diagnostic(),
+                None => Diagnostic::error(evaluated.inspect()),
+            };
+            emitter.emit(diagnostic);
+            false
+        }
+        Ok(_) => true,
+        Err(err) => {
+            let diagnostic = match err.downcast_ref::<RuntimeError>() {
+                Some(err) => err.to_diagnostic(),
+                None => Diagnostic::error(err.to_string()),
+            };
+            emitter.emit(diagnostic);
+            false
+        }
+    }
+}
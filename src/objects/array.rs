@@ -0,0 +1,60 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::traits::object::{Object, ObjectType};
+
+use super::environment::Environment;
+
+#[derive(Debug, Clone)]
+pub struct Array {
+    pub elements: Vec<Box<dyn Object>>,
+}
+
+impl Array {
+    pub fn new(elements: Vec<Box<dyn Object>>) -> Self {
+        Self { elements }
+    }
+}
+
+impl Object for Array {
+    fn t(&self) -> ObjectType {
+        ObjectType::Array
+    }
+
+    fn inspect(&self) -> String {
+        self.to_string()
+    }
+
+    fn as_any_ref(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(self) -> Box<dyn std::any::Any> {
+        Box::new(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Object> {
+        Box::new(self.clone())
+    }
+
+    fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {
+        for element in &self.elements {
+            element.trace(visit);
+        }
+    }
+}
+
+impl Display for Array {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let elements: Vec<String> = self
+            .elements
+            .iter()
+            .map(|element| element.to_string())
+            .collect();
+
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
@@ -36,6 +36,7 @@ impl Object for Boolean {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Boolean {
     fn to_string(&self) -> String {
         self.value.to_string()
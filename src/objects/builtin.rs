@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use crate::{
+    traits::object::{Object, ObjectType},
+    types::BuiltinFn,
+};
+
+/// A function of the interpreter callable from eheh, e.g: `gc_stats()`.
+#[derive(Debug, Clone)]
+pub struct Builtin {
+    pub name: &'static str,
+    /// The number of arguments it takes, none can be passed by name.
+    pub arity: usize,
+    pub function: BuiltinFn,
+}
+
+impl Builtin {
+    pub const fn new(name: &'static str, arity: usize, function: BuiltinFn) -> Self {
+        Self {
+            name,
+            arity,
+            function,
+        }
+    }
+}
+
+impl Object for Builtin {
+    fn t(&self) -> ObjectType {
+        ObjectType::Builtin
+    }
+
+    fn inspect(&self) -> String {
+        self.to_string()
+    }
+
+    fn as_any_ref(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(self) -> Box<dyn std::any::Any> {
+        Box::new(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Object> {
+        Box::new(self.clone())
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "builtin fn {}", self.name)
+    }
+}
@@ -1,34 +1,106 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::traits::object::Object;
+use crate::{allocator, ast::expressions::identifier::Slot, traits::object::Object};
 
 #[derive(Debug, Clone)]
 pub struct Environment {
     pub store: HashMap<String, Rc<RefCell<Box<dyn Object>>>>,
+    /// The locals of a function call, `None` until declared.
+    pub slots: Vec<Option<Box<dyn Object>>>,
+    /// The name of every slot.
+    pub locals: Rc<[String]>,
     pub outer: Option<Rc<RefCell<Environment>>>,
+    /// Set once a function captures the environment, which may then be part of a reference
+    /// cycle. See `gc::track`.
+    pub tracked: bool,
 }
 
 impl Environment {
     pub fn new(outer: Option<Rc<RefCell<Environment>>>) -> Self {
         Self {
             store: HashMap::new(),
+            slots: Vec::new(),
+            locals: Rc::default(),
             outer,
+            tracked: false,
         }
     }
 
-    pub fn get(&self, name: &str) -> Option<Rc<RefCell<Box<dyn Object>>>> {
+    /// The environment of a call, with a slot for each of `locals`.
+    pub fn frame(locals: Rc<[String]>, outer: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            store: HashMap::new(),
+            slots: vec![None; locals.len()],
+            locals,
+            outer: Some(outer),
+            tracked: false,
+        }
+    }
+
+    /// Looks `name` up in this environment, then in its outer ones.
+    pub fn get(&self, name: &str) -> Option<Box<dyn Object>> {
+        if let Some(value) = self.store.get(name) {
+            return Some(allocator::clone_object(value.borrow().as_ref()));
+        }
+
+        let local = self.locals.iter().position(|local| local == name);
+        if let Some(value) = local.and_then(|index| self.slots[index].as_ref()) {
+            return Some(allocator::clone_object(value.as_ref()));
+        }
+
+        self.outer
+            .as_ref()
+            .and_then(|outer_env| outer_env.borrow().get(name))
+    }
+
+    /// The value of a local, `None` if it is not declared yet.
+    pub fn get_slot(&self, slot: Slot) -> Option<Box<dyn Object>> {
+        if slot.depth == 0 {
+            let value = self.slots.get(slot.index)?.as_ref()?;
+            return Some(allocator::clone_object(value.as_ref()));
+        }
+
+        self.outer.as_ref()?.borrow().get_slot(Slot {
+            depth: slot.depth - 1,
+            index: slot.index,
+        })
+    }
+
+    /// Every name visible from this environment, the innermost first.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.store.keys().cloned().collect();
+        for (name, value) in self.locals.iter().zip(&self.slots) {
+            if value.is_some() && !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names.sort();
+
         if let Some(outer_env) = &self.outer {
-            if let Some(value) = outer_env.borrow().store.get(name) {
-                return Some(value.clone());
+            for name in outer_env.borrow().names() {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
             }
         }
-        self.store.get(name).cloned()
+
+        names
     }
 
     pub fn set(&mut self, name: String, value: Box<dyn Object>) -> Option<Rc<RefCell<Box<dyn Object>>>> {
         self.store.insert(name, Rc::new(RefCell::new(value)))
     }
 
+    /// Declares the local at `index` of this frame, by name if the frame has no such slot.
+    pub fn set_slot(&mut self, index: usize, name: &str, value: Box<dyn Object>) {
+        match self.slots.get_mut(index) {
+            Some(slot) => *slot = Some(value),
+            None => {
+                self.set(name.to_string(), value);
+            }
+        }
+    }
+
     pub fn remove(&mut self, name: &str) -> Option<Rc<RefCell<Box<dyn Object>>>> {
         self.store.remove(name)
     }
@@ -36,5 +108,21 @@ impl Environment {
     pub fn has(&self, name: &str) -> bool {
         self.store.contains_key(name)
     }
-}
 
+    /// Calls `visit` with the environments this one holds: its outer one and the ones of its
+    /// values. Values shared with other owners are only visited when `shared` is set.
+    pub fn trace(&self, shared: bool, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {
+        if let Some(outer) = &self.outer {
+            visit(outer);
+        }
+
+        for value in self.store.values() {
+            if shared || Rc::strong_count(value) == 1 {
+                value.borrow().trace(visit);
+            }
+        }
+        for value in self.slots.iter().flatten() {
+            value.trace(visit);
+        }
+    }
+}
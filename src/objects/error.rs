@@ -1,15 +1,88 @@
 use std::sync::Arc;
 
-use crate::traits::object::{Object, ObjectType};
+use crate::{
+    log::{
+        diagnostic::{Diagnostic, Label},
+        error::runtime::RuntimeError,
+    },
+    source::Span,
+    traits::object::{Object, ObjectType},
+};
+
+/// A call to an eheh function that was still running when the error was raised.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub function: String,
+    pub call_site: Span,
+}
 
 #[derive(Debug, Clone)]
 pub struct Error {
     pub err: Arc<dyn std::error::Error>,
+    /// The node whose evaluation failed, set by the evaluator.
+    pub span: Option<Span>,
+    /// The calls the error went through, the most recent first.
+    pub trace: Vec<Frame>,
 }
 
 impl Error {
     pub fn new(err: Arc<dyn std::error::Error>) -> Self {
-        Self { err }
+        Self {
+            err,
+            span: None,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = match self.err.downcast_ref::<RuntimeError>() {
+            Some(err) => err.to_diagnostic(),
+            None => Diagnostic::error(self.err.to_string()),
+        };
+
+        if let Some(span) = &self.span {
+            if diagnostic.labels.is_empty() {
+                diagnostic = diagnostic.with_label(Label::primary(span.clone()));
+            }
+        }
+
+        if self.trace.is_empty() {
+            return diagnostic;
+        }
+
+        // runs of the same call, e.g: from recursion, are shown once
+        let mut frames: Vec<String> = Vec::new();
+        let mut index = 0;
+        while index < self.trace.len() {
+            let frame = &self.trace[index];
+            let repeated = self.trace[index + 1..]
+                .iter()
+                .take_while(|next| {
+                    next.function == frame.function && next.call_site == frame.call_site
+                })
+                .count();
+
+            frames.push(format!(
+                "{:>4}: `{}`, called at {}",
+                index,
+                frame.function,
+                frame.call_site.get_location()
+            ));
+            if repeated > 0 {
+                frames.push(format!(
+                    "      [the call above is repeated {} more time{}]",
+                    repeated,
+                    if repeated == 1 { "" } else { "s" }
+                ));
+            }
+
+            index += repeated + 1;
+        }
+
+        diagnostic.with_note(format!(
+            "stack trace, most recent call first:\n{}",
+            frames.join("\n")
+        ))
     }
 }
 
@@ -38,8 +111,9 @@ impl Object for Error {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Error {
     fn to_string(&self) -> String {
-        self.err.to_string()
+        self.to_diagnostic().to_string()
     }
 }
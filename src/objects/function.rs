@@ -5,6 +5,7 @@ use crate::{
         expressions::{identifier::Identifier, typed_identifier::TypedIdentifier},
         statements::block_statement::BlockStatement,
     },
+    source::Span,
     traits::{
         node::Node,
         object::{Object, ObjectType},
@@ -13,33 +14,43 @@ use crate::{
 
 use super::environment::Environment;
 
+/// Cloned on every read of the function, its parts are shared rather than copied.
 #[derive(Debug, Clone)]
 pub struct Function {
-    pub parameters: Vec<TypedIdentifier>,
-    pub body: BlockStatement,
+    pub parameters: Rc<[TypedIdentifier]>,
+    pub body: Rc<BlockStatement>,
     pub return_type: Option<Identifier>,
+    /// The name of every slot of the frame of a call.
+    pub locals: Rc<[String]>,
     pub env: Rc<RefCell<Environment>>,
+    /// The literal the function was created from, printed as written: the tree of the body may
+    /// have been rewritten by `optimizer::optimize`.
+    pub span: Span,
 }
 
 impl Function {
     pub fn new(
-        parameters: Vec<TypedIdentifier>,
-        body: BlockStatement,
+        parameters: Rc<[TypedIdentifier]>,
+        body: Rc<BlockStatement>,
         return_type: Option<Identifier>,
+        locals: Rc<[String]>,
         env: Rc<RefCell<Environment>>,
+        span: Span,
     ) -> Self {
         Self {
             parameters,
             body,
             return_type,
+            locals,
             env,
+            span,
         }
     }
 }
 
 impl Object for Function {
     fn t(&self) -> ObjectType {
-        ObjectType::Boolean
+        ObjectType::Function
     }
 
     fn inspect(&self) -> String {
@@ -60,30 +71,15 @@ impl Object for Function {
     fn clone_boxed(&self) -> Box<dyn Object> {
         Box::new(self.clone())
     }
+
+    fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {
+        visit(&self.env);
+    }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Function {
     fn to_string(&self) -> String {
-        let mut str = String::new();
-        str.push_str("fn ");
-
-        str.push_str("(");
-
-        self.parameters.iter().enumerate().for_each(|(i, param)| {
-            str.push_str(param.to_string().as_str());
-            if i < self.parameters.len() - 1 {
-                str.push_str(", ");
-            }
-        });
-
-        str.push_str(")");
-
-        if let Some(return_type) = &self.return_type {
-            str.push_str(format!(" -> {} ", return_type.get_token_literal()).as_str());
-        }
-
-        str.push_str(self.body.to_string().as_str());
-
-        str
+        self.span.source_text().to_string()
     }
 }
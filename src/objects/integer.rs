@@ -39,6 +39,7 @@ impl Object for Integer {
 
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Integer {
     fn to_string(&self) -> String {
         self.value.to_string()
@@ -1,9 +1,12 @@
+pub mod array;
 pub mod boolean;
+pub mod builtin;
 pub mod environment;
 pub mod error;
 pub mod function;
 pub mod integer;
 pub mod null;
 pub mod string_;
+pub mod tail_call;
 pub mod return_;
 
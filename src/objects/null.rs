@@ -4,6 +4,7 @@ use crate::traits::object::{Object, ObjectType};
 pub struct Null {}
 
 impl Null {
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self { Self {  } }
 }
 
@@ -34,6 +35,7 @@ impl Object for Null {
 
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Null {
     fn to_string(&self) -> String {
         "null".to_string()
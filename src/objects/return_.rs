@@ -1,5 +1,9 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crate::traits::object::{Object, ObjectType};
 
+use super::environment::Environment;
+
 #[derive(Debug, Clone)]
 pub struct Return {
     pub value: Option<Box<dyn Object>>,
@@ -36,9 +40,16 @@ impl Object for Return {
     fn clone_boxed(&self) -> Box<dyn Object> {
         Box::new(self.clone())
     }
+
+    fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {
+        if let Some(value) = &self.value {
+            value.trace(visit);
+        }
+    }
 }
 
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Return {
     fn to_string(&self) -> String {
         if let Some(value) = &self.value {
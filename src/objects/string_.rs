@@ -1,4 +1,4 @@
-use crate::traits::object::{IntegerType, Object, ObjectType};
+use crate::traits::object::{Object, ObjectType};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StringObj {
@@ -15,7 +15,7 @@ impl StringObj {
 
 impl Object for StringObj {
     fn t(&self) -> ObjectType {
-        ObjectType::Integer(IntegerType::I64)
+        ObjectType::String
     }
 
     fn inspect(&self) -> String {
@@ -39,6 +39,7 @@ impl Object for StringObj {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for StringObj {
     fn to_string(&self) -> String {
         format!("\"{}\"", self.content)
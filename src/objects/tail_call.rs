@@ -0,0 +1,77 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::traits::object::{Object, ObjectType};
+
+use super::{environment::Environment, error::Frame, function::Function};
+
+/// The arguments of a call matched with the parameters of the function called.
+#[derive(Debug, Clone, Default)]
+pub struct Arguments {
+    /// One per parameter but the rest one, `None` for the ones left to their default value.
+    pub values: Vec<Option<Box<dyn Object>>>,
+    /// The arguments collected by the rest parameter.
+    pub rest: Vec<Box<dyn Object>>,
+}
+
+/// A call in tail position, made by the caller of the function it ends once the frame of that
+/// function is gone, so tail recursion runs in constant stack space.
+#[derive(Debug, Clone)]
+pub struct TailCall {
+    pub function: Function,
+    pub arguments: Arguments,
+    /// The only frame of the stack trace left by the call if it fails, there is none for the
+    /// tail calls before it.
+    pub frame: Frame,
+}
+
+impl TailCall {
+    pub fn new(function: Function, arguments: Arguments, frame: Frame) -> Self {
+        Self {
+            function,
+            arguments,
+            frame,
+        }
+    }
+}
+
+impl Object for TailCall {
+    fn t(&self) -> ObjectType {
+        ObjectType::TailCall
+    }
+
+    fn inspect(&self) -> String {
+        self.to_string()
+    }
+
+    fn as_any_ref(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(self) -> Box<dyn std::any::Any> {
+        Box::new(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Object> {
+        Box::new(self.clone())
+    }
+
+    fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {
+        self.function.trace(visit);
+        for value in self.arguments.values.iter().flatten() {
+            value.trace(visit);
+        }
+        for value in &self.arguments.rest {
+            value.trace(visit);
+        }
+    }
+}
+
+impl Display for TailCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tail call to {}", self.function.to_string())
+    }
+}
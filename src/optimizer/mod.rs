@@ -0,0 +1,281 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    ast::{
+        expressions::{
+            boolean_expression::BooleanExpression, call_expression::CallExpression,
+            function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+            infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+            null_expression::NullExpression, typed_identifier::TypedIdentifier,
+        },
+        kind::{ExpressionKind, ExpressionKindMut, StatementKind},
+        statements::declare_statement::DeclareStatement,
+        visit::{self, Visitor},
+        visit_mut::{self, VisitorMut},
+    },
+    evaluator::Evaluator,
+    objects::{boolean::Boolean, environment::Environment, integer::Integer},
+    program::Program,
+    source::Span,
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+    types::{ASTExpression, ASTStatement},
+};
+
+/// How much `optimize` rewrites a program, set with `-O0` and `-O1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OptLevel {
+    /// The program is run as written.
+    #[default]
+    O0,
+    /// Constant expressions are folded, unreachable code removed and constants inlined.
+    O1,
+}
+
+impl OptLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "0" => Some(OptLevel::O0),
+            "1" => Some(OptLevel::O1),
+            _ => None,
+        }
+    }
+}
+
+/// Rewrites `program` into one evaluating to the same values, errors and their location
+/// included:
+/// - operations on literals are replaced by their value, the ones that would fail are kept so
+///   that they fail at runtime,
+/// - the `if` branches that cannot be taken are emptied, the statements after a `return` removed,
+/// - the constants declared with a literal are replaced by it where they are in scope.
+///
+/// Must run before the program is resolved, the locals of functions may change.
+pub fn optimize(program: &mut Program, level: OptLevel) {
+    if level == OptLevel::O0 {
+        return;
+    }
+
+    let mut declarations = Declarations(HashMap::new());
+    declarations.visit_program(program);
+
+    let mut optimizer = Optimizer {
+        declarations: declarations.0,
+        constants: Vec::new(),
+    };
+    optimizer.visit_program(program);
+}
+
+struct Optimizer {
+    /// How many times each name is declared in the program, parameters included. A constant is
+    /// only inlined when its name is never declared again.
+    declarations: HashMap<String, usize>,
+    /// The constants in scope and their literal, innermost last.
+    constants: Vec<(String, ASTExpression)>,
+}
+
+impl VisitorMut for Optimizer {
+    /// The constants of a block are in scope until its end, they are not declared if the block
+    /// is not run.
+    fn visit_statements(&mut self, statements: &mut Vec<ASTStatement>) {
+        let scope = self.constants.len();
+
+        visit_mut::walk_statements(self, statements);
+
+        let returned = statements
+            .iter()
+            .position(|statement| matches!(statement.kind(), StatementKind::Return(_)));
+        if let Some(index) = returned {
+            statements.truncate(index + 1);
+        }
+
+        self.constants.truncate(scope);
+    }
+
+    fn visit_declare_statement(&mut self, declare: &mut DeclareStatement) {
+        let Some(value) = &mut declare.value else {
+            return;
+        };
+        self.visit_expression(value);
+
+        let name = &declare.name.value;
+        if declare.token.t == TokenType::KEYWORD(KeywordTokenType::CONST)
+            && is_literal(value)
+            && self.declarations.get(name) == Some(&1)
+        {
+            self.constants.push((name.clone(), value.clone()));
+        }
+    }
+
+    /// The children are optimized first, the expression is then replaced by its value when they
+    /// are literals.
+    fn visit_expression(&mut self, expression: &mut ASTExpression) {
+        visit_mut::walk_expression(self, expression);
+
+        let replacement = match expression.kind() {
+            ExpressionKind::Identifier(identifier) => self
+                .constants
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == identifier.value)
+                .map(|(_, literal)| relocate(literal, expression.span())),
+            ExpressionKind::Infix(infix) if is_literal(&infix.lhs) && is_literal(&infix.rhs) => {
+                fold(expression)
+            }
+            ExpressionKind::Prefix(prefix) if is_literal(&prefix.rhs) => fold(expression),
+            ExpressionKind::If(if_expression)
+                if if_expression.alternative.is_none()
+                    && truthiness(&if_expression.condition) == Some(false) =>
+            {
+                let null = TokenType::KEYWORD(KeywordTokenType::NULL);
+                let token = Token::new(null, expression.span());
+                Some(Box::new(NullExpression::new(token)) as ASTExpression)
+            }
+            _ => None,
+        };
+
+        if let Some(replacement) = replacement {
+            *expression = replacement;
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &mut CallExpression) {
+        // the callee is left as written, errors quote it
+        for arg in &mut call.args {
+            self.visit_expression(arg);
+        }
+        for arg in &mut call.named_args {
+            self.visit_named_argument(arg);
+        }
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &mut IfExpression) {
+        self.visit_expression(&mut if_expression.condition);
+
+        // the branch left out is emptied rather than removed, the span of the expression is the
+        // one of the branches
+        match (
+            truthiness(&if_expression.condition),
+            &mut if_expression.alternative,
+        ) {
+            (Some(true), Some(alternative)) => alternative.statements.clear(),
+            (Some(false), Some(alternative)) => {
+                let consequence = &mut if_expression.consequence.statements;
+                *consequence = std::mem::take(&mut alternative.statements);
+                if_expression.condition = literal_of(true, if_expression.condition.span());
+            }
+            // replaced by `null` in `visit_expression`
+            (Some(false), None) => if_expression.consequence.statements.clear(),
+            _ => {}
+        }
+
+        self.visit_block_statement(&mut if_expression.consequence);
+        if let Some(alternative) = &mut if_expression.alternative {
+            self.visit_block_statement(alternative);
+        }
+    }
+
+    fn visit_infix_expression(&mut self, infix: &mut InfixExpression) {
+        if infix.operator != "=" {
+            self.visit_expression(&mut infix.lhs);
+        }
+        self.visit_expression(&mut infix.rhs);
+    }
+}
+
+fn is_literal(expression: &ASTExpression) -> bool {
+    matches!(
+        expression.kind(),
+        ExpressionKind::Integer(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::Null(_)
+    )
+}
+
+/// The value of an expression on literals as a literal, `None` if it is not an integer or a
+/// boolean, e.g: an error.
+fn fold(expression: &ASTExpression) -> Option<ASTExpression> {
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let value = Evaluator::eval_expression(expression.as_ref(), environment).ok()?;
+    let span = expression.span();
+
+    if let Some(integer) = value.as_any_ref().downcast_ref::<Integer>() {
+        let token = Token::new(TokenType::INT, span);
+        return Some(Box::new(IntegerLiteral::new(token, integer.value)));
+    }
+
+    let boolean = value.as_any_ref().downcast_ref::<Boolean>()?;
+    Some(literal_of(boolean.value, span))
+}
+
+/// Whether a condition is always or never met, `None` if it depends on the evaluation.
+fn truthiness(condition: &ASTExpression) -> Option<bool> {
+    if !is_literal(condition) {
+        return None;
+    }
+
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let value = Evaluator::eval_expression(condition.as_ref(), environment).ok()?;
+    Some(Evaluator::is_truthy(&value))
+}
+
+fn literal_of(value: bool, span: Span) -> ASTExpression {
+    let keyword = if value {
+        KeywordTokenType::TRUE
+    } else {
+        KeywordTokenType::FALSE
+    };
+
+    Box::new(BooleanExpression::new(
+        Token::new(TokenType::KEYWORD(keyword), span),
+        value,
+    ))
+}
+
+/// A copy of `literal` at `span`, where the errors involving it point.
+fn relocate(literal: &ASTExpression, span: Span) -> ASTExpression {
+    let mut literal = literal.clone();
+
+    match literal.kind_mut() {
+        ExpressionKindMut::Integer(integer) => integer.token.span = span,
+        ExpressionKindMut::Boolean(boolean) => boolean.token.span = span,
+        ExpressionKindMut::String(string) => string.token.span = span,
+        ExpressionKindMut::Null(null) => null.token.span = span,
+        _ => {}
+    }
+
+    literal
+}
+
+/// How many times each name is declared, see `Optimizer::declarations`.
+struct Declarations(HashMap<String, usize>);
+
+impl Declarations {
+    fn count(&mut self, name: &Identifier) {
+        *self.0.entry(name.value.clone()).or_default() += 1;
+    }
+}
+
+impl<'ast> Visitor<'ast> for Declarations {
+    fn visit_declare_statement(&mut self, declare: &'ast DeclareStatement) {
+        self.count(&declare.name);
+        visit::walk_declare_statement(self, declare);
+    }
+
+    fn visit_function_literal(&mut self, function: &'ast FunctionLiteral) {
+        if let Some(name) = &function.name {
+            self.count(name);
+        }
+        visit::walk_function_literal(self, function);
+    }
+
+    fn visit_typed_identifier(&mut self, parameter: &'ast TypedIdentifier) {
+        self.count(&parameter.identifier);
+        visit::walk_typed_identifier(self, parameter);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        optimizer::{optimize, OptLevel},
+        parser::Parser,
+    };
+
+    fn optimized(input: &str, level: OptLevel) -> String {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer);
+        let mut program = parser.parse().unwrap();
+
+        optimize(&mut program, level);
+        program.to_string()
+    }
+
+    #[test]
+    fn test_optimize() {
+        let expected: Vec<(&str, &str)> = vec![
+            ("1 + 2 * 3;", "7"),
+            ("-(4 - 6) ** 2 == 4;", "true"),
+            ("!0 && 1 < 2;", "true"),
+            ("x + 2 * 3;", "(x + 6)"),
+            // kept for the error to be reported at runtime
+            ("1 / 0;", "(1 / 0)"),
+            (
+                "9_223_372_036_854_775_807 + 1;",
+                "(9223372036854775807 + 1)",
+            ),
+            (
+                "if (1 < 2) { x } else { y };",
+                "if true {\n    x\n} else{\n\n}",
+            ),
+            ("if !1 { x } else { y };", "if true {\n    y\n} else{\n\n}"),
+            ("if (null) { x };", "null"),
+            (
+                "let f = fn() { return 1; x; };",
+                "let f = fn (){\n    return 1;\n};",
+            ),
+            (
+                "const debug = false; if debug { x } else { y };",
+                "const debug = false;if true {\n    y\n} else{\n\n}",
+            ),
+            (
+                "const n = 2 * 3; let f = fn(x) { x * n }; n + 1;",
+                "const n = 6;let f = fn (x){\n    (x * 6)\n};7",
+            ),
+            // declared again, or used outside of the scope of the constant
+            ("const n = 1; let n = 2; n;", "const n = 1;let n = 2;n"),
+            (
+                "let f = fn(n) { n }; const n = 1; n;",
+                "let f = fn (n){\n    n\n};const n = 1;n",
+            ),
+            (
+                "let f = fn() { const n = 1; n }; let g = fn() { n };",
+                "let f = fn (){\n    const n = 1;    1\n};let g = fn (){\n    n\n};",
+            ),
+            // the callee is quoted by the error
+            ("const n = 1; n();", "const n = 1;n()"),
+        ];
+
+        for (input, output) in expected {
+            assert_eq!(optimized(input, OptLevel::O1), output, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_opt_level_0() {
+        const CODE: &str = "const n = 1 + 2; if true { n } else { 0 };";
+
+        assert_eq!(
+            optimized(CODE, OptLevel::O0),
+            "const n = (1 + 2);if true {\n    n\n} else{\n    0\n}"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Debug};
+use std::{collections::HashMap, fmt::Debug, rc::Rc};
 
 use crate::{
     ast::{
@@ -6,37 +6,56 @@ use crate::{
             boolean_expression::BooleanExpression, call_expression::CallExpression,
             function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
             infix_expression::InfixExpression, integer_literal::IntegerLiteral,
-            null_expression::NullExpression, prefix_expression::PrefixExpression,
-            string_literal::StringLiteral, typed_identifier::TypedIdentifier,
+            named_argument::NamedArgument, null_expression::NullExpression,
+            prefix_expression::PrefixExpression, string_literal::StringLiteral,
+            typed_identifier::TypedIdentifier,
         },
         precedence::Precedence,
         statements::{
-            block_statement::BlockStatement, declare_statement::DeclareStatement,
-            expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+            attributed_statement::{Attribute, AttributedStatement},
+            block_statement::BlockStatement,
+            declare_statement::DeclareStatement,
+            expression_statement::ExpressionStatement,
+            return_statement::ReturnStatement,
         },
     },
     lexer::Lexer,
+    lint::{self, Level, LintAttribute, LINTS, WARNINGS},
     log::{
+        diagnostic::TextEdit,
         error::{parser::ParserError, parser::ParserErrorCode},
+        suggestion::find_best_match,
         warning::ParserWarning,
     },
     program::Program,
+    source::Span,
     token::{
         token_type::{KeywordTokenType, TokenType},
         Token,
     },
+    traits::node::Node,
     types::{
         ASTExpression, ASTExpressionResult, ASTStatement, ASTStatementResult, InfixParseFn,
         ParserResult, PrefixParseFn, Result,
     },
 };
 
+/// Parsing stops once that many errors were reported, past this point they are most likely
+/// caused by the previous ones.
+pub const MAX_ERRORS: usize = 25;
+
 pub struct Parser<'a> {
     pub lexer: &'a mut Lexer,
     current_token: Token,
     peek_token: Token,
     pub errors: Vec<ParserError>,
     pub warnings: Vec<ParserWarning>,
+    /// The lint levels set by the `@allow`, `@warn` and `@deny` attributes.
+    pub lint_attributes: Vec<LintAttribute>,
+    /// The spans of the parenthesized expressions, parentheses included. The AST drops them
+    /// but the syntax tree keeps them.
+    pub groups: Vec<Span>,
+    pub max_errors: usize,
     prefix_fns: HashMap<TokenType, PrefixParseFn<'a>>,
     infix_fns: HashMap<TokenType, InfixParseFn<'a>>,
     current_delimiter: Option<TokenType>,
@@ -46,14 +65,20 @@ pub struct Parser<'a> {
 
 impl<'a> Debug for Parser<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{:?}", self)
+        f.debug_struct("Parser")
+            .field("current_token", &self.current_token)
+            .field("peek_token", &self.peek_token)
+            .field("errors", &self.errors)
+            .field("warnings", &self.warnings)
+            .finish()
     }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(lexer: &'a mut Lexer) -> Self {
-        let current_token = lexer.scan();
-        let peek_token = lexer.scan();
+        let mut errors = Vec::new();
+        let current_token = Self::scan_token(lexer, &mut errors);
+        let peek_token = Self::scan_token(lexer, &mut errors);
         let prefix_fns = HashMap::new();
         let infix_fns = HashMap::new();
 
@@ -61,8 +86,11 @@ impl<'a> Parser<'a> {
             lexer,
             current_token,
             peek_token,
-            errors: Vec::new(),
+            errors,
             warnings: Vec::new(),
+            lint_attributes: Vec::new(),
+            groups: Vec::new(),
+            max_errors: MAX_ERRORS,
             prefix_fns,
             infix_fns,
             current_delimiter: None,
@@ -162,10 +190,24 @@ impl<'a> Parser<'a> {
 
     fn advance_token(&mut self) {
         self.current_token = self.peek_token.clone();
-        self.peek_token = self.lexer.scan();
+        self.peek_token = Self::scan_token(self.lexer, &mut self.errors);
         self.maybe_save_current_delimiter();
     }
 
+    /// Scans the next token, reporting and skipping over the `ILLEGAL` ones so
+    /// that a lexical error does not end the parsing of the whole program.
+    fn scan_token(lexer: &mut Lexer, errors: &mut Vec<ParserError>) -> Token {
+        loop {
+            let token = lexer.next_token();
+
+            errors.extend(lexer.errors.drain(..).map(ParserError::from));
+
+            if token.t != TokenType::ILLEGAL {
+                return token;
+            }
+        }
+    }
+
     fn peek_token_is(&self, t: TokenType) -> bool {
         self.peek_token.t == t
     }
@@ -217,30 +259,27 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse(&mut self) -> Result<Program> {
-        let mut new_program = Program::new();
+        let mut new_program = Program::new(self.current_token.span.clone());
 
         loop {
+            if self.errors.len() >= self.max_errors {
+                self.errors.truncate(self.max_errors);
+                self.errors.push(ParserError {
+                    code: ParserErrorCode::TooManyErrors {
+                        token: self.current_token.clone(),
+                        limit: self.max_errors,
+                    },
+                    source: None,
+                });
+                break;
+            }
+
             match self.current_token.t {
-                TokenType::EOF | TokenType::ILLEGAL => break,
+                TokenType::EOF => break,
                 TokenType::SEMICOLON => {
                     self.advance_token();
                     continue;
                 }
-                TokenType::LBRACE => match self.parse_block_statement() {
-                    Ok(stmt) => {
-                        new_program.statements.push(Box::new(stmt));
-                        self.dbg_untrace("parse_block_statement");
-                    }
-                    Err(err) => {
-                        self.errors.push(err);
-                        let current_line = self.current_token.line;
-                        while current_line == self.current_token.line
-                            && !self.current_token_is(TokenType::EOF)
-                        {
-                            self.advance_token();
-                        }
-                    }
-                },
                 _ => {
                     self.dbg_trace(
                         format!("parse_statement {}", new_program.statements.len() + 1).as_str(),
@@ -249,22 +288,110 @@ impl<'a> Parser<'a> {
                         Ok(stmt) => {
                             new_program.statements.push(stmt);
                             self.dbg_untrace("parse_statement");
+                            self.advance_token();
                         }
                         Err(err) => {
                             self.errors.push(err);
-                            let current_line = self.current_token.line;
-                            while current_line == self.current_token.line {
+                            self.synchronize();
+
+                            // there is no block to close at the top level
+                            if self.current_token_is(TokenType::RBRACE) {
                                 self.advance_token();
                             }
                         }
                     }
+                }
+            }
+        }
+
+        new_program.span = new_program.span.to(&self.current_token.span);
+
+        Ok(new_program)
+    }
 
+    /// Panic-mode recovery: skips the tokens of the statement that failed to parse, stopping
+    /// after a `;`, before a `}` or before a keyword starting a new statement. Delimited groups
+    /// are skipped as a whole so that their content cannot stop the recovery.
+    fn synchronize(&mut self) {
+        loop {
+            match self.current_token.t {
+                TokenType::EOF | TokenType::RBRACE => return,
+                TokenType::SEMICOLON => {
                     self.advance_token();
+                    return;
+                }
+                TokenType::LPAREN | TokenType::LBRACK | TokenType::LBRACE => {
+                    self.skip_delimited();
                 }
+                _ => (),
+            }
+
+            if self.current_token_is(TokenType::EOF) {
+                return;
+            }
+
+            let starts_statement = Self::starts_statement(&self.peek_token.t);
+            self.advance_token();
+
+            if starts_statement {
+                return;
             }
         }
+    }
 
-        Ok(new_program)
+    /// Moves from an opening delimiter to its matching closing one. A `(` or `[` cannot be
+    /// closed past a `;` or a keyword starting a statement, the skip then stops before them as
+    /// it stops before the end of file.
+    fn skip_delimited(&mut self) {
+        let mut closing: Vec<TokenType> = Vec::new();
+
+        loop {
+            match self.current_token.t {
+                TokenType::LPAREN | TokenType::LBRACK | TokenType::LBRACE => {
+                    closing.extend(TokenType::matching_delimiter(self.current_token.t.clone()));
+                }
+                ref t if closing.last() == Some(t) => {
+                    closing.pop();
+                }
+                _ => (),
+            }
+
+            let Some(innermost) = closing.last() else {
+                return;
+            };
+            if self.peek_token_is(TokenType::EOF) {
+                return;
+            }
+
+            // `fn` and `if` are expressions too
+            let unclosed = *innermost != TokenType::RBRACE
+                && (self.peek_token_is(TokenType::SEMICOLON)
+                    || Self::starts_statement(&self.peek_token.t)
+                        && !self.peek_token_is(TokenType::KEYWORD(KeywordTokenType::FUN))
+                        && !self.peek_token_is(TokenType::KEYWORD(KeywordTokenType::IF)));
+            if unclosed {
+                return;
+            }
+
+            self.advance_token();
+        }
+    }
+
+    fn starts_statement(t: &TokenType) -> bool {
+        matches!(
+            t,
+            TokenType::AT
+                | TokenType::KEYWORD(
+                    KeywordTokenType::LET
+                        | KeywordTokenType::CONST
+                        | KeywordTokenType::VAR
+                        | KeywordTokenType::RETURN
+                        | KeywordTokenType::DEFER
+                        | KeywordTokenType::ERRDEFER
+                        | KeywordTokenType::FUN
+                        | KeywordTokenType::IF
+                )
+        )
     }
 
     fn parse_statement(&mut self) -> ASTStatementResult {
@@ -273,10 +400,153 @@ impl<'a> Parser<'a> {
             | TokenType::KEYWORD(KeywordTokenType::CONST)
             | TokenType::KEYWORD(KeywordTokenType::VAR) => self.parse_declare_statement(),
             TokenType::KEYWORD(KeywordTokenType::RETURN) => self.parse_return_statement(),
+            TokenType::LBRACE => Ok(Box::new(self.parse_block_statement()?)),
+            TokenType::AT => self.parse_attributed_statement(),
+            TokenType::IDENT => match self.misspelled_keyword() {
+                Some(keyword) => Err(ParserError {
+                    code: ParserErrorCode::MisspelledKeyword {
+                        token: self.current_token.clone(),
+                        keyword,
+                    },
+                    source: None,
+                }),
+                None => self.parse_expression_statement(),
+            },
             _ => self.parse_expression_statement(),
         }
     }
 
+    fn parse_attributed_statement(&mut self) -> ASTStatementResult {
+        self.dbg_trace("parse_attributed_statement");
+
+        let current_token = self.current_token.clone(); // @
+        let mut attributes: Vec<Attribute> = Vec::new();
+
+        while self.current_token_is(TokenType::AT) {
+            attributes.push(self.parse_attribute()?);
+            self.advance_token();
+        }
+
+        let statement = self.parse_statement()?;
+        let statement = AttributedStatement::new(current_token, attributes, statement);
+
+        for attribute in &statement.attributes {
+            self.lint_attributes.push(LintAttribute {
+                // the name was checked by `parse_attribute`
+                level: Level::from_name(&attribute.name.value).unwrap(),
+                lints: attribute
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.value.clone())
+                    .collect(),
+                span: attribute.span.clone(),
+                scope: statement.span(),
+            });
+        }
+
+        self.dbg_untrace("parse_attributed_statement");
+        Ok(Box::new(statement))
+    }
+
+    /// `@name(argument, ...)`, ends on the closing parenthesis.
+    fn parse_attribute(&mut self) -> ParserResult<Attribute> {
+        let current_token = self.current_token.clone(); // @
+
+        if !self.expect_peek_token_to_be(TokenType::IDENT) {
+            return Err(ParserError {
+                code: ParserErrorCode::UnexpectedToken {
+                    token: self.peek_token.clone(),
+                    expected_token_types: vec![TokenType::IDENT],
+                },
+                source: None,
+            });
+        }
+
+        let name = Identifier::from_token(&self.current_token);
+        if Level::from_name(&name.value).is_none() {
+            return Err(ParserError {
+                code: ParserErrorCode::UnknownAttribute {
+                    token: self.current_token.clone(),
+                },
+                source: None,
+            });
+        }
+
+        if !self.expect_peek_token_to_be(TokenType::LPAREN) {
+            return Err(ParserError {
+                code: ParserErrorCode::UnexpectedToken {
+                    token: self.peek_token.clone(),
+                    expected_token_types: vec![TokenType::LPAREN],
+                },
+                source: None,
+            });
+        }
+
+        let mut arguments: Vec<Identifier> = Vec::new();
+        loop {
+            if !self.expect_peek_token_to_be(TokenType::IDENT) {
+                return Err(ParserError {
+                    code: ParserErrorCode::UnexpectedToken {
+                        token: self.peek_token.clone(),
+                        expected_token_types: vec![TokenType::IDENT],
+                    },
+                    source: None,
+                });
+            }
+
+            let argument = Identifier::from_token(&self.current_token);
+            if !lint::is_known(&argument.value) {
+                let names = LINTS.iter().map(|lint| lint.name).chain([WARNINGS]);
+
+                self.warn(ParserWarning::UnknownLint {
+                    token: self.current_token.clone(),
+                    suggestion: find_best_match(&argument.value, names).map(str::to_string),
+                });
+            }
+            arguments.push(argument);
+
+            if !self.expect_peek_token_to_be(TokenType::COMMA) {
+                break;
+            }
+        }
+
+        if !self.expect_peek_token_to_be(TokenType::RPAREN) {
+            return Err(ParserError {
+                code: ParserErrorCode::UnexpectedToken {
+                    token: self.peek_token.clone(),
+                    expected_token_types: vec![TokenType::COMMA, TokenType::RPAREN],
+                },
+                source: None,
+            });
+        }
+
+        let span = current_token.span.to(&self.current_token.span);
+        Ok(Attribute::new(current_token, name, arguments, span))
+    }
+
+    /// The keyword the identifier starting the statement was probably meant to be, e.g: `retrun`
+    /// in `retrun 5;`. Only checked when an operand directly follows the identifier, which can
+    /// never be valid code.
+    fn misspelled_keyword(&self) -> Option<KeywordTokenType> {
+        if !matches!(
+            self.peek_token.t,
+            TokenType::IDENT | TokenType::INT | TokenType::STRING
+        ) {
+            return None;
+        }
+
+        let keywords: Vec<String> = KeywordTokenType::ALL
+            .iter()
+            .map(KeywordTokenType::to_literal)
+            .collect();
+
+        find_best_match(
+            self.current_token.literal(),
+            keywords.iter().map(String::as_str),
+        )
+        .and_then(KeywordTokenType::from_str)
+    }
+
     fn parse_identifier(&mut self) -> ASTExpressionResult {
         self.dbg_trace_inline("parse_identifier");
         Ok(Box::new(Identifier::from_token(&self.current_token)))
@@ -294,63 +564,56 @@ impl<'a> Parser<'a> {
 
     fn parse_integer_literal(&mut self) -> ASTExpressionResult {
         self.dbg_trace_inline(
-            format!("parse_integer_literal: {}", self.current_token.literal).as_str(),
+            format!("parse_integer_literal: {}", self.current_token.literal()).as_str(),
         );
         Ok(Box::new(IntegerLiteral::from_token(&self.current_token)))
     }
 
     fn parse_string_literal(&mut self) -> ASTExpressionResult {
         self.dbg_trace_inline(
-            format!("parse_string_literal: {}", self.current_token.literal).as_str(),
+            format!("parse_string_literal: {}", self.current_token.literal()).as_str(),
         );
 
         Ok(Box::new(StringLiteral::new(
             self.current_token.clone(),
-            self.current_token.literal.as_str(),
+            self.current_token.literal(),
         )))
     }
 
     // TODO: REFACTOR THIS DISGUSTING FUNCTION PLEASSEE
     fn parse_if_expression(&mut self) -> ASTExpressionResult {
-        self.dbg_trace(format!("parse_if_expression: {}", self.current_token.literal).as_str());
+        self.dbg_trace(format!("parse_if_expression: {}", self.current_token.literal()).as_str());
 
         let current_token = self.current_token.clone(); // if
-        let mut surrounded_by_paren = false;
+        let mut opening_paren: Option<Token> = None;
 
         // optional parentheses around condition
         if self.peek_token_is(TokenType::LPAREN) {
             self.advance_token();
-            self.warn(ParserWarning::UnnecessaryParentheses {
-                token: self.peek_token.clone(),
-                context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
-            });
-            surrounded_by_paren = true;
-        } else if !self.peek_token_is(TokenType::IDENT)
-            && !self.peek_token_is(TokenType::KEYWORD(KeywordTokenType::TRUE))
-            && !self.peek_token_is(TokenType::KEYWORD(KeywordTokenType::FALSE))
-        {
+            opening_paren = Some(self.current_token.clone());
+        } else if !starts_bare_condition(&self.peek_token.t) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
                     token: self.peek_token.clone(),
                     expected_token_types: vec![
                         TokenType::IDENT,
                         TokenType::LPAREN,
+                        TokenType::BANG,
                         TokenType::KEYWORD(KeywordTokenType::TRUE),
                         TokenType::KEYWORD(KeywordTokenType::FALSE),
                     ],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
         }
 
+        let surrounded_by_paren = opening_paren.is_some();
         if surrounded_by_paren && self.peek_token_is(TokenType::RPAREN)
             || self.peek_token_is(TokenType::LBRACE)
         {
             return Err(ParserError {
                 code: ParserErrorCode::MissingIfCondition {
                     token: self.current_token.clone(),
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
@@ -367,10 +630,6 @@ impl<'a> Parser<'a> {
                     token: self.current_token.clone(),
                     expected_delimiter: TokenType::LPAREN,
                     current_delimiter: self.current_delimiter.clone(),
-                    context: Some(vec![self
-                        .lexer
-                        .get_line(self.current_token.line)
-                        .unwrap_or(String::new())]),
                 },
                 source: None,
             });
@@ -380,17 +639,19 @@ impl<'a> Parser<'a> {
                     token: self.peek_token.clone(),
                     expected_delimiter: TokenType::RPAREN,
                     current_delimiter: self.current_delimiter.clone(),
-                    context: Some(vec![self
-                        .lexer
-                        .get_line(self.peek_token.line)
-                        .unwrap_or(String::new())]),
                 },
                 source: None,
             });
         }
 
-        if surrounded_by_paren {
+        if let Some(token) = opening_paren {
             self.advance_token();
+            if starts_bare_condition(&condition_token.t) {
+                self.warn(ParserWarning::UnnecessaryParentheses {
+                    token,
+                    closing: self.current_token.clone(),
+                });
+            }
         }
 
         self.advance_token(); // first token of the condition expression
@@ -400,12 +661,12 @@ impl<'a> Parser<'a> {
                 code: ParserErrorCode::UnexpectedToken {
                     token: self.current_token.clone(),
                     expected_token_types: vec![TokenType::LBRACE],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
         }
 
+        let errors_count = self.errors.len();
         let consequence_token = self.current_token.clone();
         let consequence = self.parse_block_statement()?;
 
@@ -419,29 +680,44 @@ impl<'a> Parser<'a> {
             alternative = Some(self.parse_block_statement()?);
         }
 
-        if let Some(alt) = &alternative {
+        if self.errors.len() != errors_count {
+            // the branches may only look empty because of their errors
+        } else if let Some(alt) = &alternative {
             if alt.statements.is_empty() && consequence.statements.is_empty() {
                 self.warn(ParserWarning::EmptyIfExpression {
                     token: consequence_token,
-                    context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
                 });
             } else if consequence.statements.is_empty() {
+                let swap = match negate_condition(&condition) {
+                    Some(negation) if is_blank(&consequence) => vec![
+                        negation,
+                        TextEdit::new(consequence.span.to(&alt.span), alt.span.source_text()),
+                    ],
+                    _ => Vec::new(),
+                };
+
                 self.warn(ParserWarning::EmptyIfConsequenceBranch {
                     token: consequence_token,
-                    context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
                     has_alternative: true,
+                    swap,
                 });
             } else if alt.statements.is_empty() {
                 self.warn(ParserWarning::EmptyIfAlternativeBranch {
                     token: alternative_token.unwrap(),
-                    context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
+                    else_branch: is_blank(alt).then(|| {
+                        Span::new(
+                            Rc::clone(&consequence.span.file),
+                            consequence.span.end,
+                            alt.span.end,
+                        )
+                    }),
                 });
             }
         } else if consequence.statements.is_empty() {
             self.warn(ParserWarning::EmptyIfConsequenceBranch {
                 token: consequence_token,
-                context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
                 has_alternative: false,
+                swap: Vec::new(),
             });
         }
 
@@ -470,9 +746,8 @@ impl<'a> Parser<'a> {
         if !self.expect_peek_token_to_be(TokenType::LPAREN) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
+                    token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::LPAREN],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
@@ -489,7 +764,6 @@ impl<'a> Parser<'a> {
                 return Err(ParserError {
                     code: ParserErrorCode::MissingFnReturnType {
                         token: self.current_token.clone(),
-                        context: self.lexer.get_line(self.current_token.line),
                     },
                     source: None,
                 });
@@ -501,20 +775,20 @@ impl<'a> Parser<'a> {
         if !self.expect_peek_token_to_be(TokenType::LBRACE) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
+                    token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::LBRACE, TokenType::ARROW],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
         }
 
+        let errors_count = self.errors.len();
         let body = self.parse_block_statement()?;
 
-        if body.statements.is_empty() {
+        // a body whose statements failed to parse is not really empty
+        if body.statements.is_empty() && self.errors.len() == errors_count {
             self.warn(ParserWarning::EmptyFunction {
-                token: self.current_token.clone(),
-                context: vec![self.lexer.get_line(self.current_token.line).unwrap()],
+                token: current_token.clone(),
             });
         }
 
@@ -538,38 +812,14 @@ impl<'a> Parser<'a> {
         }
 
         self.advance_token();
+        parameters.push(self.parse_function_parameter()?);
 
-        let param_ident = Identifier::from_token(&self.current_token);
-        let mut param_t: Option<Identifier> = None;
-        if self.peek_token_is(TokenType::COLON) {
+        // a rest parameter comes last
+        while self.peek_token_is(TokenType::COMMA) && parameters.last().unwrap().rest.is_none() {
             self.advance_token();
             self.advance_token();
 
-            param_t = Some(Identifier::from_token(&self.current_token));
-        }
-
-        let param = TypedIdentifier::new(param_ident, param_t);
-        parameters.push(param);
-
-        loop {
-            if !self.peek_token_is(TokenType::COMMA) {
-                break;
-            }
-
-            self.advance_token();
-            self.advance_token();
-
-            let ident = Identifier::from_token(&self.current_token);
-
-            let mut t: Option<Identifier> = None;
-            if self.peek_token_is(TokenType::COLON) {
-                self.advance_token();
-                self.advance_token();
-
-                t = Some(Identifier::from_token(&self.current_token));
-            }
-
-            parameters.push(TypedIdentifier::new(ident, t));
+            parameters.push(self.parse_function_parameter()?);
         }
 
         if !self.expect_peek_token_to_be(TokenType::RPAREN) {
@@ -577,7 +827,6 @@ impl<'a> Parser<'a> {
                 code: ParserErrorCode::UnexpectedToken {
                     token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::RPAREN],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
@@ -586,46 +835,87 @@ impl<'a> Parser<'a> {
         Ok(parameters)
     }
 
+    /// `name`, `name: type`, `name = default` or `..rest`.
+    fn parse_function_parameter(&mut self) -> ParserResult<TypedIdentifier> {
+        let mut rest: Option<Token> = None;
+        if self.current_token_is(TokenType::RANGE) {
+            rest = Some(self.current_token.clone());
+            self.advance_token();
+        }
+
+        let mut parameter = TypedIdentifier::new(Identifier::from_token(&self.current_token), None);
+        parameter.rest = rest;
+
+        if self.peek_token_is(TokenType::COLON) {
+            self.advance_token();
+            self.advance_token();
+
+            parameter.t = Some(Identifier::from_token(&self.current_token));
+        }
+
+        if parameter.rest.is_none() && self.peek_token_is(TokenType::ASSIGN) {
+            self.advance_token();
+            self.advance_token();
+
+            parameter.default = Some(self.parse_expression(Precedence::LOWEST)?);
+        }
+
+        Ok(parameter)
+    }
+
     fn parse_call_expression(&mut self, function: ASTExpression) -> ASTExpressionResult {
-        let args = self.parse_call_arguments()?;
-        let call_exp = CallExpression::new(self.current_token.clone(), function, args);
+        let (args, named_args) = self.parse_call_arguments()?;
+        let call_exp = CallExpression::new(self.current_token.clone(), function, args, named_args);
         Ok(Box::new(call_exp))
     }
 
-    fn parse_call_arguments(&mut self) -> ParserResult<Vec<ASTExpression>> {
+    fn parse_call_arguments(&mut self) -> ParserResult<(Vec<ASTExpression>, Vec<NamedArgument>)> {
         let mut args: Vec<ASTExpression> = Vec::new();
+        let mut named_args: Vec<NamedArgument> = Vec::new();
 
         if self.peek_token_is(TokenType::RPAREN) {
             self.advance_token();
-            return Ok(args);
+            return Ok((args, named_args));
         }
 
-        self.advance_token();
-        args.push(self.parse_expression(Precedence::LOWEST)?);
-
         loop {
+            self.advance_token();
+
+            if self.current_token_is(TokenType::IDENT) && self.peek_token_is(TokenType::ASSIGN) {
+                let name = Identifier::from_token(&self.current_token);
+                self.advance_token();
+                self.advance_token();
+
+                let value = self.parse_expression(Precedence::LOWEST)?;
+                named_args.push(NamedArgument::new(name, value));
+            } else if !named_args.is_empty() {
+                return Err(ParserError {
+                    code: ParserErrorCode::PositionalAfterNamedArgument {
+                        token: self.current_token.clone(),
+                    },
+                    source: None,
+                });
+            } else {
+                args.push(self.parse_expression(Precedence::LOWEST)?);
+            }
+
             if !self.peek_token_is(TokenType::COMMA) {
                 break;
             }
-
-            self.advance_token();
             self.advance_token();
-
-            args.push(self.parse_expression(Precedence::LOWEST)?);
         }
 
         if !self.expect_peek_token_to_be(TokenType::RPAREN) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
+                    token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::RPAREN],
-                    context: self.lexer.get_line(self.peek_token.line),
                 },
                 source: None,
             });
         }
 
-        Ok(args)
+        Ok((args, named_args))
     }
 
     // double cloning eww :/
@@ -641,15 +931,11 @@ impl<'a> Parser<'a> {
 
                 Ok(Box::new(PrefixExpression::new(
                     current_token.clone(),
-                    current_token.literal,
+                    current_token.literal().to_string(),
                     rhs,
                 )))
             }
-            Err(err) => {
-                self.errors.push(err.clone());
-                self.advance_token();
-                return Err(err);
-            }
+            Err(err) => Err(err),
         }
     }
 
@@ -666,7 +952,7 @@ impl<'a> Parser<'a> {
         Ok(Box::new(InfixExpression::new(
             current_token.clone(),
             lhs,
-            current_token.literal,
+            current_token.literal().to_string(),
             rhs,
         )))
     }
@@ -686,7 +972,6 @@ impl<'a> Parser<'a> {
                     code: ParserErrorCode::UnexpectedToken {
                         token: self.current_token.clone(),
                         expected_token_types: vec![TokenType::RBRACE],
-                        context: self.lexer.get_line(self.current_token.line),
                     },
                     source: None,
                 });
@@ -695,19 +980,26 @@ impl<'a> Parser<'a> {
             match self.parse_statement() {
                 Ok(stmt) => {
                     statements.push(stmt);
+                    self.advance_token();
+                }
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize();
                 }
-                _ => (),
             };
-
-            self.advance_token();
         }
 
-        let stmt = BlockStatement::new(current_token.clone(), statements);
+        let stmt = BlockStatement::new(
+            current_token.clone(),
+            statements,
+            current_token.span.to(&self.current_token.span),
+        );
 
         self.dbg_untrace("parse_block_expression");
         Ok(stmt)
     }
 
+    #[allow(clippy::needless_return)]
     fn parse_return_statement(&mut self) -> ASTStatementResult {
         self.dbg_trace("parse_return_statement");
         let current_token = &self.current_token.clone();
@@ -725,9 +1017,8 @@ impl<'a> Parser<'a> {
                 if !self.expect_peek_token_to_be(TokenType::SEMICOLON) {
                     return Err(ParserError {
                         code: ParserErrorCode::UnexpectedToken {
-                            token: self.current_token.clone(),
-                            expected_token_types: vec![TokenType::IDENT],
-                            context: self.lexer.get_line(self.peek_token.line),
+                            token: self.peek_token.clone(),
+                            expected_token_types: vec![TokenType::SEMICOLON],
                         },
                         source: None,
                     });
@@ -776,7 +1067,6 @@ impl<'a> Parser<'a> {
                         code: ParserErrorCode::UnexpectedToken {
                             token: self.current_token.clone(),
                             expected_token_types: vec![TokenType::EOF],
-                            context: self.lexer.get_line(self.current_token.line),
                         },
                         source: None,
                     });
@@ -805,7 +1095,6 @@ impl<'a> Parser<'a> {
             Err(ParserError {
                 code: ParserErrorCode::UnknownPrefixToken {
                     token: self.current_token.clone(),
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             })
@@ -814,24 +1103,25 @@ impl<'a> Parser<'a> {
 
     fn parse_grouped_expression(&mut self) -> ASTExpressionResult {
         self.dbg_trace("parse_grouped_expression");
+        let opening_paren = self.current_token.span.clone();
         self.advance_token();
 
-        let exp = self.parse_expression(Precedence::LOWEST);
+        let exp = self.parse_expression(Precedence::LOWEST)?;
 
         if !self.peek_token_is(TokenType::RPAREN) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
+                    token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::RPAREN],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
         }
 
         self.advance_token();
+        self.groups.push(opening_paren.to(&self.current_token.span));
         self.dbg_untrace("parse_grouped_expression");
-        exp
+        Ok(exp)
     }
 
     fn parse_declare_statement(&mut self) -> ASTStatementResult {
@@ -839,7 +1129,7 @@ impl<'a> Parser<'a> {
         self.dbg_trace(
             format!(
                 "parse_{}_statement",
-                self.current_token.literal.clone().to_lowercase()
+                self.current_token.literal().to_lowercase()
             )
             .as_str(),
         );
@@ -847,9 +1137,8 @@ impl<'a> Parser<'a> {
         if !self.expect_peek_token_to_be(TokenType::IDENT) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
+                    token: self.peek_token.clone(),
                     expected_token_types: vec![TokenType::IDENT],
-                    context: self.lexer.get_line(self.current_token.line),
                 },
                 source: None,
             });
@@ -864,11 +1153,7 @@ impl<'a> Parser<'a> {
             self.advance_token();
 
             self.dbg_untrace(
-                format!(
-                    "parse_{}_statement",
-                    current_token.literal.clone().to_lowercase()
-                )
-                .as_str(),
+                format!("parse_{}_statement", current_token.literal().to_lowercase()).as_str(),
             );
 
             return Ok(Box::new(DeclareStatement::new(
@@ -880,9 +1165,8 @@ impl<'a> Parser<'a> {
         } else if !self.peek_token_is(TokenType::ASSIGN) {
             return Err(ParserError {
                 code: ParserErrorCode::UnexpectedToken {
-                    token: self.current_token.clone(),
-                    expected_token_types: vec![TokenType::SEMICOLON],
-                    context: self.lexer.get_line(self.current_token.line),
+                    token: self.peek_token.clone(),
+                    expected_token_types: vec![TokenType::ASSIGN, TokenType::SEMICOLON],
                 },
                 source: None,
             });
@@ -895,19 +1179,14 @@ impl<'a> Parser<'a> {
                 if !self.expect_peek_token_to_be(TokenType::SEMICOLON) {
                     return Err(ParserError {
                         code: ParserErrorCode::UnexpectedToken {
-                            token: self.current_token.clone(),
+                            token: self.peek_token.clone(),
                             expected_token_types: vec![TokenType::SEMICOLON],
-                            context: self.lexer.get_line(self.current_token.line),
                         },
                         source: None,
                     });
                 }
                 self.dbg_untrace(
-                    format!(
-                        "parse_{}_statement",
-                        current_token.literal.clone().to_lowercase()
-                    )
-                    .as_str(),
+                    format!("parse_{}_statement", current_token.literal().to_lowercase()).as_str(),
                 );
                 Ok(Box::new(DeclareStatement::new(
                     current_token.clone(),
@@ -924,7 +1203,7 @@ impl<'a> Parser<'a> {
         if self.peek_token_is(TokenType::COLON) {
             self.advance_token();
             if self.expect_peek_token_to_be(TokenType::IDENT) {
-                Some(self.current_token.clone().literal)
+                Some(self.current_token.literal().to_string())
             } else {
                 None
             }
@@ -966,5 +1245,63 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// Whether an if condition starting with a token of type `t` can be written without parentheses,
+/// the other conditions are wrapped in parentheses as a whole.
+pub fn starts_bare_condition(t: &TokenType) -> bool {
+    matches!(
+        t,
+        TokenType::IDENT
+            | TokenType::BANG
+            | TokenType::KEYWORD(KeywordTokenType::TRUE)
+            | TokenType::KEYWORD(KeywordTokenType::FALSE)
+    )
+}
+
+/// Whether nothing but whitespace is written between the braces of `block`, removing a block
+/// with no statements would otherwise lose its comments.
+fn is_blank(block: &BlockStatement) -> bool {
+    let text = block.span.source_text();
+    text.len() < 2 || text[1..text.len() - 1].trim().is_empty()
+}
+
+/// The edit negating the condition of an if expression in place, only the conditions that can
+/// be negated without adding parentheses are handled.
+pub fn negate_condition(condition: &ASTExpression) -> Option<TextEdit> {
+    let condition_span = condition.span();
+    let condition = condition.as_any();
+
+    if let Some(boolean) = condition.downcast_ref::<BooleanExpression>() {
+        let negated = if boolean.value { "false" } else { "true" };
+        Some(TextEdit::new(boolean.token.span.clone(), negated))
+    } else if condition.is::<Identifier>() || condition.is::<CallExpression>() {
+        let span = condition_span;
+        Some(TextEdit::new(
+            Span::new(Rc::clone(&span.file), span.start, span.start),
+            "!",
+        ))
+    } else if let Some(prefix) = condition.downcast_ref::<PrefixExpression>() {
+        (prefix.operator == "!").then(|| TextEdit::delete(prefix.token.span.clone()))
+    } else if let Some(infix) = condition.downcast_ref::<InfixExpression>() {
+        let negated = match infix.operator.as_str() {
+            "==" => "!=",
+            "!=" => "==",
+            "<" => ">=",
+            ">=" => "<",
+            ">" => "<=",
+            "<=" => ">",
+            _ => return None,
+        };
+        Some(TextEdit::new(infix.token.span.clone(), negated))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
+#[allow(
+    clippy::clone_on_copy,
+    clippy::needless_borrow,
+    clippy::redundant_static_lifetimes,
+    clippy::to_string_in_format_args
+)]
 mod test;
@@ -8,11 +8,12 @@ pub mod tests {
                 prefix_expression::PrefixExpression,
             },
             statements::{
-                declare_statement::DeclareStatement, expression_statement::ExpressionStatement,
-                return_statement::ReturnStatement,
+                attributed_statement::AttributedStatement, declare_statement::DeclareStatement,
+                expression_statement::ExpressionStatement, return_statement::ReturnStatement,
             },
         },
         lexer::Lexer,
+        log::error::parser::ParserErrorCode,
         parser::Parser,
         token::token_type::{KeywordTokenType, TokenType},
         traits::node::Node,
@@ -399,6 +400,289 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_parameters_and_named_arguments() {
+        const CODE: &str = "fn(x, y: i32 = x * 2, ..rest) { x }; f(1, y = 2, z = 3);";
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+
+        let program = parser.parse().unwrap();
+
+        assert!(parser.errors.is_empty(), "{:#?}", parser.errors);
+
+        let fn_stmt = test_downcast_expression_statement_helper(&program.statements[0]);
+        let fn_exp = downcast_expression_helper::<FunctionLiteral>(&fn_stmt.expression);
+        let parameters: Vec<(String, String, bool)> = fn_exp
+            .parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.to_string(),
+                    parameter.span().source_text().to_string(),
+                    parameter.rest.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![
+                ("x".to_string(), "x".to_string(), false),
+                (
+                    "y: i32 = (x * 2)".to_string(),
+                    "y: i32 = x * 2".to_string(),
+                    false
+                ),
+                ("..rest".to_string(), "..rest".to_string(), true),
+            ]
+        );
+
+        let call_stmt = test_downcast_expression_statement_helper(&program.statements[1]);
+        let call_exp = downcast_expression_helper::<CallExpression>(&call_stmt.expression);
+        assert_eq!(call_exp.args.len(), 1);
+        assert_eq!(call_exp.named_args.len(), 2);
+        assert_eq!(call_exp.named_args[1].span().source_text(), "z = 3");
+        assert_eq!(call_exp.to_string(), "f(1, y = 2, z = 3)");
+    }
+
+    #[test]
+    fn test_misplaced_arguments() {
+        const CODES: [(&str, usize, &str); 2] = [
+            ("f(y = 2, 1);", 1010, "1"),
+            ("fn(..rest, x) { rest };", 1000, ","),
+        ];
+
+        for (code, id, token) in CODES {
+            let mut lexer = Lexer::new(code);
+            let mut parser = Parser::new(&mut lexer);
+            parser.parse().unwrap();
+
+            let error = &parser.errors[0];
+            assert_eq!(error.code.id(), id, "{}", code);
+            assert_eq!(
+                error.code.token().unwrap().span.source_text(),
+                token,
+                "{}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_node_spans() {
+        const CODE: &str = r#"
+            let age = 20 + 3;
+            add(age, 1)
+            if age > 18 { true } else { false }
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+
+        let program = parser.parse().unwrap();
+
+        assert!(parser.errors.is_empty(), "{:#?}", parser.errors);
+
+        let declare_stmt = downcast_statement_helper::<DeclareStatement>(&program.statements[0]);
+        assert_eq!(declare_stmt.span().source_text(), "let age = 20 + 3");
+        assert_eq!(
+            declare_stmt.value.as_ref().unwrap().span().source_text(),
+            "20 + 3"
+        );
+        assert_eq!(declare_stmt.span().get_location(), "<anonymous>:2:13");
+
+        let call_stmt = test_downcast_expression_statement_helper(&program.statements[1]);
+        assert_eq!(call_stmt.span().source_text(), "add(age, 1)");
+
+        let if_stmt = test_downcast_expression_statement_helper(&program.statements[2]);
+        assert_eq!(
+            if_stmt.span().source_text(),
+            "if age > 18 { true } else { false }"
+        );
+        assert_eq!(if_stmt.span().line(), 4);
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        const CODE: &str = r#"
+            let a = 1
+            let b = (2 + 3;
+            let = 4;
+            fn add(x, y) {
+                return x +;
+                x + y
+            }
+            let c = add(1,
+                2;
+            let ok = 5;
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+
+        let program = parser.parse().unwrap();
+
+        // one error per mistake, located on the offending token
+        let errors: Vec<(usize, String)> = parser
+            .errors
+            .iter()
+            .map(|err| (err.code.id(), err.code.token().unwrap().get_location()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (1000, "<anonymous>:3:13".to_string()),
+                (1000, "<anonymous>:3:27".to_string()),
+                (1000, "<anonymous>:4:17".to_string()),
+                (1001, "<anonymous>:6:27".to_string()),
+                (1000, "<anonymous>:10:18".to_string()),
+            ]
+        );
+
+        // the statements following the mistakes are still parsed
+        assert_eq!(program.statements.len(), 2);
+        assert_eq!(program.statements[1].to_string(), "let ok = 5;");
+
+        let add = test_downcast_expression_statement_helper(&program.statements[0]);
+        let function = downcast_expression_helper::<FunctionLiteral>(&add.expression);
+        assert_eq!(function.body.statements.len(), 1);
+    }
+
+    #[test]
+    fn test_error_recovery_unclosed_delimiters() {
+        const CODE: &str = r#"let f = [1;
+let g = 3 +;
+let h = );
+let i = 2 +;
+let j = (1, [2, fn() { 3; }
+return 4 +;
+let ok = 5;"#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+
+        let program = parser.parse().unwrap();
+
+        // an unclosed group stops at the `;` or the statement after it
+        let lines: Vec<usize> = parser
+            .errors
+            .iter()
+            .map(|err| err.code.token().unwrap().span.line())
+            .collect();
+        assert_eq!(lines, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(program.statements.len(), 1);
+        assert_eq!(program.statements[0].to_string(), "let ok = 5;");
+    }
+
+    #[test]
+    fn test_error_recovery_is_capped() {
+        let code = "let = 1;\n".repeat(10);
+
+        let mut lexer = Lexer::new(&code);
+        let mut parser = Parser::new(&mut lexer);
+        parser.max_errors = 3;
+
+        parser.parse().unwrap();
+
+        let error_codes: Vec<usize> = parser.errors.iter().map(|err| err.code.id()).collect();
+        assert_eq!(error_codes, vec![1000, 1000, 1000, 1006]);
+    }
+
+    #[test]
+    fn test_misspelled_keywords() {
+        const CODE: &str = r#"
+            fun add(a, b) { a + b };
+            let double = fn(x) {
+                retrun x * 2;
+            };
+            lett a = 5;
+            cnst b = 1;
+            iff a > b { a }
+            foo bar;
+            fun(1);
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        parser.parse().unwrap();
+
+        let suggestions: Vec<(String, String)> = parser
+            .errors
+            .iter()
+            .map(|err| match &err.code {
+                ParserErrorCode::MisspelledKeyword { token, keyword } => {
+                    (token.literal().to_string(), keyword.to_literal())
+                }
+                code => panic!("Unexpected error: {:?}", code),
+            })
+            .collect();
+
+        // `foo bar` is not close to any keyword and `fun(1)` is a valid call
+        assert_eq!(
+            suggestions,
+            vec![
+                ("fun".to_string(), "fn".to_string()),
+                ("retrun".to_string(), "return".to_string()),
+                ("lett".to_string(), "let".to_string()),
+                ("cnst".to_string(), "const".to_string()),
+                ("iff".to_string(), "if".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attributed_statements() {
+        const CODE: &str = r#"
+            @allow(unnecessary_parens, empty_if)
+            let a = if (true) {} else {};
+            @deny(warnings) @warn(empty_function) fn f() {}
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        assert!(parser.errors.is_empty(), "{:?}", parser.errors);
+        assert_eq!(program.statements.len(), 2);
+
+        let expected_attributes = [
+            vec!["@allow(unnecessary_parens, empty_if)"],
+            vec!["@deny(warnings)", "@warn(empty_function)"],
+        ];
+        for (statement, expected) in program.statements.iter().zip(expected_attributes) {
+            let statement = downcast_statement_helper::<AttributedStatement>(statement);
+            let attributes: Vec<String> = statement
+                .attributes
+                .iter()
+                .map(|attribute| attribute.to_string())
+                .collect();
+
+            assert_eq!(attributes, expected);
+        }
+
+        assert_eq!(parser.lint_attributes.len(), 3);
+        assert_eq!(
+            parser.lint_attributes[0].scope.source_text(),
+            "@allow(unnecessary_parens, empty_if)\n            let a = if (true) {} else {}"
+        );
+    }
+
+    #[test]
+    fn test_invalid_attributes() {
+        const CODE: &str = r#"
+            @inline fn f() { 1 }
+            @allow fn g() { 1 }
+            @deny(1) fn h() { 1 }
+        "#;
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        parser.parse().unwrap();
+
+        let errors: Vec<usize> = parser.errors.iter().map(|err| err.code.id()).collect();
+        assert_eq!(errors, vec![1009, 1000, 1000]);
+    }
+
     fn test_downcast_expression_statement_helper(statement: &ASTStatement) -> &ExpressionStatement {
         match statement.as_any().downcast_ref::<ExpressionStatement>() {
             Some(exp_stmt) => exp_stmt,
@@ -1,14 +1,16 @@
-use crate::{traits::node::Node, types::ASTStatement};
+use crate::{source::Span, traits::node::Node, types::ASTStatement};
 
 #[derive(Debug)]
 pub struct Program {
     pub statements: Vec<ASTStatement>,
+    pub span: Span,
 }
 
 impl Program {
-    pub fn new() -> Self {
+    pub fn new(span: Span) -> Self {
         Self {
             statements: Vec::new(),
+            span,
         }
     }
 }
@@ -22,6 +24,10 @@ impl Node for Program {
         }
     }
 
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -35,6 +41,7 @@ impl Node for Program {
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for Program {
     fn to_string(&self) -> String {
         let mut s = String::new();
@@ -48,6 +55,7 @@ impl ToString for Program {
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_static_lifetimes)]
 mod tests {
     use crate::{lexer::Lexer, parser::Parser};
 
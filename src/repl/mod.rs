@@ -7,9 +7,14 @@ use std::{
 use crate::{
     evaluator::Evaluator,
     lexer::Lexer,
+    lint::{self, LintLevels},
+    log::diagnostic::Severity,
     objects::environment::Environment,
+    optimizer::{self, OptLevel},
     parser::Parser,
-    traits::{node::Node, object::ObjectType},
+    source::SourceMap,
+    traits::object::ObjectType,
+    type_checker,
 };
 
 pub struct REPL {
@@ -18,9 +23,13 @@ pub struct REPL {
     pub index: usize,
     pub is_running: bool,
     pub environment: Rc<RefCell<Environment>>,
+    pub source_map: SourceMap,
+    pub lint_levels: LintLevels,
+    pub opt_level: OptLevel,
 }
 
 impl REPL {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             buffer: String::new(),
@@ -28,9 +37,13 @@ impl REPL {
             index: 1,
             is_running: false,
             environment: Rc::new(RefCell::new(Environment::new(None))),
+            source_map: SourceMap::new(),
+            lint_levels: LintLevels::new(),
+            opt_level: OptLevel::default(),
         }
     }
 
+    #[allow(clippy::print_literal)]
     pub fn start(&mut self) {
         println!(
             "Interactive Eheh ({}) - press Ctrl+C to exit (type h() ENTER for help)",
@@ -70,27 +83,54 @@ impl REPL {
             return;
         }
 
-        let mut lexer = Lexer::new(&self.buffer);
+        let file = self
+            .source_map
+            .add_file(&format!("<repl:{}>", self.index - 1), &self.buffer);
+        let mut lexer = Lexer::from_source(file);
         let mut parser = Parser::new(&mut lexer);
 
-        let program = parser.parse().unwrap();
+        let mut program = match parser.parse() {
+            Ok(program) => program,
+            Err(err) => {
+                println!("{}", err);
+                return;
+            }
+        };
 
         if !parser.errors.is_empty() {
             for error in &parser.errors {
-                print!("{}", error);
+                println!("{}", error);
             }
             return;
         }
 
-        for warning in &parser.warnings {
-            println!("{}", warning);
+        parser.warnings.extend(lint::semantic::check(&program));
+
+        let mut denied = false;
+        for diagnostic in self
+            .lint_levels
+            .report(&parser.warnings, &parser.lint_attributes)
+        {
+            denied |= diagnostic.severity == Severity::Error;
+            println!("{}", diagnostic);
         }
 
-        let evaluated =
-            Evaluator::eval(Box::new(program.as_node()), self.environment.clone()).unwrap();
+        for error in type_checker::check(&program).errors {
+            println!("{}", error.to_warning());
+        }
 
-        if evaluated.t() != ObjectType::Null {
-            println!("{}\n", evaluated.to_string());
+        if denied {
+            return;
+        }
+
+        optimizer::optimize(&mut program, self.opt_level);
+
+        match Evaluator::eval(&program, self.environment.clone()) {
+            Ok(evaluated) if evaluated.t() != ObjectType::Null => {
+                println!("{}\n", evaluated.to_string());
+            }
+            Ok(_) => (),
+            Err(err) => println!("{}", err),
         }
     }
 
@@ -123,3 +163,6 @@ impl REPL {
         }
     }
 }
+
+#[cfg(test)]
+mod test;
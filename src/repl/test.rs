@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::Parser, repl::REPL, type_checker};
+
+    #[test]
+    fn test_type_errors_do_not_stop_evaluation() {
+        // `f` calls the `g` declared after it, not the integer the checker sees
+        const CODE: &str = "let g = 5;
+let f = fn() { g() };
+let g = fn() { 2 };
+let result = f();
+";
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        assert_eq!(type_checker::check(&program).errors[0].code.id(), 2001);
+
+        let mut repl = REPL::new();
+        repl.buffer = CODE.to_string();
+        repl.eval();
+
+        let result = repl.environment.borrow().get("result").unwrap();
+        assert_eq!(result.to_string(), "2");
+    }
+}
@@ -0,0 +1,521 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    ast::{
+        expressions::{
+            call_expression::CallExpression,
+            function_literal::FunctionLiteral,
+            identifier::{Identifier, Slot},
+            if_expression::IfExpression,
+            infix_expression::InfixExpression,
+            prefix_expression::PrefixExpression,
+        },
+        statements::{
+            attributed_statement::AttributedStatement, block_statement::BlockStatement,
+            declare_statement::DeclareStatement, defer_statement::DeferStatement,
+            expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+        },
+    },
+    fix::apply_suggestions,
+    lexer::Lexer,
+    log::diagnostic::{Applicability, Suggestion, TextEdit},
+    parser::Parser,
+    program::Program,
+    source::{SourceFile, Span},
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+    types::{ASTExpression, ASTStatement},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    Let,
+    Const,
+    Var,
+    Parameter,
+}
+
+/// A `let`, `const` or `var` declaration, or a parameter.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub name: Token,
+    pub kind: BindingKind,
+}
+
+/// An identifier of the program, declarations included.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub span: Span,
+    /// The index of the definition in `Resolution::definitions`, `None` for names declared
+    /// outside of the program, e.g: by a previous file or in the REPL.
+    pub definition: Option<usize>,
+}
+
+/// The outcome of `resolve`.
+#[derive(Debug, Default)]
+pub struct Resolution {
+    pub definitions: Vec<Definition>,
+    /// In source order, the names of the named arguments binding a known parameter included.
+    pub references: Vec<Reference>,
+    /// The names of the other named arguments in source order, e.g: of the calls of a function
+    /// passed as an argument.
+    pub unresolved_arguments: Vec<Span>,
+}
+
+impl Resolution {
+    /// The identifier at `offset`, its end included.
+    pub fn reference_at(&self, offset: usize) -> Option<&Reference> {
+        self.references
+            .iter()
+            .find(|reference| reference.span.start <= offset && offset <= reference.span.end)
+    }
+
+    /// The definition of the identifier at `offset`.
+    pub fn definition_at(&self, offset: usize) -> Option<&Definition> {
+        self.reference_at(offset)
+            .and_then(|reference| reference.definition)
+            .map(|definition| &self.definitions[definition])
+    }
+
+    /// The identifiers resolving to the same definition as the one at `offset`, its declaration
+    /// included.
+    pub fn references_at(&self, offset: usize) -> Vec<&Span> {
+        let Some(definition) = self.reference_at(offset).and_then(|r| r.definition) else {
+            return Vec::new();
+        };
+
+        self.references
+            .iter()
+            .filter(|reference| reference.definition == Some(definition))
+            .map(|reference| &reference.span)
+            .collect()
+    }
+}
+
+/// Binds every identifier of the program to its declaration, the way the evaluator looks
+/// names up: functions introduce scopes but blocks do not, and the body of a function sees every
+/// binding of the scopes around it since it runs after they are declared.
+///
+/// Identifiers naming a local of a function are given the `Slot` the evaluator stores it in, a
+/// name declared twice in a function keeping its slot, and calls in tail position are marked.
+pub fn resolve(program: &Program) -> Resolution {
+    let mut resolver = Resolver {
+        resolution: Resolution::default(),
+        scopes: vec![Scope::default()],
+        functions: HashMap::new(),
+        parameters: Vec::new(),
+        named_arguments: Vec::new(),
+    };
+
+    resolver.statements(&program.statements);
+    resolver.close_scope();
+    resolver.resolve_named_arguments();
+
+    // function bodies are resolved after their scope
+    resolver
+        .resolution
+        .references
+        .sort_by_key(|reference| reference.span.start);
+    resolver
+        .resolution
+        .unresolved_arguments
+        .sort_by_key(|span| span.start);
+    resolver.resolution
+}
+
+/// The edits renaming the binding of the identifier at `offset`, its declaration and its uses,
+/// to `new_name`. Renames changing what any identifier of the file refers to are refused, e.g:
+/// when `new_name` is already declared in the same function.
+pub fn rename(
+    file: &Rc<SourceFile>,
+    offset: usize,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, String> {
+    if !is_identifier(new_name) {
+        return Err(format!("`{}` is not a valid identifier", new_name));
+    }
+
+    let resolution = resolve_file(file)?;
+    let reference = resolution
+        .reference_at(offset)
+        .ok_or("there is no binding to rename here".to_string())?;
+    let name = reference.span.source_text();
+    if reference.definition.is_none() {
+        return Err(format!("`{}` is not declared in this file", name));
+    }
+    if name == new_name {
+        return Ok(Vec::new());
+    }
+
+    // a call naming the parameter may not be renamed with it
+    let definition = reference.definition.map(|d| &resolution.definitions[d]);
+    if definition.is_some_and(|definition| definition.kind == BindingKind::Parameter) {
+        let unresolved = resolution
+            .unresolved_arguments
+            .iter()
+            .find(|span| [name, new_name].contains(&span.source_text()));
+
+        if let Some(span) = unresolved {
+            return Err(format!(
+                "renaming `{}` to `{}` could change the parameter the argument `{}` at {} binds",
+                name,
+                new_name,
+                span.source_text(),
+                span.get_location()
+            ));
+        }
+    }
+
+    let edits: Vec<TextEdit> = resolution
+        .references_at(offset)
+        .into_iter()
+        .map(|span| TextEdit::new(span.clone(), new_name))
+        .collect();
+
+    // the renamed file must resolve the same way, identifier for identifier
+    let suggestion = Suggestion::new("", edits, Applicability::MachineApplicable);
+    let (src, _) = apply_suggestions(&file.src, &[&suggestion]);
+    let renamed = resolve_file(&Rc::new(SourceFile::new(file.id, &file.name, &src)))?;
+
+    let shift = |offset: usize| {
+        let before = suggestion
+            .edits
+            .iter()
+            .filter(|edit| edit.span.end <= offset)
+            .count();
+        (offset as isize + before as isize * (new_name.len() as isize - name.len() as isize))
+            as usize
+    };
+    let start = |resolution: &Resolution, reference: &Reference| {
+        reference
+            .definition
+            .map(|definition| resolution.definitions[definition].name.span.start)
+    };
+
+    for (before, after) in resolution.references.iter().zip(&renamed.references) {
+        if start(&renamed, after) != start(&resolution, before).map(shift) {
+            return Err(format!(
+                "renaming `{}` to `{}` would change what `{}` refers to at {}",
+                name,
+                new_name,
+                before.span.source_text(),
+                before.span.get_location()
+            ));
+        }
+    }
+
+    Ok(suggestion.edits)
+}
+
+fn resolve_file(file: &Rc<SourceFile>) -> Result<Resolution, String> {
+    let mut lexer = Lexer::from_source(Rc::clone(file));
+    let mut parser = Parser::new(&mut lexer);
+    let program = parser.parse().unwrap();
+    if !parser.errors.is_empty() {
+        return Err("the file does not parse".to_string());
+    }
+
+    Ok(resolve(&program))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut lexer = Lexer::new(name);
+    let token = lexer.next_token();
+
+    token.t == TokenType::IDENT && token.literal() == name && lexer.next_token().t == TokenType::EOF
+}
+
+/// Marks the calls of a function body whose value is the value of the function: the returned
+/// ones and the last expression of the body, or of both branches of an `if` ending it.
+fn mark_tail_calls(statements: &[ASTStatement]) {
+    for statement in statements {
+        mark_returned_calls(statement);
+    }
+
+    if let Some(statement) = statements.last() {
+        let statement = match statement.as_any().downcast_ref::<AttributedStatement>() {
+            Some(attributed) => &attributed.statement,
+            None => statement,
+        };
+
+        if let Some(statement) = statement.as_any().downcast_ref::<ExpressionStatement>() {
+            mark_tail_expression(&statement.expression);
+        }
+    }
+}
+
+fn mark_tail_expression(expression: &ASTExpression) {
+    let expression = expression.as_any();
+
+    if let Some(call) = expression.downcast_ref::<CallExpression>() {
+        call.tail.set(true);
+    } else if let Some(if_expression) = expression.downcast_ref::<IfExpression>() {
+        mark_tail_calls(&if_expression.consequence.statements);
+        if let Some(alternative) = &if_expression.alternative {
+            mark_tail_calls(&alternative.statements);
+        }
+    }
+}
+
+/// `return` leaves the function from any block but the ones of the functions defined in it.
+fn mark_returned_calls(statement: &ASTStatement) {
+    let statement = statement.as_any();
+
+    if let Some(attributed) = statement.downcast_ref::<AttributedStatement>() {
+        mark_returned_calls(&attributed.statement);
+    } else if let Some(block) = statement.downcast_ref::<BlockStatement>() {
+        block.statements.iter().for_each(mark_returned_calls);
+    } else if let Some(return_statement) = statement.downcast_ref::<ReturnStatement>() {
+        if let Some(value) = &return_statement.value {
+            if let Some(call) = value.as_any().downcast_ref::<CallExpression>() {
+                call.tail.set(true);
+            }
+            mark_returns_in_branches(value);
+        }
+    } else if let Some(statement) = statement.downcast_ref::<ExpressionStatement>() {
+        mark_returns_in_branches(&statement.expression);
+    } else if let Some(declare) = statement.downcast_ref::<DeclareStatement>() {
+        if let Some(value) = &declare.value {
+            mark_returns_in_branches(value);
+        }
+    }
+}
+
+fn mark_returns_in_branches(expression: &ASTExpression) {
+    if let Some(if_expression) = expression.as_any().downcast_ref::<IfExpression>() {
+        let alternative = if_expression.alternative.iter();
+
+        for statement in if_expression
+            .consequence
+            .statements
+            .iter()
+            .chain(alternative.flat_map(|block| &block.statements))
+        {
+            mark_returned_calls(statement);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Scope<'a> {
+    definitions: Vec<usize>,
+    /// The name of every slot of the scope, empty for the global one.
+    locals: Vec<String>,
+    /// The functions defined in the scope, resolved once every binding of the scope is known.
+    functions: Vec<&'a FunctionLiteral>,
+}
+
+struct Resolver<'a> {
+    resolution: Resolution,
+    scopes: Vec<Scope<'a>>,
+    /// The functions bound by `let` and `const`, by definition.
+    functions: HashMap<usize, &'a FunctionLiteral>,
+    /// The definitions of the parameters of every function resolved.
+    parameters: Vec<(&'a FunctionLiteral, Vec<usize>)>,
+    /// The names of the named arguments, with the function called when it is known.
+    named_arguments: Vec<(&'a Identifier, Option<&'a FunctionLiteral>)>,
+}
+
+impl<'a> Resolver<'a> {
+    fn statements(&mut self, statements: &'a [ASTStatement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a ASTStatement) {
+        let statement = statement.as_any();
+
+        if let Some(attributed) = statement.downcast_ref::<AttributedStatement>() {
+            self.statement(&attributed.statement);
+        } else if let Some(declare) = statement.downcast_ref::<DeclareStatement>() {
+            let kind = match declare.token.t {
+                TokenType::KEYWORD(KeywordTokenType::VAR) => BindingKind::Var,
+                TokenType::KEYWORD(KeywordTokenType::CONST) => BindingKind::Const,
+                _ => BindingKind::Let,
+            };
+
+            // `let x = x + 1;` uses the previous `x`
+            if let Some(value) = &declare.value {
+                self.expression(value);
+            }
+            let definition = self.declare(&declare.name, kind);
+
+            // a `var` may be assigned another function
+            let function = declare
+                .value
+                .as_ref()
+                .and_then(|value| value.as_any().downcast_ref::<FunctionLiteral>());
+            if let Some(function) = function.filter(|_| kind != BindingKind::Var) {
+                self.functions.insert(definition, function);
+            }
+        } else if let Some(statement) = statement.downcast_ref::<ExpressionStatement>() {
+            self.expression(&statement.expression);
+        } else if let Some(return_statement) = statement.downcast_ref::<ReturnStatement>() {
+            if let Some(value) = &return_statement.value {
+                self.expression(value);
+            }
+        } else if let Some(defer) = statement.downcast_ref::<DeferStatement>() {
+            self.expression(&defer.value);
+        } else if let Some(block) = statement.downcast_ref::<BlockStatement>() {
+            self.statements(&block.statements);
+        }
+    }
+
+    fn expression(&mut self, expression: &'a ASTExpression) {
+        let expression = expression.as_any();
+
+        if let Some(identifier) = expression.downcast_ref::<Identifier>() {
+            let definition = self.lookup(identifier);
+            self.resolution.references.push(Reference {
+                span: identifier.token.span.clone(),
+                definition,
+            });
+        } else if let Some(function) = expression.downcast_ref::<FunctionLiteral>() {
+            self.scopes.last_mut().unwrap().functions.push(function);
+        } else if let Some(call) = expression.downcast_ref::<CallExpression>() {
+            self.expression(&call.function);
+            for arg in &call.args {
+                self.expression(arg);
+            }
+            for arg in &call.named_args {
+                self.expression(&arg.value);
+            }
+
+            let function = call.function.as_any();
+            let callee = match function.downcast_ref::<Identifier>() {
+                Some(identifier) => self
+                    .lookup(identifier)
+                    .and_then(|definition| self.functions.get(&definition).copied()),
+                None => function.downcast_ref::<FunctionLiteral>(),
+            };
+            for arg in &call.named_args {
+                self.named_arguments.push((&arg.name, callee));
+            }
+        } else if let Some(if_expression) = expression.downcast_ref::<IfExpression>() {
+            self.expression(&if_expression.condition);
+            self.statements(&if_expression.consequence.statements);
+            if let Some(alternative) = &if_expression.alternative {
+                self.statements(&alternative.statements);
+            }
+        } else if let Some(infix) = expression.downcast_ref::<InfixExpression>() {
+            self.expression(&infix.lhs);
+            self.expression(&infix.rhs);
+        } else if let Some(prefix) = expression.downcast_ref::<PrefixExpression>() {
+            self.expression(&prefix.rhs);
+        }
+    }
+
+    /// Resolves the functions of the innermost scope, their own functions included.
+    fn close_scope(&mut self) {
+        let functions = std::mem::take(&mut self.scopes.last_mut().unwrap().functions);
+
+        for function in functions {
+            self.scopes.push(Scope::default());
+            let mut parameters = Vec::new();
+            for parameter in &function.parameters {
+                // defaults see the parameters before them
+                if let Some(default) = &parameter.default {
+                    self.expression(default);
+                }
+                parameters.push(self.declare(&parameter.identifier, BindingKind::Parameter));
+            }
+            self.parameters.push((function, parameters));
+
+            self.statements(&function.body.statements);
+            self.close_scope();
+            mark_tail_calls(&function.body.statements);
+
+            let scope = self.scopes.pop().unwrap();
+            function.locals.replace(scope.locals.into());
+        }
+    }
+
+    /// Returns the index of the definition.
+    fn declare(&mut self, name: &Identifier, kind: BindingKind) -> usize {
+        let definition = self.resolution.definitions.len();
+        self.resolution.definitions.push(Definition {
+            name: name.token.clone(),
+            kind,
+        });
+        self.resolution.references.push(Reference {
+            span: name.token.span.clone(),
+            definition: Some(definition),
+        });
+
+        let global = self.scopes.len() == 1;
+        let scope = self.scopes.last_mut().unwrap();
+        scope.definitions.push(definition);
+
+        if !global {
+            let index = match scope.locals.iter().position(|local| *local == name.value) {
+                Some(index) => index,
+                None => {
+                    scope.locals.push(name.value.clone());
+                    scope.locals.len() - 1
+                }
+            };
+            name.slot.set(Some(Slot { depth: 0, index }));
+        }
+
+        definition
+    }
+
+    /// Binds the names of the named arguments to the parameters of the functions called, once
+    /// every function is resolved.
+    fn resolve_named_arguments(&mut self) {
+        for (name, callee) in std::mem::take(&mut self.named_arguments) {
+            let parameter = callee
+                .and_then(|callee| {
+                    self.parameters
+                        .iter()
+                        .find(|(function, _)| std::ptr::eq(*function, callee))
+                })
+                .and_then(|(_, parameters)| {
+                    parameters.iter().find(|&&parameter| {
+                        self.resolution.definitions[parameter].name.literal() == name.value
+                    })
+                });
+
+            match parameter {
+                Some(&parameter) => self.resolution.references.push(Reference {
+                    span: name.token.span.clone(),
+                    definition: Some(parameter),
+                }),
+                None => {
+                    self.resolution
+                        .unresolved_arguments
+                        .push(name.token.span.clone());
+                }
+            }
+        }
+    }
+
+    /// The definition of `identifier`, giving it the slot of the local it names.
+    fn lookup(&self, identifier: &Identifier) -> Option<usize> {
+        let name = identifier.value.as_str();
+
+        self.scopes.iter().enumerate().rev().find_map(|(i, scope)| {
+            let definition = scope.definitions.iter().rev().find(|&&definition| {
+                self.resolution.definitions[definition].name.literal() == name
+            })?;
+
+            identifier.slot.set(
+                scope
+                    .locals
+                    .iter()
+                    .position(|local| local == name)
+                    .map(|index| Slot {
+                        depth: self.scopes.len() - 1 - i,
+                        index,
+                    }),
+            );
+            Some(*definition)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test;
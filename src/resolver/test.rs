@@ -0,0 +1,353 @@
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{
+        ast::{
+            expressions::{
+                call_expression::CallExpression,
+                function_literal::FunctionLiteral,
+                identifier::{Identifier, Slot},
+                if_expression::IfExpression,
+                infix_expression::InfixExpression,
+            },
+            statements::{
+                declare_statement::DeclareStatement, expression_statement::ExpressionStatement,
+                return_statement::ReturnStatement,
+            },
+        },
+        lexer::Lexer,
+        parser::Parser,
+        resolver::{rename, resolve, BindingKind, Resolution},
+        source::SourceFile,
+    };
+
+    fn resolution(code: &str) -> Resolution {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        resolve(&program)
+    }
+
+    /// Every identifier as `name@offset`, followed by the offset of its declaration.
+    fn resolved(code: &str) -> Vec<String> {
+        let resolution = resolution(code);
+
+        resolution
+            .references
+            .iter()
+            .map(|reference| {
+                let declaration = match reference.definition {
+                    Some(definition) => resolution.definitions[definition]
+                        .name
+                        .span
+                        .start
+                        .to_string(),
+                    None => "?".to_string(),
+                };
+
+                format!(
+                    "{}@{} -> {}",
+                    reference.span.source_text(),
+                    reference.span.start,
+                    declaration
+                )
+            })
+            .collect()
+    }
+
+    fn downcast<T: 'static>(node: &dyn std::any::Any) -> &T {
+        node.downcast_ref::<T>().unwrap()
+    }
+
+    /// The source once renamed, or the reason the rename is refused.
+    fn renamed(code: &str, offset: usize, new_name: &str) -> Result<String, String> {
+        let file = Rc::new(SourceFile::new(0, "main.eheh", code));
+        let mut edits = rename(&file, offset, new_name)?;
+        edits.sort_by_key(|edit| edit.span.start);
+
+        let mut src = code.to_string();
+        for edit in edits.iter().rev() {
+            src.replace_range(edit.span.start..edit.span.end, &edit.replacement);
+        }
+
+        Ok(src)
+    }
+
+    #[test]
+    fn test_resolve() {
+        // the body of a function runs after the bindings declared after it
+        assert_eq!(
+            resolved("let a = 1; let f = fn(a) { a + b }; let b = a; f(b);"),
+            vec![
+                "a@4 -> 4",
+                "f@15 -> 15",
+                "a@22 -> 22",
+                "a@27 -> 22",
+                "b@31 -> 40",
+                "b@40 -> 40",
+                "a@44 -> 4",
+                "f@47 -> 15",
+                "b@49 -> 40",
+            ]
+        );
+
+        // the value is resolved before the binding it declares
+        assert_eq!(
+            resolved("let x = 1; let x = x + 1; x;"),
+            vec!["x@4 -> 4", "x@15 -> 15", "x@19 -> 4", "x@26 -> 15"]
+        );
+
+        // defaults see the parameters before them
+        assert_eq!(
+            resolved("let f = fn(a, b = a) { b };"),
+            vec!["f@4 -> 4", "a@11 -> 11", "b@14 -> 14", "a@18 -> 11", "b@23 -> 14"]
+        );
+
+        // blocks do not introduce scopes, names declared elsewhere are left unresolved
+        assert_eq!(
+            resolved("if (true) { let y = 1; }; print(y);"),
+            vec!["y@16 -> 16", "print@26 -> ?", "y@32 -> 16"]
+        );
+    }
+
+    #[test]
+    fn test_slots() {
+        const CODE: &str = "let f = fn(a, b) { let a = b; fn(c) { a + c } };";
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        resolve(&program);
+
+        let f = downcast::<DeclareStatement>(program.statements[0].as_any());
+        assert_eq!(f.name.slot.get(), None);
+
+        let outer = downcast::<FunctionLiteral>(f.value.as_ref().unwrap().as_any());
+        assert_eq!(outer.locals.borrow().to_vec(), ["a", "b"]);
+
+        // a redeclared name keeps its slot
+        let a = downcast::<DeclareStatement>(outer.body.statements[0].as_any());
+        assert_eq!(a.name.slot.get(), Some(Slot { depth: 0, index: 0 }));
+        let b = downcast::<Identifier>(a.value.as_ref().unwrap().as_any());
+        assert_eq!(b.slot.get(), Some(Slot { depth: 0, index: 1 }));
+
+        let statement = downcast::<ExpressionStatement>(outer.body.statements[1].as_any());
+        let inner = downcast::<FunctionLiteral>(statement.expression.as_any());
+        assert_eq!(inner.locals.borrow().to_vec(), ["c"]);
+
+        let statement = downcast::<ExpressionStatement>(inner.body.statements[0].as_any());
+        let sum = downcast::<InfixExpression>(statement.expression.as_any());
+        let a = downcast::<Identifier>(sum.lhs.as_any());
+        assert_eq!(a.slot.get(), Some(Slot { depth: 1, index: 0 }));
+        let c = downcast::<Identifier>(sum.rhs.as_any());
+        assert_eq!(c.slot.get(), Some(Slot { depth: 0, index: 0 }));
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        const CODE: &str = "\
+let f = fn(n) {
+    if n == 0 { return a(n); }
+    let x = if n == 1 { return b(n); } else { c(n) };
+    d(e(n)) + g(n);
+    if n == 2 { h(n) } else { i(n) }
+};
+j(fn() { k() });";
+
+        let mut lexer = Lexer::new(CODE);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+        resolve(&program);
+
+        let mut calls = Vec::new();
+        let mut pending: Vec<&dyn std::any::Any> = program
+            .statements
+            .iter()
+            .map(|statement| statement.as_any())
+            .collect();
+        // every call of the program, in no particular order
+        while let Some(node) = pending.pop() {
+            if let Some(call) = node.downcast_ref::<CallExpression>() {
+                calls.push((call.function.to_string(), call.tail.get()));
+                pending.extend(call.args.iter().map(|arg| arg.as_any()));
+            } else if let Some(declare) = node.downcast_ref::<DeclareStatement>() {
+                pending.extend(declare.value.iter().map(|value| value.as_any()));
+            } else if let Some(statement) = node.downcast_ref::<ExpressionStatement>() {
+                pending.push(statement.expression.as_any());
+            } else if let Some(statement) = node.downcast_ref::<ReturnStatement>() {
+                pending.extend(statement.value.iter().map(|value| value.as_any()));
+            } else if let Some(function) = node.downcast_ref::<FunctionLiteral>() {
+                pending.extend(function.body.statements.iter().map(|s| s.as_any()));
+            } else if let Some(infix) = node.downcast_ref::<InfixExpression>() {
+                pending.extend([infix.lhs.as_any(), infix.rhs.as_any()]);
+            } else if let Some(if_expression) = node.downcast_ref::<IfExpression>() {
+                let alternative = if_expression.alternative.iter();
+                pending.extend(
+                    if_expression
+                        .consequence
+                        .statements
+                        .iter()
+                        .chain(alternative.flat_map(|block| &block.statements))
+                        .map(|statement| statement.as_any()),
+                );
+            }
+        }
+        calls.sort();
+
+        let tail: Vec<&str> = calls
+            .iter()
+            .filter(|(_, tail)| *tail)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(tail, vec!["a", "b", "h", "i", "k"]);
+        // `c`, `d`, `e`, `g` and the top-level `j` are not
+        assert_eq!(calls.len(), 10);
+    }
+
+    #[test]
+    fn test_definitions_and_references() {
+        const CODE: &str = "let a = 1;\nlet f = fn(n) { n * a };\nf(a);";
+
+        let resolution = resolution(CODE);
+
+        let definition = resolution.definition_at(31).unwrap();
+        assert_eq!(definition.name.span.start, 4);
+        assert_eq!(definition.kind, BindingKind::Let);
+        assert_eq!(
+            resolution
+                .definition_at(27)
+                .map(|definition| definition.kind),
+            Some(BindingKind::Parameter)
+        );
+        assert_eq!(resolution.definition_at(36).unwrap().name.span.start, 15);
+        assert!(resolution.definition_at(8).is_none());
+
+        let references: Vec<usize> = resolution
+            .references_at(5)
+            .iter()
+            .map(|span| span.start)
+            .collect();
+        assert_eq!(references, vec![4, 31, 38]);
+        assert!(resolution.references_at(8).is_empty());
+    }
+
+    #[test]
+    fn test_rename() {
+        assert_eq!(
+            renamed("let a = 1;\nlet f = fn(n) { n * a };\nf(a);", 31, "count"),
+            Ok("let count = 1;\nlet f = fn(n) { n * count };\nf(count);".to_string())
+        );
+        // only the binding under the cursor
+        assert_eq!(
+            renamed("let x = 1; let f = fn(x) { x }; x;", 22, "y"),
+            Ok("let x = 1; let f = fn(y) { y }; x;".to_string())
+        );
+        assert_eq!(
+            renamed("let x = 1; x;", 11, "x"),
+            Ok("let x = 1; x;".to_string())
+        );
+    }
+
+    #[test]
+    fn test_named_arguments() {
+        assert_eq!(
+            resolved("let f = fn(x, y) { y }; f(x = 1, y = 2);"),
+            vec![
+                "f@4 -> 4",
+                "x@11 -> 11",
+                "y@14 -> 14",
+                "y@19 -> 14",
+                "f@24 -> 4",
+                "x@26 -> 11",
+                "y@33 -> 14"
+            ]
+        );
+
+        // the function called is not known
+        let resolution = resolution("let g = fn(f) { f(x = 1) }; var h = fn(x) { x }; h(x = 2);");
+        let unresolved: Vec<usize> = resolution
+            .unresolved_arguments
+            .iter()
+            .map(|span| span.start)
+            .collect();
+        assert_eq!(unresolved, vec![18, 51]);
+    }
+
+    #[test]
+    fn test_rename_named_arguments() {
+        assert_eq!(
+            renamed("let f = fn(x, y) { y }; f(x = 1, y = 2);", 11, "z"),
+            Ok("let f = fn(z, y) { y }; f(z = 1, y = 2);".to_string())
+        );
+        // from the argument
+        assert_eq!(
+            renamed("let f = fn(x) { x }; f(x = 1); f(2);", 23, "z"),
+            Ok("let f = fn(z) { z }; f(z = 1); f(2);".to_string())
+        );
+
+        assert_eq!(
+            renamed("let g = fn(f) { f(x = 1) }; let h = fn(x) { x };", 39, "z"),
+            Err(
+                "renaming `x` to `z` could change the parameter the argument `x` at main.eheh:1:19 \
+                 binds"
+                    .to_string()
+            )
+        );
+        assert!(renamed("let g = fn(f) { f(z = 1) }; let h = fn(x) { x };", 39, "z").is_err());
+        // the other bindings are not passed by name
+        assert_eq!(
+            renamed("let g = fn(f) { f(x = 1) }; let x = 1;", 32, "z"),
+            Ok("let g = fn(f) { f(x = 1) }; let z = 1;".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rename_conflicts() {
+        const CODES: [(&str, usize, &str); 4] = [
+            // already declared in the same function
+            ("let a = 1; let b = 2; a + b;", 4, "b"),
+            // captured by a parameter
+            ("let a = 1; let f = fn(b) { a + b };", 4, "b"),
+            // shadows a binding used in the function
+            ("let b = 1; let f = fn(a) { a + b };", 22, "b"),
+            // a name declared elsewhere
+            ("let a = 1; print(a);", 4, "print"),
+        ];
+
+        for (code, offset, new_name) in CODES {
+            let err = renamed(code, offset, new_name).unwrap_err();
+            assert!(err.contains("would change what"), "{}: {}", code, err);
+        }
+
+        assert_eq!(
+            renamed("let a = 1; let b = 2; a + b;", 4, "b"),
+            Err(
+                "renaming `a` to `b` would change what `a` refers to at main.eheh:1:23".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_rename_errors() {
+        for new_name in ["", "1x", "let", "a b", "a+"] {
+            assert_eq!(
+                renamed("let a = 1;", 4, new_name),
+                Err(format!("`{}` is not a valid identifier", new_name))
+            );
+        }
+
+        assert_eq!(
+            renamed("print(1);", 2, "log"),
+            Err("`print` is not declared in this file".to_string())
+        );
+        assert_eq!(
+            renamed("let a = 1;", 8, "b"),
+            Err("there is no binding to rename here".to_string())
+        );
+        assert_eq!(
+            renamed("let a = ;", 4, "b"),
+            Err("the file does not parse".to_string())
+        );
+    }
+}
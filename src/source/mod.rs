@@ -0,0 +1,223 @@
+use std::{cell::OnceCell, fmt::Debug, fs, io, path::Path, rc::Rc};
+
+pub type FileId = usize;
+
+/// A piece of eheh source code, either read from the disk or typed in the REPL.
+pub struct SourceFile {
+    pub id: FileId,
+    pub name: String,
+    pub src: String,
+    /// Byte offset of the first character of every line, computed on first use.
+    line_starts: OnceCell<Vec<usize>>,
+}
+
+impl SourceFile {
+    pub fn new(id: FileId, name: &str, src: &str) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            src: src.to_owned(),
+            line_starts: OnceCell::new(),
+        }
+    }
+
+    pub fn anonymous(src: &str) -> Self {
+        Self::new(0, "<anonymous>", src)
+    }
+
+    pub fn line_starts(&self) -> &[usize] {
+        self.line_starts.get_or_init(|| {
+            std::iter::once(0)
+                .chain(self.src.match_indices('\n').map(|(i, _)| i + 1))
+                .collect()
+        })
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts().len()
+    }
+
+    /// 1-based line number of the given byte offset.
+    pub fn line_number(&self, offset: usize) -> usize {
+        match self.line_starts().binary_search(&offset) {
+            Ok(line) => line + 1,
+            Err(line) => line,
+        }
+    }
+
+    /// 1-based line and column (in characters) of the given byte offset.
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.src.len());
+        let line = self.line_number(offset);
+        let line_start = self.line_starts()[line - 1];
+        let column = self.src[line_start..offset].chars().count() + 1;
+
+        (line, column)
+    }
+
+    /// The content of the 1-based `n`th line, without its line terminator.
+    pub fn line(&self, n: usize) -> Option<&str> {
+        if n == 0 || n > self.line_count() {
+            return None;
+        }
+
+        let start = self.line_starts()[n - 1];
+        let end = self
+            .line_starts()
+            .get(n)
+            .map(|next| next - 1)
+            .unwrap_or(self.src.len());
+
+        Some(self.src[start..end].trim_end_matches('\r'))
+    }
+}
+
+impl Debug for SourceFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SourceFile({}, {:?})", self.id, self.name)
+    }
+}
+
+/// A byte range `[start, end)` inside of a `SourceFile`.
+#[derive(Clone)]
+pub struct Span {
+    pub file: Rc<SourceFile>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file: Rc<SourceFile>, start: usize, end: usize) -> Self {
+        Self { file, start, end }
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(&self, other: &Span) -> Span {
+        Span::new(
+            Rc::clone(&self.file),
+            self.start.min(other.start),
+            self.end.max(other.end),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn line(&self) -> usize {
+        self.file.line_number(self.start)
+    }
+
+    pub fn column(&self) -> usize {
+        self.file.line_column(self.start).1
+    }
+
+    pub fn end_line(&self) -> usize {
+        self.file.line_number(self.end)
+    }
+
+    pub fn source_text(&self) -> &str {
+        &self.file.src[self.start..self.end]
+    }
+
+    pub fn source_line(&self) -> Option<String> {
+        self.file.line(self.line()).map(str::to_string)
+    }
+
+    pub fn get_location(&self) -> String {
+        let (line, column) = self.file.line_column(self.start);
+        format!("{}:{}:{}", self.file.name, line, column)
+    }
+}
+
+impl PartialEq for Span {
+    fn eq(&self, other: &Self) -> bool {
+        self.file.id == other.file.id && self.start == other.start && self.end == other.end
+    }
+}
+
+impl Debug for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}..{}]", self.file.name, self.start, self.end)
+    }
+}
+
+/// Owns every `SourceFile` of a program and hands out their `FileId`s.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<Rc<SourceFile>>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    pub fn add_file(&mut self, name: &str, src: &str) -> Rc<SourceFile> {
+        let file = Rc::new(SourceFile::new(self.files.len(), name, src));
+        self.files.push(Rc::clone(&file));
+        file
+    }
+
+    pub fn load_file(&mut self, path: &Path) -> io::Result<Rc<SourceFile>> {
+        let src = fs::read_to_string(path)?;
+        Ok(self.add_file(path.to_string_lossy().as_ref(), &src))
+    }
+
+    pub fn get(&self, id: FileId) -> Option<Rc<SourceFile>> {
+        self.files.get(id).cloned()
+    }
+
+    pub fn files(&self) -> &[Rc<SourceFile>] {
+        &self.files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{SourceFile, SourceMap, Span};
+
+    #[test]
+    fn test_line_column_lookup() {
+        let file = SourceFile::anonymous("let a = 1;\r\nlet bé = 2;\n\nlet c = 3;");
+
+        assert_eq!(file.line_count(), 4);
+        assert_eq!(file.line_column(0), (1, 1));
+        assert_eq!(file.line_column(4), (1, 5));
+        assert_eq!(file.line_column(12), (2, 1));
+        // `é` is two bytes long but a single column
+        assert_eq!(file.line_column(19), (2, 7));
+        assert_eq!(file.line_column(25), (3, 1));
+        assert_eq!(file.line_column(26), (4, 1));
+
+        assert_eq!(file.line(1), Some("let a = 1;"));
+        assert_eq!(file.line(2), Some("let bé = 2;"));
+        assert_eq!(file.line(3), Some(""));
+        assert_eq!(file.line(4), Some("let c = 3;"));
+        assert_eq!(file.line(5), None);
+    }
+
+    #[test]
+    fn test_source_map_file_ids() {
+        let mut source_map = SourceMap::new();
+        let main = source_map.add_file("main.eheh", "let a = 1;");
+        let utils = source_map.add_file("utils.eheh", "let b = a;\nb");
+
+        assert_eq!(main.id, 0);
+        assert_eq!(utils.id, 1);
+        assert_eq!(source_map.get(1).unwrap().name, "utils.eheh");
+        assert!(source_map
+            .load_file(Path::new("does/not/exist.eheh"))
+            .is_err());
+
+        let span = Span::new(utils, 11, 12);
+        assert_eq!(span.get_location(), "utils.eheh:2:1");
+        assert_eq!(span.source_text(), "b");
+    }
+}
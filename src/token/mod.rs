@@ -1,37 +1,45 @@
+use crate::source::Span;
+
 use self::token_type::TokenType;
 
 pub mod token_type;
+pub mod trivia;
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub t: TokenType,
-    pub literal: String,
-    pub line: usize,
-    pub column: usize,
-    pub file: Option<String>,
+    pub span: Span,
 }
 
 impl Token {
-    pub fn new(
-        t: TokenType,
-        literal: String,
-        line: usize,
-        column: usize,
-        file: Option<String>,
-    ) -> Self {
-        Self {
-            t,
-            literal,
-            line,
-            column,
-            file,
+    pub fn new(t: TokenType, span: Span) -> Self {
+        Self { t, span }
+    }
+
+    /// The text of the token, borrowed from its source file.
+    ///
+    /// String literals are returned without their quotes and `EOF` has an empty literal.
+    pub fn literal(&self) -> &str {
+        let text = self.span.source_text();
+
+        match self.t {
+            TokenType::STRING => {
+                let content = text.strip_prefix('"').unwrap_or(text);
+                content.strip_suffix('"').unwrap_or(content)
+            }
+            _ => text,
         }
     }
 
+    pub fn line(&self) -> usize {
+        self.span.line()
+    }
+
+    pub fn column(&self) -> usize {
+        self.span.column()
+    }
+
     pub fn get_location(&self) -> String {
-        if let Some(file) = &self.file {
-            format!("{}:{}:{}", file, self.line, self.column)
-        } else {
-            format!("{}:{}", self.line, self.column)
-        }
+        self.span.get_location()
     }
 }
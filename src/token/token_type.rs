@@ -158,6 +158,23 @@ impl TokenType {
 }
 
 impl KeywordTokenType {
+    pub const ALL: [KeywordTokenType; 14] = [
+        KeywordTokenType::FUN,
+        KeywordTokenType::LET,
+        KeywordTokenType::CONST,
+        KeywordTokenType::VAR,
+        KeywordTokenType::TRUE,
+        KeywordTokenType::FALSE,
+        KeywordTokenType::IF,
+        KeywordTokenType::ELSE,
+        KeywordTokenType::END,
+        KeywordTokenType::DO,
+        KeywordTokenType::NULL,
+        KeywordTokenType::RETURN,
+        KeywordTokenType::DEFER,
+        KeywordTokenType::ERRDEFER,
+    ];
+
     pub fn to_literal(&self) -> String {
         match self {
             KeywordTokenType::FUN => "fn".to_string(),
@@ -178,6 +195,7 @@ impl KeywordTokenType {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(identifier: &str) -> Option<KeywordTokenType> {
         match identifier {
             "fn" => Some(KeywordTokenType::FUN),
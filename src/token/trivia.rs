@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use crate::source::Span;
+
+use super::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriviaKind {
+    /// Spaces, tabs and line breaks.
+    Whitespace,
+    /// `// ...`, up to the end of the line.
+    Comment,
+    /// `/* ... */`
+    CommentBlock,
+}
+
+/// A piece of the source that is not part of any token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// A token along with the trivia around it, scanned by the lexer in lossless mode.
+///
+/// The trivia following a token up to the end of its line is trailing, e.g: the comment in
+/// `let x = 1; // one`, every other trivia is leading the next token. Concatenating the text of
+/// every token of a source gives the source back, `EOF` leads with the trivia ending the file.
+#[derive(Debug, Clone)]
+pub struct SyntaxToken {
+    pub token: Token,
+    pub leading: Vec<Trivia>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl SyntaxToken {
+    pub fn new(token: Token, leading: Vec<Trivia>, trailing: Vec<Trivia>) -> Self {
+        Self {
+            token,
+            leading,
+            trailing,
+        }
+    }
+
+    /// The span of the token and of its trivia.
+    pub fn full_span(&self) -> Span {
+        let start = self.leading.first().map_or(&self.token.span, |t| &t.span);
+        let end = self.trailing.last().map_or(&self.token.span, |t| &t.span);
+
+        start.to(end)
+    }
+}
+
+impl Display for SyntaxToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.full_span().source_text())
+    }
+}
@@ -1,13 +1,18 @@
+use crate::ast::kind::{ExpressionKind, ExpressionKindMut, OwnedExpression};
+
 use super::node::Node;
 
 pub trait Expression: Node {
-    fn eval(&self) -> String;
     fn clone_boxed(&self) -> Box<dyn Expression>;
+    /// The expression as its type, see `ast::visit`.
+    fn kind(&self) -> ExpressionKind<'_>;
+    fn kind_mut(&mut self) -> ExpressionKindMut<'_>;
+    fn into_kind(self: Box<Self>) -> OwnedExpression;
 }
 
 impl core::fmt::Debug for dyn Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Expression{{{}}}", self.eval())
+        write!(f, "Expression{{{}}}", self.to_string())
     }
 }
 
@@ -1,7 +1,10 @@
 use std::any::Any;
 
+use crate::source::Span;
+
 pub trait Node: ToString {
     fn get_token_literal(&self) -> String;
+    fn span(&self) -> Span;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn as_any(&self) -> &dyn Any;
     fn as_node(&self) -> &dyn Node;
@@ -1,4 +1,6 @@
-use std::{any::Any, fmt::Display};
+use std::{any::Any, cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::objects::environment::Environment;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum IntegerType {
@@ -27,8 +29,14 @@ pub enum IntegerType {
 pub enum ObjectType {
     Boolean,
     Integer(IntegerType),
+    String,
     Return,
     Function,
+    Builtin,
+    GcStats,
+    AllocStats,
+    TailCall,
+    Array,
     Error,
     Null,
 }
@@ -37,9 +45,15 @@ impl Display for ObjectType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ObjectType::Boolean => write!(f, "boolean"),
+            ObjectType::String => write!(f, "string"),
             ObjectType::Return => write!(f, "return"),
             ObjectType::Error => write!(f, "error"),
             ObjectType::Function => write!(f, "function"),
+            ObjectType::Builtin => write!(f, "builtin function"),
+            ObjectType::GcStats => write!(f, "gc stats"),
+            ObjectType::AllocStats => write!(f, "allocation stats"),
+            ObjectType::TailCall => write!(f, "tail call"),
+            ObjectType::Array => write!(f, "array"),
             ObjectType::Null => write!(f, "null"),
             ObjectType::Integer(i) => i.fmt(f),
         }
@@ -73,10 +87,15 @@ pub trait Object: ToString {
     /// type of the object -> `ObjectType`
     fn t(&self) -> ObjectType;
     fn inspect(&self) -> String;
+    #[allow(clippy::wrong_self_convention)]
     fn as_any(self) -> Box<dyn Any>;
     fn as_any_ref(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn clone_boxed(&self) -> Box<dyn Object>;
+
+    /// Calls `visit` with every environment the object holds, the ones of the objects it
+    /// contains included. See `gc`.
+    fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>)) {}
 }
 
 impl core::fmt::Debug for dyn Object {
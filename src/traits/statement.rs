@@ -1,8 +1,13 @@
+use crate::ast::kind::{OwnedStatement, StatementKind, StatementKindMut};
+
 use super::node::Node;
 
 pub trait Statement: Node {
-    fn process(&self);
     fn clone_boxed(&self) -> Box<dyn Statement>;
+    /// The statement as its type, see `ast::visit`.
+    fn kind(&self) -> StatementKind<'_>;
+    fn kind_mut(&mut self) -> StatementKindMut<'_>;
+    fn into_kind(self: Box<Self>) -> OwnedStatement;
 }
 
 impl core::fmt::Debug for dyn Statement {
@@ -0,0 +1,524 @@
+use std::fmt::Display;
+
+use crate::{
+    ast::{
+        expressions::{
+            boolean_expression::BooleanExpression, call_expression::CallExpression,
+            function_literal::FunctionLiteral, identifier::Identifier, if_expression::IfExpression,
+            infix_expression::InfixExpression, integer_literal::IntegerLiteral,
+            null_expression::NullExpression, prefix_expression::PrefixExpression,
+            string_literal::StringLiteral,
+        },
+        statements::{
+            attributed_statement::AttributedStatement, block_statement::BlockStatement,
+            declare_statement::DeclareStatement, defer_statement::DeferStatement,
+            expression_statement::ExpressionStatement, return_statement::ReturnStatement,
+        },
+    },
+    log::error::type_checker::{TypeError, TypeErrorCode},
+    program::Program,
+    resolver::BindingKind,
+    source::Span,
+    token::{
+        token_type::{KeywordTokenType, TokenType},
+        Token,
+    },
+    traits::node::Node,
+    types::{ASTExpression, ASTStatement},
+};
+
+/// The integer types an annotation can name, they are all `i64` at runtime.
+const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// What `-`, `!`, the arithmetic and the comparison operators can be applied to.
+const OPERAND: &str = "`i64` or `bool`";
+
+/// The type of a value as far as it can be known without running the program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Integer,
+    Boolean,
+    String,
+    Null,
+    Function {
+        parameters: Vec<Type>,
+        returns: Box<Type>,
+    },
+    /// Only known at runtime, e.g: a parameter without annotation. Never reported.
+    Unknown,
+}
+
+impl Type {
+    /// The type named by an annotation, e.g: `u32` in `let age: u32 = 20;`. Names the checker
+    /// does not know give `None`.
+    pub fn from_annotation(name: &str) -> Option<Type> {
+        match name {
+            "bool" => Some(Type::Boolean),
+            "string" => Some(Type::String),
+            name if INTEGER_TYPES.contains(&name) => Some(Type::Integer),
+            _ => None,
+        }
+    }
+
+    /// Whether a value of type `other` can be used where `self` is expected, the parameters
+    /// and return types of functions are not compared yet.
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Function { .. }, Type::Function { .. }) => true,
+            (expected, found) => expected == found,
+        }
+    }
+
+    fn is_operand(&self) -> bool {
+        matches!(self, Type::Integer | Type::Boolean | Type::Unknown)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Integer => write!(f, "i64"),
+            Type::Boolean => write!(f, "bool"),
+            Type::String => write!(f, "string"),
+            Type::Null => write!(f, "null"),
+            Type::Function {
+                parameters,
+                returns,
+            } => {
+                let parameters: Vec<String> = parameters.iter().map(Type::to_string).collect();
+                write!(f, "fn({}) -> {}", parameters.join(", "), returns)
+            }
+            Type::Unknown => write!(f, "_"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub name: Token,
+    pub kind: BindingKind,
+    pub t: Type,
+    /// The annotation as written, e.g: `u32`.
+    pub annotation: Option<String>,
+    /// The source of the value when it is a literal, e.g: `5` in `let x = 5;`.
+    pub value: Option<String>,
+}
+
+impl Binding {
+    /// The type shown to the user, the annotation rather than the type it stands for.
+    pub fn type_name(&self) -> String {
+        self.annotation.clone().unwrap_or(self.t.to_string())
+    }
+}
+
+/// The outcome of `check`.
+#[derive(Debug, Default)]
+pub struct TypeCheck {
+    pub errors: Vec<TypeError>,
+    pub bindings: Vec<Binding>,
+}
+
+impl TypeCheck {
+    /// The binding whose name is at `name`, see `resolver::Definition`.
+    pub fn binding(&self, name: &Span) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.name.span == *name)
+    }
+}
+
+/// Infers the type of every binding and reports the operations that would fail at runtime
+/// because of the type of their operands, e.g: `1 + "one"`. Types only known at runtime are
+/// never reported.
+pub fn check(program: &Program) -> TypeCheck {
+    let mut checker = Checker {
+        check: TypeCheck::default(),
+        scopes: vec![Vec::new()],
+        returns: Vec::new(),
+    };
+
+    checker.statements(&program.statements);
+    checker.check
+}
+
+struct Checker {
+    check: TypeCheck,
+    /// The bindings of every function being checked, the program first, as indices in
+    /// `check.bindings`. Blocks do not introduce scopes, just like at runtime.
+    scopes: Vec<Vec<usize>>,
+    /// The values returned by every function being checked.
+    returns: Vec<Vec<(Type, Span)>>,
+}
+
+impl Checker {
+    /// The type and the span of the value of the statements, `None` when they end with a
+    /// `return`.
+    fn statements(&mut self, statements: &[ASTStatement]) -> Option<(Type, Option<Span>)> {
+        let mut t = Type::Null;
+        for statement in statements {
+            t = self.statement(statement);
+        }
+
+        let Some(last) = statements.last() else {
+            return Some((t, None));
+        };
+
+        let last = match last.as_any().downcast_ref::<AttributedStatement>() {
+            Some(attributed) => attributed.statement.as_any(),
+            None => last.as_any(),
+        };
+
+        if last.is::<ReturnStatement>() {
+            None
+        } else if let Some(statement) = last.downcast_ref::<ExpressionStatement>() {
+            Some((t, Some(statement.expression.span())))
+        } else {
+            Some((Type::Unknown, None))
+        }
+    }
+
+    /// The type of the value of an expression statement, `Unknown` for the other statements.
+    fn statement(&mut self, statement: &ASTStatement) -> Type {
+        let statement = statement.as_any();
+
+        if let Some(attributed) = statement.downcast_ref::<AttributedStatement>() {
+            return self.statement(&attributed.statement);
+        } else if let Some(declare) = statement.downcast_ref::<DeclareStatement>() {
+            self.declare_statement(declare);
+        } else if let Some(statement) = statement.downcast_ref::<ExpressionStatement>() {
+            return self.expression(&statement.expression);
+        } else if let Some(return_statement) = statement.downcast_ref::<ReturnStatement>() {
+            let returned = match &return_statement.value {
+                Some(value) => (self.expression(value), value.span()),
+                None => (Type::Null, return_statement.token.span.clone()),
+            };
+
+            if let Some(returns) = self.returns.last_mut() {
+                returns.push(returned);
+            }
+        } else if let Some(defer) = statement.downcast_ref::<DeferStatement>() {
+            self.expression(&defer.value);
+        } else if let Some(block) = statement.downcast_ref::<BlockStatement>() {
+            self.statements(&block.statements);
+        }
+
+        Type::Unknown
+    }
+
+    fn declare_statement(&mut self, declare: &DeclareStatement) {
+        let kind = match declare.token.t {
+            TokenType::KEYWORD(KeywordTokenType::VAR) => BindingKind::Var,
+            TokenType::KEYWORD(KeywordTokenType::CONST) => BindingKind::Const,
+            _ => BindingKind::Let,
+        };
+        let annotated = declare
+            .type_specifier
+            .as_deref()
+            .and_then(Type::from_annotation);
+
+        let Some(value) = &declare.value else {
+            self.declare(
+                &declare.name.token,
+                kind,
+                annotated.unwrap_or(Type::Unknown),
+                declare.type_specifier.clone(),
+                None,
+            );
+            return;
+        };
+
+        let literal = value.as_any().is::<IntegerLiteral>()
+            || value.as_any().is::<StringLiteral>()
+            || value.as_any().is::<BooleanExpression>()
+            || value.as_any().is::<NullExpression>();
+
+        // declared first so that the function can call itself
+        let is_function = value.as_any().is::<FunctionLiteral>();
+        let binding = is_function.then(|| {
+            self.declare(
+                &declare.name.token,
+                kind,
+                annotated.clone().unwrap_or(Type::Unknown),
+                declare.type_specifier.clone(),
+                None,
+            )
+        });
+
+        let t = self.expression(value);
+        if let (Some(expected), Some(annotation)) = (&annotated, &declare.type_specifier) {
+            if !expected.accepts(&t) {
+                self.error(TypeErrorCode::Mismatch {
+                    span: value.span(),
+                    expected: format!("`{}`", annotation),
+                    found: t.to_string(),
+                    reason: None,
+                });
+            }
+        }
+
+        let t = annotated.unwrap_or(t);
+        match binding {
+            Some(binding) => self.check.bindings[binding].t = t,
+            None => {
+                self.declare(
+                    &declare.name.token,
+                    kind,
+                    t,
+                    declare.type_specifier.clone(),
+                    literal.then(|| value.span().source_text().to_string()),
+                );
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &ASTExpression) -> Type {
+        let any = expression.as_any();
+
+        if let Some(identifier) = any.downcast_ref::<Identifier>() {
+            self.identifier(identifier)
+        } else if any.is::<IntegerLiteral>() {
+            Type::Integer
+        } else if any.is::<StringLiteral>() {
+            Type::String
+        } else if any.is::<BooleanExpression>() {
+            Type::Boolean
+        } else if any.is::<NullExpression>() {
+            Type::Null
+        } else if let Some(prefix) = any.downcast_ref::<PrefixExpression>() {
+            let t = self.expression(&prefix.rhs);
+
+            match prefix.operator.as_str() {
+                "!" | "-" | "++" | "--" if matches!(t, Type::String | Type::Function { .. }) => {
+                    self.mismatch(&prefix.rhs, OPERAND, &t);
+                    Type::Unknown
+                }
+                "!" => Type::Boolean,
+                "-" | "++" | "--" => Type::Integer,
+                _ => Type::Unknown,
+            }
+        } else if let Some(infix) = any.downcast_ref::<InfixExpression>() {
+            self.infix_expression(infix)
+        } else if let Some(call) = any.downcast_ref::<CallExpression>() {
+            self.call_expression(call)
+        } else if let Some(if_expression) = any.downcast_ref::<IfExpression>() {
+            self.expression(&if_expression.condition);
+
+            let consequence = self.statements(&if_expression.consequence.statements);
+            let alternative = match &if_expression.alternative {
+                Some(alternative) => self.statements(&alternative.statements),
+                // the value is `null` when the condition is false
+                None => Some((Type::Null, None)),
+            };
+
+            match (consequence, alternative) {
+                (Some((consequence, _)), Some((alternative, _))) if consequence == alternative => {
+                    consequence
+                }
+                (Some((t, _)), None) | (None, Some((t, _))) => t,
+                _ => Type::Unknown,
+            }
+        } else if let Some(function) = any.downcast_ref::<FunctionLiteral>() {
+            self.function(function)
+        } else {
+            Type::Unknown
+        }
+    }
+
+    fn infix_expression(&mut self, infix: &InfixExpression) -> Type {
+        if infix.operator == "=" {
+            self.expression(&infix.lhs);
+            self.expression(&infix.rhs);
+            return Type::Unknown;
+        }
+
+        let lhs = self.expression(&infix.lhs);
+        let rhs = self.expression(&infix.rhs);
+
+        let mut valid = true;
+        for (operand, t) in [(&infix.lhs, &lhs), (&infix.rhs, &rhs)] {
+            if !t.is_operand() {
+                self.mismatch(operand, OPERAND, t);
+                valid = false;
+            }
+        }
+
+        match infix.operator.as_str() {
+            _ if !valid => Type::Unknown,
+            "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" => Type::Boolean,
+            ".." | "..=" => Type::Unknown,
+            _ => Type::Integer,
+        }
+    }
+
+    fn call_expression(&mut self, call: &CallExpression) -> Type {
+        let function = self.expression(&call.function);
+        let args: Vec<Type> = call.args.iter().map(|arg| self.expression(arg)).collect();
+        for arg in &call.named_args {
+            self.expression(&arg.value);
+        }
+
+        match function {
+            Type::Function {
+                parameters,
+                returns,
+            } => {
+                for ((arg, t), parameter) in call.args.iter().zip(&args).zip(&parameters) {
+                    if !parameter.accepts(t) {
+                        self.mismatch(arg, &format!("`{}`", parameter), t);
+                    }
+                }
+
+                *returns
+            }
+            Type::Unknown => Type::Unknown,
+            t => {
+                self.error(TypeErrorCode::NotCallable {
+                    span: call.function.span(),
+                    found: t.to_string(),
+                });
+                Type::Unknown
+            }
+        }
+    }
+
+    fn function(&mut self, function: &FunctionLiteral) -> Type {
+        self.scopes.push(Vec::new());
+        self.returns.push(Vec::new());
+
+        let mut parameters: Vec<Type> = Vec::new();
+        for parameter in &function.parameters {
+            let annotated = parameter
+                .t
+                .as_ref()
+                .and_then(|t| Type::from_annotation(&t.value).map(|expected| (t, expected)));
+
+            // defaults see the parameters before them
+            let default = parameter
+                .default
+                .as_ref()
+                .map(|default| (default, self.expression(default)));
+
+            let t = match (annotated, default) {
+                _ if parameter.rest.is_some() => Type::Unknown,
+                (Some((annotation, expected)), Some((default, found))) => {
+                    if !expected.accepts(&found) {
+                        self.error(TypeErrorCode::Mismatch {
+                            span: default.span(),
+                            expected: format!("`{}`", annotation.value),
+                            found: found.to_string(),
+                            reason: Some(annotation.span()),
+                        });
+                    }
+
+                    expected
+                }
+                (Some((_, expected)), None) => expected,
+                (None, Some((_, found))) => found,
+                (None, None) => Type::Unknown,
+            };
+
+            self.declare(
+                &parameter.identifier.token,
+                BindingKind::Parameter,
+                t.clone(),
+                parameter.t.as_ref().map(|t| t.value.clone()),
+                None,
+            );
+            parameters.push(t);
+        }
+
+        let tail = self.statements(&function.body.statements);
+        let mut returned = self.returns.pop().unwrap();
+        self.scopes.pop();
+
+        if let Some((t, span)) = tail {
+            returned.push((t, span.unwrap_or(function.body.span())));
+        }
+
+        let annotated = function
+            .return_type
+            .as_ref()
+            .and_then(|t| Type::from_annotation(&t.value).map(|expected| (t, expected)));
+
+        let returns = match annotated {
+            Some((annotation, expected)) => {
+                for (t, span) in returned {
+                    if !expected.accepts(&t) {
+                        self.error(TypeErrorCode::Mismatch {
+                            span,
+                            expected: format!("`{}`", annotation.value),
+                            found: t.to_string(),
+                            reason: Some(annotation.span()),
+                        });
+                    }
+                }
+
+                expected
+            }
+            None => match returned.split_first() {
+                Some(((first, _), rest)) if rest.iter().all(|(t, _)| t == first) => first.clone(),
+                _ => Type::Unknown,
+            },
+        };
+
+        Type::Function {
+            parameters,
+            returns: Box::new(returns),
+        }
+    }
+
+    fn identifier(&mut self, identifier: &Identifier) -> Type {
+        let found = self.scopes.iter().rev().find_map(|scope| {
+            scope
+                .iter()
+                .rev()
+                .find(|&&index| self.check.bindings[index].name.literal() == identifier.value)
+        });
+
+        match found {
+            Some(&index) => self.check.bindings[index].t.clone(),
+            None => Type::Unknown,
+        }
+    }
+
+    /// Returns the index of the binding.
+    fn declare(
+        &mut self,
+        name: &Token,
+        kind: BindingKind,
+        t: Type,
+        annotation: Option<String>,
+        value: Option<String>,
+    ) -> usize {
+        let index = self.check.bindings.len();
+        self.check.bindings.push(Binding {
+            name: name.clone(),
+            kind,
+            t,
+            annotation,
+            value,
+        });
+        self.scopes.last_mut().unwrap().push(index);
+
+        index
+    }
+
+    fn mismatch(&mut self, expression: &ASTExpression, expected: &str, found: &Type) {
+        self.error(TypeErrorCode::Mismatch {
+            span: expression.span(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+            reason: None,
+        });
+    }
+
+    fn error(&mut self, code: TypeErrorCode) {
+        self.check.errors.push(TypeError::new(code));
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,220 @@
+mod tests {
+    use crate::{
+        lexer::Lexer,
+        parser::Parser,
+        resolver::BindingKind,
+        type_checker::{check, Type, TypeCheck},
+    };
+
+    fn type_check(code: &str) -> TypeCheck {
+        let mut lexer = Lexer::new(code);
+        let mut parser = Parser::new(&mut lexer);
+        let program = parser.parse().unwrap();
+
+        check(&program)
+    }
+
+    /// The code and the primary label of every error.
+    fn errors(code: &str) -> Vec<(usize, String, String)> {
+        type_check(code)
+            .errors
+            .iter()
+            .map(|error| {
+                let diagnostic = error.to_diagnostic();
+                let label = &diagnostic.labels[0];
+
+                (
+                    error.code.id(),
+                    label.span.source_text().to_string(),
+                    label.message.clone().unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_bindings() {
+        const CODE: &str = "\
+let a = 1;
+const b: u8 = 2;
+var c = a < b;
+let s = \"eheh\";
+let add = fn(x: i32, y) -> i32 { x + y };
+let sum = add(a, 2);
+let choose = fn(x) { if (x) { 1 } else { 2 } };
+let unknown = fn(x) { if (x) { return 1; } \"two\" };";
+
+        let check = type_check(CODE);
+        let bindings: Vec<(String, BindingKind, String)> = check
+            .bindings
+            .iter()
+            .map(|binding| {
+                (
+                    binding.name.literal().to_string(),
+                    binding.kind,
+                    binding.t.to_string(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            bindings,
+            vec![
+                ("a".to_string(), BindingKind::Let, "i64".to_string()),
+                ("b".to_string(), BindingKind::Const, "i64".to_string()),
+                ("c".to_string(), BindingKind::Var, "bool".to_string()),
+                ("s".to_string(), BindingKind::Let, "string".to_string()),
+                (
+                    "add".to_string(),
+                    BindingKind::Let,
+                    "fn(i64, _) -> i64".to_string()
+                ),
+                ("x".to_string(), BindingKind::Parameter, "i64".to_string()),
+                ("y".to_string(), BindingKind::Parameter, "_".to_string()),
+                ("sum".to_string(), BindingKind::Let, "i64".to_string()),
+                (
+                    "choose".to_string(),
+                    BindingKind::Let,
+                    "fn(_) -> i64".to_string()
+                ),
+                ("x".to_string(), BindingKind::Parameter, "_".to_string()),
+                (
+                    "unknown".to_string(),
+                    BindingKind::Let,
+                    "fn(_) -> _".to_string()
+                ),
+                ("x".to_string(), BindingKind::Parameter, "_".to_string()),
+            ]
+        );
+        assert!(check.errors.is_empty());
+
+        let b = &check.bindings[1];
+        assert_eq!(b.type_name(), "u8");
+        assert_eq!(b.value.as_deref(), Some("2"));
+        assert_eq!(check.bindings[2].value, None);
+    }
+
+    #[test]
+    fn test_binding() {
+        const CODE: &str = "let a = 1;\nlet f = fn(a: bool) { a };";
+
+        let check = type_check(CODE);
+        let a = &check.bindings[2];
+        assert_eq!(a.kind, BindingKind::Parameter);
+        assert_eq!(
+            check.binding(&a.name.span).map(|binding| &binding.t),
+            Some(&Type::Boolean)
+        );
+        assert_eq!(
+            check
+                .binding(&check.bindings[0].name.span)
+                .map(|binding| &binding.t),
+            Some(&Type::Integer)
+        );
+    }
+
+    #[test]
+    fn test_recursion() {
+        const CODE: &str =
+            "let fib = fn(n: i32) -> i32 { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) };";
+
+        let check = type_check(CODE);
+        assert!(check.errors.is_empty(), "{:?}", check.errors);
+        assert_eq!(
+            check.bindings[0].t,
+            Type::Function {
+                parameters: vec![Type::Integer],
+                returns: Box::new(Type::Integer),
+            }
+        );
+    }
+
+    #[test]
+    fn test_type_errors() {
+        const CODES: [(&str, (usize, &str, &str)); 9] = [
+            (
+                "1 + \"one\";",
+                (2000, "\"one\"", "expected `i64` or `bool`, found `string`"),
+            ),
+            (
+                "let f = fn() {}; f * 2;",
+                (2000, "f", "expected `i64` or `bool`, found `fn() -> null`"),
+            ),
+            (
+                "-\"one\";",
+                (2000, "\"one\"", "expected `i64` or `bool`, found `string`"),
+            ),
+            (
+                "null == 1;",
+                (2000, "null", "expected `i64` or `bool`, found `null`"),
+            ),
+            ("let x = 5; x();", (2001, "x", "not a function")),
+            (
+                "let f = fn(a: bool) { a }; f(1);",
+                (2000, "1", "expected `bool`, found `i64`"),
+            ),
+            (
+                "let x: i32 = true;",
+                (2000, "true", "expected `i32`, found `bool`"),
+            ),
+            (
+                "fn() -> string { return 1; };",
+                (2000, "1", "expected `string`, found `i64`"),
+            ),
+            (
+                "let f = fn(x: bool = 1) { x };",
+                (2000, "1", "expected `bool`, found `i64`"),
+            ),
+        ];
+
+        for (code, (id, span, message)) in CODES {
+            assert_eq!(
+                errors(code),
+                vec![(id, span.to_string(), message.to_string())],
+                "{}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_types_are_not_reported() {
+        const CODES: [&str; 5] = [
+            "let f = fn(a) { a + 1 }; f(\"one\");",
+            "let f = fn(g) { g() }; f(1);",
+            "let x: Point = 1;",
+            "let f = fn() -> Point { 1 };",
+            "let a = if (true) { 1 } else { \"one\" }; a + 1;",
+        ];
+
+        for code in CODES {
+            assert_eq!(errors(code), vec![], "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_return_type_mismatch_points_at_the_annotation() {
+        const CODE: &str = "let f = fn(a) -> bool { if (a) { return 1; } \"two\" };";
+
+        let check = type_check(CODE);
+        let diagnostics: Vec<(String, String)> = check
+            .errors
+            .iter()
+            .map(|error| {
+                let diagnostic = error.to_diagnostic();
+                (
+                    diagnostic.labels[0].span.source_text().to_string(),
+                    diagnostic.labels[1].span.source_text().to_string(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                ("1".to_string(), "bool".to_string()),
+                ("\"two\"".to_string(), "bool".to_string()),
+            ]
+        );
+    }
+}
@@ -17,3 +17,4 @@ pub type ASTExpressionResult = ParserResult<ASTExpression>;
 
 pub type PrefixParseFn<'a> = fn(parser: &mut Parser<'a>) -> ASTExpressionResult;
 pub type InfixParseFn<'a> = fn(parser: &mut Parser<'a>, ASTExpression) -> ASTExpressionResult;
+pub type BuiltinFn = fn(args: Vec<Box<dyn Object>>) -> Box<dyn Object>;